@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS theme_overrides (
+    guild_id TEXT NOT NULL,
+    target TEXT NOT NULL,
+    colour INTEGER,
+    emoji TEXT,
+    PRIMARY KEY (guild_id, target)
+);
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS incidents (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    subject_id TEXT NOT NULL,
+    opened_at INTEGER NOT NULL,
+    last_activity INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS incident_messages (
+    incident_id INTEGER NOT NULL REFERENCES incidents (id),
+    channel_id TEXT NOT NULL,
+    message_id TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    PRIMARY KEY (incident_id, message_id)
+);
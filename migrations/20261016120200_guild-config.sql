@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS guild_config (
+    guild_id TEXT PRIMARY KEY NOT NULL,
+    timestamp_style TEXT
+);
@@ -0,0 +1 @@
+ALTER TABLE guild_config ADD COLUMN suppress_quick_self_deletes BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1 @@
+ALTER TABLE guild_config ADD COLUMN min_deleted_message_age INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1 @@
+ALTER TABLE guild_config ADD COLUMN log_system_messages BOOLEAN NOT NULL DEFAULT FALSE;
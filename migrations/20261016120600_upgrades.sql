@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS upgrades (
+    name TEXT PRIMARY KEY NOT NULL,
+    cursor INTEGER NOT NULL DEFAULT 0,
+    processed INTEGER NOT NULL DEFAULT 0,
+    completed_at INTEGER
+);
@@ -0,0 +1,27 @@
+CREATE TABLE IF NOT EXISTS guild_settings (
+    guild_id TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (guild_id, key)
+);
+
+INSERT INTO guild_settings (guild_id, key, value)
+SELECT guild_id, 'timestamp_style', timestamp_style FROM guild_config WHERE timestamp_style IS NOT NULL;
+
+INSERT INTO guild_settings (guild_id, key, value)
+SELECT guild_id, 'suppress_quick_self_deletes', 'true' FROM guild_config WHERE suppress_quick_self_deletes;
+
+INSERT INTO guild_settings (guild_id, key, value)
+SELECT guild_id, 'min_deleted_message_age', CAST(min_deleted_message_age AS TEXT) FROM guild_config WHERE min_deleted_message_age > 0;
+
+INSERT INTO guild_settings (guild_id, key, value)
+SELECT guild_id, 'log_system_messages', 'true' FROM guild_config WHERE log_system_messages;
+
+INSERT INTO guild_settings (guild_id, key, value)
+SELECT guild_id, 'theme.' || target || '.colour', CAST(colour AS TEXT) FROM theme_overrides WHERE colour IS NOT NULL;
+
+INSERT INTO guild_settings (guild_id, key, value)
+SELECT guild_id, 'theme.' || target || '.emoji', emoji FROM theme_overrides WHERE emoji IS NOT NULL;
+
+DROP TABLE guild_config;
+DROP TABLE theme_overrides;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS feature_flags (
+    -- a guild ID, or 'global' for the instance-wide default.
+    scope TEXT NOT NULL,
+    feature TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    PRIMARY KEY (scope, feature)
+);
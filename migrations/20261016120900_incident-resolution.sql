@@ -0,0 +1,3 @@
+ALTER TABLE incidents ADD COLUMN resolved_at INTEGER;
+ALTER TABLE incidents ADD COLUMN pinned_channel_id TEXT;
+ALTER TABLE incidents ADD COLUMN pinned_message_id TEXT;
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS route_mutes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    -- the log_channels column of the muted route, e.g. 'chat_logs'.
+    route TEXT NOT NULL,
+    muted_by TEXT NOT NULL,
+    reason TEXT,
+    muted_at INTEGER NOT NULL,
+    until INTEGER NOT NULL,
+    lifted_by TEXT,
+    -- whether the "logging resumed" notice has been posted.
+    resumed BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS route_mutes_active ON route_mutes (guild_id, route, until);
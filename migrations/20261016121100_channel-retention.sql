@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS channel_retention (
+    channel_id TEXT PRIMARY KEY NOT NULL,
+    guild_id TEXT NOT NULL,
+    days INTEGER NOT NULL
+);
@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS cases (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    -- numbered per guild, starting at 1.
+    case_number INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    moderator_id TEXT NOT NULL,
+    target TEXT,
+    reason TEXT,
+    created_at INTEGER NOT NULL,
+    UNIQUE (guild_id, case_number)
+);
+
+CREATE TABLE IF NOT EXISTS lockdowns (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    case_number INTEGER NOT NULL,
+    -- the @everyone overwrite before the lockdown, so it can be restored exactly. NULL if there was none.
+    previous_allow TEXT,
+    previous_deny TEXT,
+    until INTEGER,
+    lifted_at INTEGER
+);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS member_verification (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    joined_at INTEGER NOT NULL,
+    -- whether the member joined through membership screening. If not, their first role counts as verification.
+    screened BOOLEAN NOT NULL,
+    verified_at INTEGER,
+    PRIMARY KEY (guild_id, user_id)
+);
@@ -0,0 +1 @@
+ALTER TABLE member_verification ADD COLUMN first_message_at INTEGER;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS member_counts (
+    guild_id TEXT PRIMARY KEY NOT NULL,
+    member_count INTEGER NOT NULL,
+    -- the highest member count milestone already announced.
+    last_milestone INTEGER NOT NULL DEFAULT 0,
+    updated_at INTEGER NOT NULL
+);
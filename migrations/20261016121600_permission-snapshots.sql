@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS permission_snapshots (
+    channel_id TEXT PRIMARY KEY NOT NULL,
+    guild_id TEXT NOT NULL,
+    -- JSON array of the channel's permission overwrites.
+    overwrites TEXT NOT NULL,
+    taken_at INTEGER NOT NULL
+);
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS trusted_roles (
+    guild_id TEXT NOT NULL,
+    role_id TEXT NOT NULL,
+    PRIMARY KEY (guild_id, role_id)
+);
+
+-- logs about trusted members that were kept out of the log channels, for admins to review.
+CREATE TABLE IF NOT EXISTS suppressed_logs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    -- the rendered embed, as JSON.
+    embed TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS suppressed_logs_guild ON suppressed_logs (guild_id, created_at);
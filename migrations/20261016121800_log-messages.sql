@@ -0,0 +1,16 @@
+-- every log message the bot has posted, so they can be triaged and searched later.
+CREATE TABLE IF NOT EXISTS log_messages (
+    message_id TEXT PRIMARY KEY NOT NULL,
+    channel_id TEXT NOT NULL,
+    guild_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    subject_id TEXT,
+    incident_id INTEGER,
+    created_at INTEGER NOT NULL,
+    -- triage state set by moderators reacting to the log, e.g. 'investigating'.
+    status TEXT,
+    triaged_by TEXT,
+    triaged_at INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS log_messages_status ON log_messages (guild_id, status, created_at);
@@ -0,0 +1 @@
+ALTER TABLE log_channels ADD COLUMN moderation_logs TEXT;
@@ -0,0 +1,2 @@
+-- whether the appeal link was DMed to the target: 'delivered' or 'failed'. NULL if no DM was attempted.
+ALTER TABLE cases ADD COLUMN appeal_dm TEXT;
@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS oncall_rotation (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    -- order in the rotation. the active pointer (the `oncall_pointer` setting) indexes into it.
+    position INTEGER NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS oncall_pages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    -- the alert that triggered the page.
+    channel_id TEXT,
+    message_id TEXT,
+    created_at INTEGER NOT NULL,
+    -- how many moderators have been paged so far, counting from the pointer.
+    attempts INTEGER NOT NULL,
+    last_paged_at INTEGER NOT NULL,
+    acknowledged_by TEXT,
+    acknowledged_at INTEGER
+);
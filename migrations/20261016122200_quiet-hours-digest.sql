@@ -0,0 +1,11 @@
+-- low-severity logs held back during a route's quiet hours, posted as a digest once they end.
+CREATE TABLE IF NOT EXISTS digest_entries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    route TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    -- the styled log embed, as JSON.
+    embed TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
@@ -0,0 +1,4 @@
+-- the message a log is about (e.g. the deleted or edited message), so logs can be found from a message link.
+ALTER TABLE log_messages ADD COLUMN about_message_id TEXT;
+
+CREATE INDEX IF NOT EXISTS log_messages_about ON log_messages (about_message_id);
@@ -0,0 +1,15 @@
+ALTER TABLE log_channels ADD COLUMN report_logs TEXT;
+
+-- messages members reported with the "Report to mods" context menu.
+CREATE TABLE IF NOT EXISTS user_reports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    -- kept for abuse tracking, but never shown in the report log itself.
+    reporter_id TEXT NOT NULL,
+    reported_user_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    message_id TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    -- the report's log message, whose triage status says how the report was handled.
+    log_message_id TEXT
+);
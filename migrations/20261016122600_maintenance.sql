@@ -0,0 +1,21 @@
+-- scopes (a guild ID, or 'global' for the whole instance) whose log deliveries are paused for maintenance.
+CREATE TABLE IF NOT EXISTS maintenance (
+    scope TEXT PRIMARY KEY NOT NULL,
+    started_by TEXT NOT NULL,
+    started_at INTEGER NOT NULL
+);
+
+-- logs that would have been posted during maintenance, in the order they happened.
+CREATE TABLE IF NOT EXISTS maintenance_queue (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    -- the log kind, for logs that are tracked in log_messages once posted.
+    kind TEXT,
+    subject_id TEXT,
+    about_message_id TEXT,
+    incident_id INTEGER,
+    -- the message and its followups, as a JSON array of message payloads.
+    messages TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
@@ -0,0 +1,9 @@
+-- how often each of a guild's custom emojis was used in messages, bucketed by day.
+CREATE TABLE IF NOT EXISTS emoji_usage (
+    guild_id TEXT NOT NULL,
+    emoji_id TEXT NOT NULL,
+    -- days since the unix epoch.
+    day INTEGER NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (guild_id, emoji_id, day)
+);
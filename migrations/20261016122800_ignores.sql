@@ -0,0 +1,10 @@
+-- authors whose messages are never logged, per guild or for the whole instance ('global').
+CREATE TABLE IF NOT EXISTS ignores (
+    scope TEXT NOT NULL,
+    -- 'user' for a user or bot ID, 'application' for the application behind webhook and interaction messages.
+    kind TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    added_by TEXT NOT NULL,
+    added_at INTEGER NOT NULL,
+    PRIMARY KEY (scope, kind, target_id)
+);
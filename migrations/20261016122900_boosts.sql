@@ -0,0 +1,9 @@
+-- periods members spent boosting, for logging how long they boosted in total.
+CREATE TABLE IF NOT EXISTS boosts (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    started_at INTEGER NOT NULL,
+    -- NULL while the member is still boosting.
+    ended_at INTEGER,
+    PRIMARY KEY (guild_id, user_id, started_at)
+);
@@ -0,0 +1,10 @@
+-- named copies of a guild's configuration, taken with /config snapshot and restored with /config rollback.
+CREATE TABLE IF NOT EXISTS config_snapshots (
+    guild_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    -- the configuration rows, as a JSON object of table name to rows.
+    data TEXT NOT NULL,
+    PRIMARY KEY (guild_id, name)
+);
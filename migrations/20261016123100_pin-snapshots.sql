@@ -0,0 +1,8 @@
+-- the last known pins of each channel, diffed against on pin updates to tell which message changed.
+CREATE TABLE IF NOT EXISTS pin_snapshots (
+    channel_id TEXT PRIMARY KEY NOT NULL,
+    guild_id TEXT NOT NULL,
+    -- JSON array of the pinned message IDs.
+    message_ids TEXT NOT NULL,
+    taken_at INTEGER NOT NULL
+);
@@ -0,0 +1,9 @@
+-- how many logs of each kind a guild produced, bucketed by hour, as the baseline for anomaly alerts.
+CREATE TABLE IF NOT EXISTS event_counts (
+    guild_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    -- hours since the unix epoch.
+    hour INTEGER NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (guild_id, kind, hour)
+);
@@ -0,0 +1,11 @@
+-- full contents of long edited messages, revealed on demand from the compact edit log's buttons.
+CREATE TABLE IF NOT EXISTS edit_versions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    message_id TEXT NOT NULL,
+    author_id TEXT NOT NULL,
+    previous TEXT NOT NULL,
+    new TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
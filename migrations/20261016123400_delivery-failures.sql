@@ -0,0 +1,6 @@
+-- how many logs failed to post across the instance, bucketed by hour, for the operator dashboard.
+CREATE TABLE IF NOT EXISTS delivery_failures (
+    -- hours since the unix epoch.
+    hour INTEGER PRIMARY KEY NOT NULL,
+    count INTEGER NOT NULL
+);
@@ -0,0 +1,9 @@
+-- audit log entries that were already handed to the formatters, from the gateway or by polling,
+-- so polling doesn't log them a second time.
+CREATE TABLE IF NOT EXISTS seen_audit_entries (
+    entry_id TEXT PRIMARY KEY NOT NULL,
+    guild_id TEXT NOT NULL,
+    seen_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS seen_audit_entries_guild ON seen_audit_entries (guild_id);
@@ -0,0 +1,4 @@
+-- where the log of each case was posted, so the log can be edited along with the case. NULL for cases
+-- whose log wasn't posted, e.g. lockdowns or logs queued for maintenance.
+ALTER TABLE cases ADD COLUMN log_channel_id TEXT;
+ALTER TABLE cases ADD COLUMN log_message_id TEXT;
@@ -0,0 +1,13 @@
+-- moderation actions attributed to a moderator through the audit log, for /modstats.
+CREATE TABLE IF NOT EXISTS moderator_actions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    moderator_id TEXT NOT NULL,
+    -- 'ban', 'kick', 'timeout' or 'delete'.
+    action TEXT NOT NULL,
+    -- how many times the action was taken at once, e.g. the number of messages in a purge.
+    count INTEGER NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS moderator_actions_guild ON moderator_actions (guild_id, created_at);
@@ -0,0 +1,18 @@
+use serenity::all::{GuildId, User};
+
+use crate::settings::{keys, Settings};
+
+/// Fills in `{user_id}`, `{user}` and `{server_id}` in an appeal URL template.
+pub fn render(template: &str, guild_id: GuildId, user: &User) -> String {
+    template
+        .replace("{user_id}", &user.id.to_string())
+        .replace("{user}", &user.name)
+        .replace("{server_id}", &guild_id.to_string())
+}
+
+/// The appeal link for `user`, if the guild configured one.
+pub async fn link(settings: &Settings, guild_id: GuildId, user: &User) -> Option<String> {
+    let template = settings.get(guild_id, &keys::APPEAL_URL).await;
+
+    (!template.is_empty()).then(|| render(&template, guild_id, user))
+}
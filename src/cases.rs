@@ -0,0 +1,219 @@
+use std::str::FromStr;
+
+use serenity::all::{ChannelId, GuildId, MessageId, UserId};
+use sqlx::{Pool, Sqlite};
+
+use crate::logging::now;
+
+/// Records a moderation action and returns its guild-local case number.
+/// `target` is a user ID for actions against a member, or a description like `<#channel>` otherwise.
+pub async fn open(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    action: &str,
+    moderator: UserId,
+    target: Option<String>,
+    reason: Option<String>,
+) -> Result<i64, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let moderator = moderator.to_string();
+    let now = now() as i64;
+
+    let row = sqlx::query!(
+        r#"INSERT INTO cases (guild_id, case_number, action, moderator_id, target, reason, created_at)
+        VALUES (?1, (SELECT COALESCE(MAX(case_number), 0) + 1 FROM cases WHERE guild_id = ?1), ?2, ?3, ?4, ?5, ?6)
+        RETURNING case_number"#,
+        guild_id,
+        action,
+        moderator,
+        target,
+        reason,
+        now
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.case_number)
+}
+
+/// Records whether the appeal link could be DMed to the target of a case.
+pub async fn record_appeal_dm(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    case_number: i64,
+    delivered: bool,
+) -> Result<(), sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let appeal_dm = if delivered { "delivered" } else { "failed" };
+
+    sqlx::query!(
+        "UPDATE cases SET appeal_dm = ? WHERE guild_id = ? AND case_number = ?",
+        appeal_dm,
+        guild_id,
+        case_number
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Remembers where the log of a case was posted.
+pub async fn link_log(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    case_number: i64,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> Result<(), sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let channel_id = channel_id.to_string();
+    let message_id = message_id.to_string();
+
+    sqlx::query!(
+        "UPDATE cases SET log_channel_id = ?, log_message_id = ? WHERE guild_id = ? AND case_number = ?",
+        channel_id,
+        message_id,
+        guild_id,
+        case_number
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Replaces the reason of a case. Returns `false` if there's no such case.
+pub async fn set_reason(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    case_number: i64,
+    reason: &str,
+) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    let result = sqlx::query!(
+        "UPDATE cases SET reason = ? WHERE guild_id = ? AND case_number = ?",
+        reason,
+        guild_id,
+        case_number
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub struct Case {
+    pub case_number: i64,
+    pub action: String,
+    pub moderator: UserId,
+    /// A user ID, or a description like `<#channel>`. See [`open`].
+    pub target: Option<String>,
+    pub reason: Option<String>,
+    pub created_at: i64,
+    /// Where the case's log was posted, if it was.
+    pub log_message: Option<(ChannelId, MessageId)>,
+}
+
+fn log_message(
+    channel_id: Option<String>,
+    message_id: Option<String>,
+) -> Option<(ChannelId, MessageId)> {
+    Some((
+        ChannelId::from_str(&channel_id?).ok()?,
+        MessageId::from_str(&message_id?).ok()?,
+    ))
+}
+
+pub async fn get(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    case_number: i64,
+) -> Result<Option<Case>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    Ok(sqlx::query!(
+        "SELECT case_number, action, moderator_id, target, reason, created_at, log_channel_id, log_message_id
+        FROM cases WHERE guild_id = ? AND case_number = ?",
+        guild_id,
+        case_number
+    )
+    .fetch_optional(pool)
+    .await?
+    .and_then(|row| {
+        Some(Case {
+            case_number: row.case_number,
+            action: row.action,
+            moderator: UserId::from_str(&row.moderator_id).ok()?,
+            target: row.target,
+            reason: row.reason,
+            created_at: row.created_at,
+            log_message: log_message(row.log_channel_id, row.log_message_id),
+        })
+    }))
+}
+
+/// The latest case against `target` opened since `since`.
+pub async fn latest_for_target(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    target: &str,
+    since: i64,
+) -> Result<Option<Case>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    Ok(sqlx::query!(
+        "SELECT case_number, action, moderator_id, target, reason, created_at, log_channel_id, log_message_id
+        FROM cases WHERE guild_id = ? AND target = ? AND created_at >= ? ORDER BY case_number DESC LIMIT 1",
+        guild_id,
+        target,
+        since
+    )
+    .fetch_optional(pool)
+    .await?
+    .and_then(|row| {
+        Some(Case {
+            case_number: row.case_number,
+            action: row.action,
+            moderator: UserId::from_str(&row.moderator_id).ok()?,
+            target: row.target,
+            reason: row.reason,
+            created_at: row.created_at,
+            log_message: log_message(row.log_channel_id, row.log_message_id),
+        })
+    }))
+}
+
+/// The most recent cases against `target`, newest first.
+pub async fn for_target(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    target: &str,
+    limit: i64,
+) -> Result<Vec<Case>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    Ok(sqlx::query!(
+        "SELECT case_number, action, moderator_id, target, reason, created_at, log_channel_id, log_message_id
+        FROM cases WHERE guild_id = ? AND target = ? ORDER BY case_number DESC LIMIT ?",
+        guild_id,
+        target,
+        limit
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter_map(|row| {
+        Some(Case {
+            case_number: row.case_number,
+            action: row.action,
+            moderator: UserId::from_str(&row.moderator_id).ok()?,
+            target: row.target,
+            reason: row.reason,
+            created_at: row.created_at,
+            log_message: log_message(row.log_channel_id, row.log_message_id),
+        })
+    })
+    .collect())
+}
@@ -1,37 +1,102 @@
 use poise::{
-    serenity_prelude::{Guild, GuildId},
+    serenity_prelude::{Emoji, EmojiId, FullEvent, Guild, GuildId, Sticker, StickerId},
     FrameworkBuilder,
 };
-use serenity::{cache::Settings, prelude::*};
+use serenity::{cache::Settings as CacheSettings, prelude::*};
 use sqlx::{Pool, Sqlite};
 
+use std::sync::Arc;
+
+use crate::{
+    logging::{
+        bulk_roles::BulkRoles, cache_boost::CacheBoost, damping::Damper,
+        housekeeping::Housekeeping, snapshots::Snapshots, soundboard::SoundboardSound,
+        voice_hops::VoiceHops, FormatterRegistry,
+    },
+    settings::Settings,
+};
+
 pub(crate) type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
 pub(crate) type Context<'a> = poise::Context<'a, Data, Error>;
 
+#[derive(Clone)]
 pub struct Data {
     pub pool: sqlx::Pool<sqlx::Sqlite>,
+    pub formatters: Arc<FormatterRegistry>,
+    pub damper: Arc<Damper>,
+    pub bulk_roles: Arc<BulkRoles>,
+    pub voice_hops: Arc<VoiceHops>,
+    pub housekeeping: Arc<Housekeeping>,
+    pub cache_boost: Arc<CacheBoost>,
+    pub emojis: Arc<Snapshots<EmojiId, Emoji>>,
+    pub stickers: Arc<Snapshots<StickerId, Sticker>>,
+    pub sounds: Arc<Snapshots<u64, SoundboardSound>>,
+    pub settings: Settings,
 }
 
 impl Data {
     pub fn new(pool: sqlx::Pool<sqlx::Sqlite>) -> Self {
-        Self { pool }
+        Self {
+            settings: Settings::new(pool.clone()),
+            pool,
+            formatters: Arc::new(FormatterRegistry::new()),
+            damper: Arc::new(Damper::default()),
+            bulk_roles: Arc::new(BulkRoles::default()),
+            voice_hops: Arc::new(VoiceHops::default()),
+            housekeeping: Arc::new(Housekeeping::default()),
+            cache_boost: Arc::new(CacheBoost::default()),
+            emojis: Arc::new(Snapshots::default()),
+            stickers: Arc::new(Snapshots::default()),
+            sounds: Arc::new(Snapshots::default()),
+        }
     }
 }
 
+// lets raw event handlers, which poise doesn't pass its data to, find it in the context instead.
+impl TypeMapKey for Data {
+    type Value = Data;
+}
+
 pub async fn get_framework_builder(pool: Pool<Sqlite>) -> FrameworkBuilder<Data, Error> {
     let framework_options = poise::FrameworkOptions {
-        commands: vec![crate::commands::channels()],
+        commands: vec![
+            crate::commands::announce(),
+            crate::commands::cacheboost(),
+            crate::commands::case(),
+            crate::commands::channels(),
+            crate::commands::config(),
+            crate::commands::coverage(),
+            crate::commands::dashboard(),
+            crate::commands::drift(),
+            crate::commands::emojistats(),
+            crate::commands::features(),
+            crate::commands::history(),
+            crate::commands::ignores(),
+            crate::commands::incident(),
+            crate::commands::language(),
+            crate::commands::lockdown(),
+            crate::commands::maintenance(),
+            crate::commands::modstats(),
+            crate::commands::mute(),
+            crate::commands::mydata(),
+            crate::commands::oncall(),
+            crate::commands::panic(),
+            crate::commands::quiet_hours(),
+            crate::commands::report(),
+            crate::commands::resolve(),
+            crate::commands::retention(),
+            crate::commands::search(),
+            crate::commands::template(),
+            crate::commands::theme(),
+            crate::commands::trusted(),
+            crate::commands::welcome(),
+        ],
         prefix_options: poise::PrefixFrameworkOptions {
             prefix: None,
             ..Default::default()
         },
         event_handler: |ctx, event, framework_ctx, data| {
-            Box::pin(crate::logging::handle_logging_events(
-                ctx,
-                event,
-                framework_ctx,
-                data,
-            ))
+            Box::pin(on_event(ctx, event, framework_ctx, data))
         },
         on_error: |error| Box::pin(on_error(error)),
         ..Default::default()
@@ -65,28 +130,151 @@ pub async fn get_framework_builder(pool: Pool<Sqlite>) -> FrameworkBuilder<Data,
                     .unwrap();
                 }
 
-                Ok(Data::new(pool))
+                let data = Data::new(pool);
+                ctx.data.write().await.insert::<Data>(data.clone());
+
+                tokio::spawn(crate::logging::damping::flush_summaries(
+                    ctx.clone(),
+                    data.clone(),
+                ));
+
+                tokio::spawn(crate::logging::bulk_roles::flush(ctx.clone(), data.clone()));
+
+                tokio::spawn(crate::retention::enforce(
+                    ctx.http.clone(),
+                    data.pool.clone(),
+                    data.settings.clone(),
+                    data.housekeeping.clone(),
+                ));
+
+                tokio::spawn(crate::logging::cache_boost::expire(
+                    ctx.http.clone(),
+                    data.pool.clone(),
+                    data.settings.clone(),
+                    data.cache_boost.clone(),
+                ));
+
+                tokio::spawn(crate::lockdown::revert_expired(
+                    ctx.http.clone(),
+                    data.pool.clone(),
+                    data.settings.clone(),
+                ));
+
+                tokio::spawn(crate::logging::drift::check(ctx.clone(), data.clone()));
+
+                tokio::spawn(crate::logging::anomalies::check(ctx.clone(), data.clone()));
+
+                tokio::spawn(crate::reports::post_weekly(
+                    ctx.http.clone(),
+                    data.pool.clone(),
+                    data.settings.clone(),
+                ));
+
+                tokio::spawn(crate::logging::mutes::announce_resumed(
+                    ctx.http.clone(),
+                    data.pool.clone(),
+                    data.settings.clone(),
+                ));
+
+                tokio::spawn(crate::logging::quiet_hours::flush(
+                    ctx.http.clone(),
+                    data.pool.clone(),
+                    data.settings.clone(),
+                ));
+
+                tokio::spawn(crate::logging::maintenance::flush(
+                    ctx.http.clone(),
+                    data.pool.clone(),
+                ));
+
+                tokio::spawn(crate::logging::edit_versions::expire(data.pool.clone()));
+
+                tokio::spawn(crate::member_counts::reconcile(
+                    ctx.http.clone(),
+                    data.pool.clone(),
+                    data.settings.clone(),
+                ));
+
+                tokio::spawn(crate::oncall::escalate(
+                    ctx.http.clone(),
+                    data.pool.clone(),
+                    data.settings.clone(),
+                ));
+
+                tokio::spawn(crate::logging::audit_poll::poll(ctx.clone(), data.clone()));
+
+                Ok(data)
             })
         })
 }
 
+/// The gateway intents the bot connects with.
+pub(crate) fn intents() -> GatewayIntents {
+    GatewayIntents::GUILDS
+        | GatewayIntents::GUILD_MEMBERS
+        | GatewayIntents::GUILD_MESSAGES
+        | GatewayIntents::MESSAGE_CONTENT
+        | GatewayIntents::GUILD_VOICE_STATES
+        | GatewayIntents::GUILD_MESSAGE_REACTIONS
+        | GatewayIntents::GUILD_MODERATION
+        | GatewayIntents::GUILD_EMOJIS_AND_STICKERS
+        | GatewayIntents::GUILD_INVITES
+        | GatewayIntents::GUILD_INTEGRATIONS
+        | GatewayIntents::AUTO_MODERATION_CONFIGURATION
+}
+
 pub async fn get_client(pool: sqlx::Pool<Sqlite>) -> serenity::Client {
     let token = std::env::var("DISCORD_API_TOKEN")
         .unwrap_or_else(|_| panic!("Discord API token not present in environment. Double-check that DISCORD_API_TOKEN is set and restart."));
 
-    let mut cache_settings = Settings::default();
+    let mut cache_settings = CacheSettings::default();
     cache_settings.max_messages = 250;
 
-    serenity::Client::builder(
-        token,
-        GatewayIntents::GUILD_MEMBERS
-            | GatewayIntents::GUILD_MESSAGES
-            | GatewayIntents::MESSAGE_CONTENT,
-    )
-    .cache_settings(cache_settings)
-    .framework(get_framework_builder(pool).await.build())
-    .await
-    .unwrap()
+    serenity::Client::builder(token, intents())
+        .cache_settings(cache_settings)
+        .framework(get_framework_builder(pool).await.build())
+        .raw_event_handler(crate::logging::soundboard::SoundboardEvents)
+        .await
+        .unwrap()
+}
+
+async fn on_event(
+    ctx: &serenity::client::Context,
+    event: &FullEvent,
+    framework_ctx: poise::FrameworkContext<'_, Data, Error>,
+    data: &Data,
+) -> Result<(), Error> {
+    if let FullEvent::GuildCreate {
+        guild,
+        is_new: Some(true),
+    } = event
+    {
+        tokio::spawn(crate::onboarding::start(
+            ctx.clone(),
+            data.pool.clone(),
+            guild.clone(),
+        ));
+    }
+
+    // the member count is adjusted first, so welcomes show the count including the new member.
+    if let Err(error) = crate::member_counts::on_event(ctx, event, data).await {
+        println!("Failed to update member counts: {error}");
+    }
+
+    if let FullEvent::GuildMemberAddition { new_member } = event {
+        crate::welcomes::greet(ctx, data, new_member).await;
+    }
+
+    // none of these should keep the event from being logged.
+    if let Err(error) = crate::emoji_stats::on_event(ctx, event, data).await {
+        println!("Failed to record emoji usage: {error}");
+    }
+
+    if let Err(error) = crate::oncall::on_event(ctx, event, data).await {
+        println!("Failed to handle on-call acknowledgement: {error}");
+    }
+
+    crate::logging::handle_logging_events(ctx, event, framework_ctx, data).await
 }
 
 async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
@@ -1,9 +1,75 @@
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
-use poise::serenity_prelude::*;
+use poise::{serenity_prelude::*, CreateReply};
 use sqlx::{prelude::*, Pool, Sqlite};
 
-use crate::client::{Context, Error};
+use crate::{
+    client::{Context, Error},
+    logging::{channel_export, routing::Routing},
+};
+
+/// How long the offer to export a log channel's history stays open.
+const EXPORT_PROMPT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+mod announce;
+mod cacheboost;
+mod case;
+mod config;
+mod coverage;
+mod dashboard;
+mod drift;
+mod emojistats;
+mod features;
+mod history;
+mod ignores;
+mod incident;
+mod language;
+mod lockdown;
+mod maintenance;
+mod modstats;
+mod mute;
+mod mydata;
+mod oncall;
+mod panic;
+mod quiet_hours;
+mod report;
+mod resolve;
+mod retention;
+mod search;
+mod template;
+mod theme;
+mod trusted;
+mod welcome;
+
+pub use announce::announce;
+pub use cacheboost::cacheboost;
+pub use case::case;
+pub use config::config;
+pub use coverage::coverage;
+pub use dashboard::dashboard;
+pub use drift::drift;
+pub use emojistats::emojistats;
+pub use features::features;
+pub use history::history;
+pub use ignores::ignores;
+pub use incident::incident;
+pub use language::language;
+pub use lockdown::lockdown;
+pub use maintenance::maintenance;
+pub use modstats::modstats;
+pub use mute::mute;
+pub use mydata::mydata;
+pub use oncall::oncall;
+pub use panic::panic;
+pub use quiet_hours::quiet_hours;
+pub use report::report;
+pub use resolve::resolve;
+pub use retention::retention;
+pub use search::search;
+pub use template::template;
+pub use theme::theme;
+pub use trusted::trusted;
+pub use welcome::welcome;
 
 #[derive(FromRow)]
 struct LogChannels {
@@ -11,6 +77,9 @@ struct LogChannels {
     member_logs: Option<String>,
     chat_logs: Option<String>,
     server_logs: Option<String>,
+    moderation_logs: Option<String>,
+    report_logs: Option<String>,
+    voice_logs: Option<String>,
 }
 
 impl LogChannels {
@@ -36,18 +105,40 @@ impl LogChannels {
             .and_then(|id| ChannelId::from_str(id).ok())
     }
 
+    pub fn moderation_logs(&self) -> Option<ChannelId> {
+        self.moderation_logs
+            .as_ref()
+            .and_then(|id| ChannelId::from_str(id).ok())
+    }
+
+    pub fn report_logs(&self) -> Option<ChannelId> {
+        self.report_logs
+            .as_ref()
+            .and_then(|id| ChannelId::from_str(id).ok())
+    }
+
+    pub fn voice_logs(&self) -> Option<ChannelId> {
+        self.voice_logs
+            .as_ref()
+            .and_then(|id| ChannelId::from_str(id).ok())
+    }
+
     pub fn new(guild_id: GuildId) -> Self {
         Self {
             guild_id: guild_id.to_string(),
             member_logs: None,
             chat_logs: None,
             server_logs: None,
+            moderation_logs: None,
+            report_logs: None,
+            voice_logs: None,
         }
     }
 
     pub async fn insert_default(pool: &Pool<Sqlite>, guild_id: String) {
+        // guild_id isn't actually a primary key (see the initial migration), so ON CONFLICT wouldn't catch duplicates.
         sqlx::query!(
-            "INSERT INTO log_channels (guild_id) VALUES (?) ON CONFLICT DO NOTHING",
+            "INSERT INTO log_channels (guild_id) SELECT ?1 WHERE NOT EXISTS (SELECT 1 FROM log_channels WHERE guild_id = ?1)",
             guild_id
         )
         .execute(pool)
@@ -66,7 +157,7 @@ pub async fn channels(_: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-#[derive(Debug, poise::ChoiceParameter, Clone, Copy)]
+#[derive(Debug, poise::ChoiceParameter, Clone, Copy, PartialEq, Eq)]
 pub enum LogType {
     #[name = "Member Logs"]
     Member,
@@ -74,14 +165,44 @@ pub enum LogType {
     Chat,
     #[name = "Server Logs"]
     Server,
+    #[name = "Moderation Logs"]
+    Moderation,
+    #[name = "Report Logs"]
+    Reports,
+    #[name = "Voice Logs"]
+    Voice,
 }
 
 impl LogType {
-    pub(crate) fn as_column_name(&self) -> &str {
+    pub const ALL: [Self; 6] = [
+        Self::Member,
+        Self::Chat,
+        Self::Server,
+        Self::Moderation,
+        Self::Reports,
+        Self::Voice,
+    ];
+
+    pub(crate) fn as_column_name(&self) -> &'static str {
         match self {
             Self::Member => "member_logs",
             Self::Chat => "chat_logs",
             Self::Server => "server_logs",
+            Self::Moderation => "moderation_logs",
+            Self::Reports => "report_logs",
+            Self::Voice => "voice_logs",
+        }
+    }
+
+    pub(crate) fn from_column_name(column_name: &str) -> Option<Self> {
+        match column_name {
+            "member_logs" => Some(Self::Member),
+            "chat_logs" => Some(Self::Chat),
+            "server_logs" => Some(Self::Server),
+            "moderation_logs" => Some(Self::Moderation),
+            "report_logs" => Some(Self::Reports),
+            "voice_logs" => Some(Self::Voice),
+            _ => None,
         }
     }
 
@@ -104,6 +225,69 @@ impl LogType {
 
         ChannelId::from_str(id).ok()
     }
+
+    pub(crate) async fn store_channel(
+        &self,
+        pool: &Pool<Sqlite>,
+        guild_id: GuildId,
+        channel: Option<ChannelId>,
+    ) -> Result<(), sqlx::Error> {
+        use LogType as C;
+
+        let guild_id = guild_id.to_string();
+        let value = channel.map(|id| id.to_string());
+
+        LogChannels::insert_default(pool, guild_id.clone()).await;
+
+        (match self {
+            C::Member => {
+                sqlx::query!(
+                    "UPDATE log_channels SET member_logs = ? WHERE guild_id = ?",
+                    value,
+                    guild_id
+                )
+            }
+            C::Chat => {
+                sqlx::query!(
+                    "UPDATE log_channels SET chat_logs = ? WHERE guild_id = ?",
+                    value,
+                    guild_id
+                )
+            }
+            C::Server => {
+                sqlx::query!(
+                    "UPDATE log_channels SET server_logs = ? WHERE guild_id = ?",
+                    value,
+                    guild_id
+                )
+            }
+            C::Moderation => {
+                sqlx::query!(
+                    "UPDATE log_channels SET moderation_logs = ? WHERE guild_id = ?",
+                    value,
+                    guild_id
+                )
+            }
+            C::Reports => {
+                sqlx::query!(
+                    "UPDATE log_channels SET report_logs = ? WHERE guild_id = ?",
+                    value,
+                    guild_id
+                )
+            }
+            C::Voice => {
+                sqlx::query!(
+                    "UPDATE log_channels SET voice_logs = ? WHERE guild_id = ?",
+                    value,
+                    guild_id
+                )
+            }
+        })
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
 }
 
 impl ToString for LogType {
@@ -112,48 +296,80 @@ impl ToString for LogType {
             Self::Member => "Member Logs".into(),
             Self::Chat => "Chat Logs".into(),
             Self::Server => "Server Logs".into(),
+            Self::Moderation => "Moderation Logs".into(),
+            Self::Reports => "Report Logs".into(),
+            Self::Voice => "Voice Logs".into(),
         }
     }
 }
 
+/// Follows up on a routing change with an ephemeral summary of which kinds of log are now delivered elsewhere,
+/// so a misconfiguration shows up right away rather than when something goes missing.
+pub(crate) async fn preview_routing(ctx: Context<'_>, before: Routing) -> Result<(), Error> {
+    let after = Routing::snapshot(ctx.data(), ctx.guild_id().unwrap(), before.language).await?;
+    let changes = before.changes(&after);
+
+    let description = if changes.is_empty() {
+        "No kinds of log changed where they're delivered.".to_string()
+    } else {
+        changes.join("\n")
+    };
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::new()
+                    .title(match before.language {
+                        Some(language) => {
+                            format!("Delivery preview for {} content", language.eng_name())
+                        }
+                        None => "Delivery preview".to_string(),
+                    })
+                    .description(description),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Parses durations like `90s`, `30m`, `2h` or `1d` into seconds. A bare number is taken as minutes.
+pub(crate) fn parse_duration(input: &str) -> Option<u64> {
+    let input = input.trim();
+    let (amount, unit) = input.split_at(
+        input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len()),
+    );
+    let amount = amount.parse::<u64>().ok().filter(|amount| *amount > 0)?;
+
+    let multiplier = match unit.trim() {
+        "s" => 1,
+        "" | "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return None,
+    };
+
+    amount.checked_mul(multiplier)
+}
+
 #[poise::command(slash_command)]
 async fn set(
     ctx: Context<'_>,
     log_type: LogType,
     #[channel_types("Text")] channel: Option<ChannelId>,
 ) -> Result<(), Error> {
-    use LogType as C;
-
     let pool = &ctx.data().pool;
+    let before = Routing::snapshot(ctx.data(), ctx.guild_id().unwrap(), None).await?;
+    let previous = log_type.fetch_channel(pool, ctx.guild_id().unwrap()).await;
 
-    let guild_id = ctx.guild_id().unwrap().to_string();
-    let value = channel.map(|id| id.to_string());
+    log_type
+        .store_channel(pool, ctx.guild_id().unwrap(), channel)
+        .await?;
 
-    (match log_type {
-        C::Member => {
-            sqlx::query!(
-                "UPDATE log_channels SET member_logs = ? WHERE guild_id = ?",
-                value,
-                guild_id
-            )
-        }
-        C::Chat => {
-            sqlx::query!(
-                "UPDATE log_channels SET chat_logs = ? WHERE guild_id = ?",
-                value,
-                guild_id
-            )
-        }
-        C::Server => {
-            sqlx::query!(
-                "UPDATE log_channels SET server_logs = ? WHERE guild_id = ?",
-                value,
-                guild_id
-            )
-        }
-    })
-    .execute(pool)
-    .await?;
+    let value = channel.map(|id| id.to_string());
 
     match value {
         None => ctx.reply(format!("Unset {}", log_type.to_string())),
@@ -166,6 +382,89 @@ async fn set(
     .await
     .unwrap();
 
+    preview_routing(ctx, before).await?;
+
+    match previous {
+        Some(previous) if Some(previous) != channel => offer_export(ctx, previous).await,
+        _ => Ok(()),
+    }
+}
+
+/// Asks whether to export the logs in a channel that just stopped receiving them, e.g. before it's deleted,
+/// and sends the export if so.
+async fn offer_export(ctx: Context<'_>, previous: ChannelId) -> Result<(), Error> {
+    let export_id = format!("{}:export", ctx.id());
+    let skip_id = format!("{}:skip", ctx.id());
+
+    let prompt = ctx
+        .send(
+            CreateReply::default()
+                .content(format!(
+                    "<#{previous}> no longer receives these logs. Export its log history before repurposing it?"
+                ))
+                .components(vec![CreateActionRow::Buttons(vec![
+                    CreateButton::new(&export_id)
+                        .label("Export")
+                        .style(ButtonStyle::Primary),
+                    CreateButton::new(&skip_id)
+                        .label("No thanks")
+                        .style(ButtonStyle::Secondary),
+                ])])
+                .ephemeral(true),
+        )
+        .await?;
+
+    let interaction = prompt
+        .message()
+        .await?
+        .await_component_interaction(ctx.serenity_context())
+        .author_id(ctx.author().id)
+        .timeout(EXPORT_PROMPT_TIMEOUT)
+        .await;
+
+    let Some(interaction) =
+        interaction.filter(|interaction| interaction.data.custom_id == export_id)
+    else {
+        prompt
+            .edit(
+                ctx,
+                CreateReply::default()
+                    .content(format!("<#{previous}>'s log history wasn't exported."))
+                    .components(Vec::new()),
+            )
+            .await?;
+        return Ok(());
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(format!("Exporting <#{previous}>'s log history..."))
+                    .components(Vec::new()),
+            ),
+        )
+        .await?;
+
+    let bot_id = ctx.cache().current_user().id;
+
+    let followup = match channel_export::export(ctx.http(), previous, bot_id).await {
+        Ok((archive, count)) => CreateInteractionResponseFollowup::new()
+            .content(format!("Exported {count} logs from <#{previous}>."))
+            .add_file(CreateAttachment::bytes(
+                archive.into_bytes(),
+                format!("logs-{previous}.txt"),
+            )),
+        Err(error) => CreateInteractionResponseFollowup::new().content(format!(
+            "Couldn't export <#{previous}>'s log history: {error}"
+        )),
+    };
+
+    interaction
+        .create_followup(ctx, followup.ephemeral(true))
+        .await?;
+
     Ok(())
 }
 
@@ -194,7 +493,7 @@ async fn list(ctx: Context<'_>) -> Result<(), Error> {
     let guild_name = log_channels.guild_id().name(ctx).unwrap();
 
     ctx.reply(format!(
-        "Log channels for {guild_name}\nMember logs: <#{}>\nChat logs: <#{}>\nServer logs: <#{}>",
+        "Log channels for {guild_name}\nMember logs: <#{}>\nChat logs: <#{}>\nServer logs: <#{}>\nModeration logs: <#{}>\nReport logs: <#{}>\nVoice logs: <#{}>",
         log_channels
             .member_logs()
             .map(|id| id.to_string())
@@ -206,6 +505,18 @@ async fn list(ctx: Context<'_>) -> Result<(), Error> {
         log_channels
             .server_logs()
             .map(|id| id.to_string())
+            .unwrap_or("None".into()),
+        log_channels
+            .moderation_logs()
+            .map(|id| id.to_string())
+            .unwrap_or("None".into()),
+        log_channels
+            .report_logs()
+            .map(|id| id.to_string())
+            .unwrap_or("None".into()),
+        log_channels
+            .voice_logs()
+            .map(|id| id.to_string())
             .unwrap_or("None".into())
     ))
     .await?;
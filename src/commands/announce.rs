@@ -0,0 +1,89 @@
+use poise::serenity_prelude::*;
+
+use crate::{
+    client::{Context, Error},
+    commands::LogType,
+    settings::keys,
+};
+
+#[derive(Debug, poise::ChoiceParameter, Clone, Copy)]
+pub enum AnnouncementTarget {
+    #[name = "Server log channels"]
+    ServerLogs,
+    #[name = "Guild owners (DM)"]
+    OwnerDm,
+}
+
+#[poise::command(
+    slash_command,
+    owners_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn announce(
+    ctx: Context<'_>,
+    #[description = "The notice to send, e.g. a maintenance window."] message: String,
+    target: AnnouncementTarget,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let data = ctx.data();
+    let embed = CreateEmbed::new()
+        .title("📢 Announcement from the logsalot operator")
+        .description(&message)
+        .footer(CreateEmbedFooter::new(
+            "Server managers can opt out with /config announcements.",
+        ));
+
+    let (mut delivered, mut opted_out, mut failed) = (0, 0, 0);
+
+    for guild_id in ctx.cache().guilds() {
+        if !data.settings.get(guild_id, &keys::ANNOUNCEMENTS).await {
+            opted_out += 1;
+            continue;
+        }
+
+        let result = match target {
+            AnnouncementTarget::ServerLogs => {
+                match LogType::Server.fetch_channel(&data.pool, guild_id).await {
+                    Some(channel) => channel
+                        .send_message(ctx, CreateMessage::new().embed(embed.clone()))
+                        .await
+                        .map(|_| ()),
+                    None => {
+                        failed += 1;
+                        continue;
+                    }
+                }
+            }
+            AnnouncementTarget::OwnerDm => {
+                let Some(owner_id) = guild_id
+                    .to_guild_cached(ctx.cache())
+                    .map(|guild| guild.owner_id)
+                else {
+                    failed += 1;
+                    continue;
+                };
+
+                owner_id
+                    .direct_message(ctx, CreateMessage::new().embed(embed.clone()))
+                    .await
+                    .map(|_| ())
+            }
+        };
+
+        match result {
+            Ok(()) => delivered += 1,
+            Err(error) => {
+                println!("Failed to deliver announcement to guild {guild_id}: {error}");
+                failed += 1;
+            }
+        }
+    }
+
+    ctx.reply(format!(
+        "Announcement delivered to {delivered} guilds. {opted_out} opted out, {failed} failed or had no destination."
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::{
+    client::{Context, Error},
+    commands::parse_duration,
+    logging::{cache_boost, now},
+};
+
+/// Boosts are capped, since every boosted guild holds up to a thousand messages per channel in memory.
+const MAX_BOOST_SECS: u64 = 24 * 60 * 60;
+
+#[poise::command(
+    slash_command,
+    subcommands("start", "stop"),
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn cacheboost(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Caches far more of this server's messages for a while, so older deleted messages keep their content.
+#[poise::command(slash_command)]
+async fn start(
+    ctx: Context<'_>,
+    #[description = "How long to boost for, e.g. 30m, 2h or 1d. At most a day."] duration: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let Some(seconds) = parse_duration(&duration).filter(|seconds| *seconds <= MAX_BOOST_SECS)
+    else {
+        ctx.reply(format!(
+            "{duration} is not a valid duration. Try something like 30m or 2h, up to 1d."
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let until = (now() + seconds) as i64;
+    let boost = &ctx.data().cache_boost;
+
+    if !boost.start(guild_id, until) {
+        ctx.reply(format!("The cache boost now lasts until <t:{until}:f>."))
+            .await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+
+    let kept = cache_boost::prefetch(ctx.http(), boost, guild_id).await?;
+
+    ctx.reply(format!(
+        "Caching up to {} messages per channel until <t:{until}:f>, starting with {kept} recent ones.",
+        cache_boost::BOOSTED_MESSAGES_PER_CHANNEL
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Ends a cache boost early.
+#[poise::command(slash_command)]
+async fn stop(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    if ctx.data().cache_boost.stop(guild_id) {
+        ctx.reply("The cache boost has ended. Messages are cached as usual again.")
+            .await?;
+    } else {
+        ctx.reply("This server's cache isn't boosted.").await?;
+    }
+
+    Ok(())
+}
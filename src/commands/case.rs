@@ -0,0 +1,130 @@
+use poise::CreateReply;
+use serenity::{
+    all::{EditMessage, EmbedField},
+    builder::CreateEmbed,
+};
+
+use crate::{
+    cases,
+    client::{Context, Error},
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("view", "reason"),
+    guild_only,
+    default_member_permissions = "MODERATE_MEMBERS"
+)]
+pub async fn case(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Shows a moderation case and links its log.
+#[poise::command(slash_command)]
+async fn view(ctx: Context<'_>, #[description = "The case number."] id: i64) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let Some(case) = cases::get(&ctx.data().pool, guild_id, id).await? else {
+        ctx.reply(format!("There's no case #{id} in this server."))
+            .await?;
+        return Ok(());
+    };
+
+    // targets are user IDs for actions against members, and already formatted otherwise.
+    let target = case
+        .target
+        .map_or("None".to_string(), |target| match target.parse::<u64>() {
+            Ok(user_id) => format!("<@{user_id}>"),
+            Err(_) => target,
+        });
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("Case #{}", case.case_number))
+        .field("Action", case.action, true)
+        .field("Moderator", format!("<@{}>", case.moderator), true)
+        .field("Target", target, true)
+        .field("Opened", format!("<t:{}:f>", case.created_at), true)
+        .field(
+            "Reason",
+            case.reason.unwrap_or("No reason given".to_string()),
+            false,
+        );
+
+    if let Some((channel_id, message_id)) = case.log_message {
+        embed = embed.field(
+            "Log",
+            format!(
+                "[Jump to log]({})",
+                message_id.link(channel_id, Some(guild_id))
+            ),
+            false,
+        );
+    }
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Changes the reason of a moderation case, in its log as well.
+#[poise::command(slash_command)]
+async fn reason(
+    ctx: Context<'_>,
+    #[description = "The case number."] id: i64,
+    #[description = "The new reason."]
+    #[max_length = 1000]
+    reason: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = &ctx.data().pool;
+
+    if !cases::set_reason(pool, guild_id, id, &reason).await? {
+        ctx.reply(format!("There's no case #{id} in this server."))
+            .await?;
+        return Ok(());
+    }
+
+    let Some((channel_id, message_id)) = cases::get(pool, guild_id, id)
+        .await?
+        .and_then(|case| case.log_message)
+    else {
+        ctx.reply(format!(
+            "Updated the reason of case #{id}. It has no log to edit."
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    // the log may have been deleted since, or the bot may have lost access to its channel.
+    let edited = match channel_id.message(ctx, message_id).await {
+        Ok(message) => match message.embeds.into_iter().next() {
+            Some(mut embed) => {
+                match embed.fields.iter_mut().find(|field| field.name == "Reason") {
+                    Some(field) => field.value = reason,
+                    None => embed.fields.push(EmbedField::new("Reason", reason, false)),
+                }
+
+                channel_id
+                    .edit_message(
+                        ctx,
+                        message_id,
+                        EditMessage::new().embed(CreateEmbed::from(embed)),
+                    )
+                    .await
+                    .is_ok()
+            }
+            None => false,
+        },
+        Err(_) => false,
+    };
+
+    ctx.reply(if edited {
+        format!("Updated the reason of case #{id} and its log.")
+    } else {
+        format!("Updated the reason of case #{id}, but its log couldn't be edited.")
+    })
+    .await?;
+
+    Ok(())
+}
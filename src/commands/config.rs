@@ -0,0 +1,696 @@
+use poise::{ChoiceParameter, CreateReply};
+
+use serenity::all::{ChannelId, RoleId};
+
+use crate::{
+    client::{Context, Error},
+    commands::{preview_routing, LogType},
+    config_snapshots,
+    logging::{
+        edit_versions,
+        formatters::{attachment_cap_key, MAX_ATTACHMENT_CAP},
+        outbound,
+        routing::Routing,
+        timestamps::TimestampStyle,
+        triage::TriageStatus,
+    },
+    settings::keys,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands(
+        "timestamps",
+        "self_deletes",
+        "min_delete_age",
+        "system_messages",
+        "announcements",
+        "pin_alerts",
+        "webhook_spoofs",
+        "voice_hops",
+        "nuke_ping_owner",
+        "vanity_ping",
+        "quarantine_webhook",
+        "verification_lurk",
+        "first_messages",
+        "reactions",
+        "milestones",
+        "triage_emoji",
+        "appeals",
+        "ntfy",
+        "pushover",
+        "report_limit",
+        "data_exports",
+        "metadata_only",
+        "attachment_cap",
+        "snapshot",
+        "rollback"
+    ),
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn config(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn timestamps(ctx: Context<'_>, style: TimestampStyle) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::TIMESTAMP_STYLE, &style)
+        .await?;
+
+    ctx.reply(format!(
+        "Log timestamps will now use the {} style.",
+        style.name()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn self_deletes(ctx: Context<'_>, suppress: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::SUPPRESS_QUICK_SELF_DELETES, &suppress)
+        .await?;
+
+    ctx.reply(if suppress {
+        "Authors quickly deleting their own messages will no longer be logged."
+    } else {
+        "All message deletions will be logged again."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn min_delete_age(
+    ctx: Context<'_>,
+    #[description = "Don't log deletions of messages younger than this. 0 logs all deletions."]
+    #[min = 0]
+    seconds: u32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::MIN_DELETED_MESSAGE_AGE, &(seconds as i64))
+        .await?;
+
+    ctx.reply(match seconds {
+        0 => "All message deletions will be logged.".to_string(),
+        _ => format!(
+            "Deletions of messages younger than {seconds} seconds will no longer be logged."
+        ),
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn system_messages(ctx: Context<'_>, log: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::LOG_SYSTEM_MESSAGES, &log)
+        .await?;
+
+    ctx.reply(if log {
+        "System messages (boosts, pins, joins, ...) will now be logged."
+    } else {
+        "System messages will no longer be logged."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn announcements(ctx: Context<'_>, receive: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::ANNOUNCEMENTS, &receive)
+        .await?;
+
+    ctx.reply(if receive {
+        "This server will receive announcements from the bot operator again."
+    } else {
+        "This server has opted out of operator announcements."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn pin_alerts(ctx: Context<'_>, pin: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::PIN_CRITICAL_ALERTS, &pin)
+        .await?;
+
+    ctx.reply(if pin {
+        "Critical alerts will be pinned until their incident is resolved with /incident resolve."
+    } else {
+        "Critical alerts will no longer be pinned."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn webhook_spoofs(ctx: Context<'_>, detect: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::DETECT_WEBHOOK_SPOOFS, &detect)
+        .await?;
+
+    ctx.reply(if detect {
+        "Webhook messages posing as members will be flagged."
+    } else {
+        "Webhook messages will no longer be checked for impersonation. Useful if you rely on proxy bots like PluralKit."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn voice_hops(
+    ctx: Context<'_>,
+    #[description = "Joins/leaves that count as hopping. 0 turns the alert off."]
+    #[min = 0]
+    threshold: u32,
+    #[description = "Window in seconds the joins/leaves have to happen in."]
+    #[min = 1]
+    #[max = 3600]
+    seconds: Option<u32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    settings
+        .set(guild_id, &keys::VOICE_HOP_THRESHOLD, &(threshold as i64))
+        .await?;
+
+    if let Some(seconds) = seconds {
+        settings
+            .set(guild_id, &keys::VOICE_HOP_WINDOW, &(seconds as i64))
+            .await?;
+    }
+
+    let window = settings.get(guild_id, &keys::VOICE_HOP_WINDOW).await;
+
+    ctx.reply(match threshold {
+        0 => "Voice channel hopping will no longer be flagged.".to_string(),
+        _ => format!(
+            "Members joining or leaving voice channels {threshold} times within {window} seconds will be flagged."
+        ),
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn nuke_ping_owner(ctx: Context<'_>, ping: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::NUKE_PING_OWNER, &ping)
+        .await?;
+
+    ctx.reply(if ping {
+        "The server owner will be pinged when mass channel or role deletions are detected."
+    } else {
+        "Mass deletion alerts will no longer ping the server owner."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn vanity_ping(
+    ctx: Context<'_>,
+    #[description = "Role to ping when the vanity URL changes. Omit to stop pinging."] role: Option<
+        RoleId,
+    >,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::VANITY_PING_ROLE, &role)
+        .await?;
+
+    ctx.reply(match role {
+        Some(role) => format!("<@&{role}> will be pinged when the vanity URL changes."),
+        None => "Vanity URL alerts will no longer ping a role.".to_string(),
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn quarantine_webhook(
+    ctx: Context<'_>,
+    #[description = "URL to POST raid and nuke detections to. Omit to stop sending them."]
+    url: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    let Some(url) = url else {
+        settings
+            .unset(guild_id, keys::QUARANTINE_WEBHOOK_URL.name)
+            .await?;
+        ctx.reply("Detections will no longer be sent to a quarantine webhook.")
+            .await?;
+        return Ok(());
+    };
+
+    if let Err(error) = outbound::client_for(&url).await {
+        ctx.reply(format!(
+            "{url} can't be used as a quarantine webhook: {error}."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    settings
+        .set(guild_id, &keys::QUARANTINE_WEBHOOK_URL, &url)
+        .await?;
+
+    ctx.reply("Raid and nuke detections will now also be sent to the quarantine webhook.")
+        .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn verification_lurk(
+    ctx: Context<'_>,
+    #[description = "Flag members who verify this many hours or more after joining. 0 turns the alert off."]
+    #[min = 0]
+    hours: u32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::VERIFICATION_LURK_HOURS, &(hours as i64))
+        .await?;
+
+    ctx.reply(match hours {
+        0 => "Late verifications will no longer be flagged.".to_string(),
+        _ => format!("Members verifying {hours} hours or more after joining will be flagged."),
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn first_messages(ctx: Context<'_>, log: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::LOG_FIRST_MESSAGES, &log)
+        .await?;
+
+    ctx.reply(if log {
+        "The first message of each new member will be logged to member logs."
+    } else {
+        "First messages of new members will no longer be logged."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn reactions(ctx: Context<'_>, log: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::LOG_REACTIONS, &log)
+        .await?;
+
+    ctx.reply(if log {
+        "Reactions being added and removed will be logged to chat logs."
+    } else {
+        "Individual reactions will no longer be logged. Moderators clearing reactions is still logged."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn milestones(
+    ctx: Context<'_>,
+    #[description = "Log every time the member count reaches a multiple of this, e.g. 100 or 1000. 0 turns it off."]
+    #[min = 0]
+    interval: u32,
+    #[description = "Also announce milestones publicly in this channel."]
+    #[channel_types("Text")]
+    public_channel: Option<ChannelId>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    settings
+        .set(guild_id, &keys::MILESTONE_INTERVAL, &(interval as i64))
+        .await?;
+    settings
+        .set(guild_id, &keys::MILESTONE_CHANNEL, &public_channel)
+        .await?;
+
+    ctx.reply(match (interval, public_channel) {
+        (0, _) => "Member count milestones will no longer be logged.".to_string(),
+        (_, None) => format!("Every {interval} members will be logged as a milestone."),
+        (_, Some(channel)) => format!(
+            "Every {interval} members will be logged as a milestone and announced in <#{channel}>."
+        ),
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn triage_emoji(
+    ctx: Context<'_>,
+    status: TriageStatus,
+    #[description = "Emoji moderators react to logs with to set this status. Omit to reset to the default."]
+    emoji: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    match emoji {
+        Some(emoji) => {
+            settings
+                .set_raw(guild_id, &status.emoji_key(), emoji.trim().to_string())
+                .await?
+        }
+        None => settings.unset(guild_id, &status.emoji_key()).await?,
+    }
+
+    ctx.reply(format!(
+        "Reacting to a log with {} will now mark it as {}.",
+        status.emoji(settings, guild_id).await,
+        status.name()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn appeals(
+    ctx: Context<'_>,
+    #[description = "Appeal link for ban logs. Supports {user_id}, {user} and {server_id}. Omit to turn appeal links off."]
+    url: Option<String>,
+    #[description = "Also DM the link to banned members. This often fails once they no longer share a server with the bot."]
+    dm: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+    let dm = dm.unwrap_or(false);
+
+    settings
+        .set(
+            guild_id,
+            &keys::APPEAL_URL,
+            &url.clone().unwrap_or_default(),
+        )
+        .await?;
+    settings.set(guild_id, &keys::APPEAL_DM, &dm).await?;
+
+    ctx.reply(match (url, dm) {
+        (None, _) => "Ban logs will no longer include an appeal link.".to_string(),
+        (Some(url), false) => format!("Ban logs will now include the appeal link `{url}`."),
+        (Some(url), true) => format!(
+            "Ban logs will now include the appeal link `{url}`, and banned members will be sent it in DMs."
+        ),
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn ntfy(
+    ctx: Context<'_>,
+    #[description = "ntfy topic URL to push critical alerts to, e.g. https://ntfy.sh/my-topic. Omit to stop pushing them."]
+    url: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    let Some(url) = url else {
+        settings.unset(guild_id, keys::NTFY_URL.name).await?;
+        ctx.reply("Critical alerts will no longer be pushed to ntfy.")
+            .await?;
+        return Ok(());
+    };
+
+    if let Err(error) = outbound::client_for(&url).await {
+        ctx.reply(format!("{url} can't be used as an ntfy topic: {error}."))
+            .await?;
+        return Ok(());
+    }
+
+    settings.set(guild_id, &keys::NTFY_URL, &url).await?;
+
+    ctx.send(
+        CreateReply::default()
+            .content("Critical alerts will now be pushed to your ntfy topic.")
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn pushover(
+    ctx: Context<'_>,
+    #[description = "Pushover application token. Omit both to stop pushing alerts."] token: Option<
+        String,
+    >,
+    #[description = "Pushover user or group key."] user: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    let (Some(token), Some(user)) = (token, user) else {
+        settings.unset(guild_id, keys::PUSHOVER_TOKEN.name).await?;
+        settings.unset(guild_id, keys::PUSHOVER_USER.name).await?;
+        ctx.reply("Critical alerts will no longer be pushed to Pushover.")
+            .await?;
+        return Ok(());
+    };
+
+    settings
+        .set(guild_id, &keys::PUSHOVER_TOKEN, &token)
+        .await?;
+    settings.set(guild_id, &keys::PUSHOVER_USER, &user).await?;
+
+    ctx.send(
+        CreateReply::default()
+            .content("Critical alerts will now be pushed to Pushover.")
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn report_limit(
+    ctx: Context<'_>,
+    #[description = "How many messages a member may report per hour. 0 removes the limit."]
+    #[min = 0]
+    per_hour: u32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::REPORT_LIMIT_PER_HOUR, &(per_hour as i64))
+        .await?;
+
+    ctx.reply(match per_hour {
+        0 => "Members can now send any number of reports.".to_string(),
+        _ => format!("Members can now send up to {per_hour} reports per hour."),
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn data_exports(
+    ctx: Context<'_>,
+    #[description = "Let members export the records kept about them with /mydata."] enabled: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::DATA_EXPORTS, &enabled)
+        .await?;
+
+    ctx.reply(if enabled {
+        "Members can now export the records kept about them with `/mydata`."
+    } else {
+        "Members can no longer export their records, though `/mydata` still tells them what's kept."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn metadata_only(
+    ctx: Context<'_>,
+    #[description = "Log message metadata only, hiding content and attachments behind lengths and hashes."]
+    enabled: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::METADATA_ONLY, &enabled)
+        .await?;
+
+    // the full versions of long edits are message content too.
+    if enabled {
+        edit_versions::forget(&ctx.data().pool, guild_id).await?;
+    }
+
+    ctx.reply(if enabled {
+        "Message logs will now only show the length and a hash of message content, and attachments won't be re-uploaded."
+    } else {
+        "Message logs will include message content and attachments again."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn attachment_cap(
+    ctx: Context<'_>,
+    #[description = "The log channel to cap."] route: LogType,
+    #[description = "How many attachments to re-upload per log. The rest are linked instead."]
+    #[min = 0]
+    #[max = 10]
+    count: u32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let count = (count as usize).min(MAX_ATTACHMENT_CAP);
+    let name = route.name();
+
+    ctx.data()
+        .settings
+        .set_raw(guild_id, &attachment_cap_key(route), count.to_string())
+        .await?;
+
+    ctx.reply(match count {
+        0 => format!("{name} will now link attachments instead of re-uploading them."),
+        _ => {
+            format!("{name} will now re-upload up to {count} attachments each, and link the rest.")
+        }
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn snapshot(
+    ctx: Context<'_>,
+    #[description = "What to call the snapshot. Reusing a name replaces that snapshot."]
+    #[max_length = 50]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let count =
+        config_snapshots::save(&ctx.data().pool, guild_id, name.trim(), ctx.author().id).await?;
+
+    ctx.reply(format!(
+        "Saved the current configuration ({count} entries) as `{}`. Use `/config rollback` to restore it.",
+        name.trim()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+async fn autocomplete_snapshot<'a>(ctx: Context<'a>, partial: &'a str) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return vec![];
+    };
+
+    config_snapshots::names(&ctx.data().pool, guild_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|name| name.contains(partial))
+        .take(25)
+        .collect()
+}
+
+#[poise::command(slash_command)]
+async fn rollback(
+    ctx: Context<'_>,
+    #[description = "The snapshot to restore. Settings changed since then are reverted."]
+    #[autocomplete = "autocomplete_snapshot"]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let data = ctx.data();
+
+    let before = Routing::snapshot(data, guild_id, None).await?;
+
+    let Some(count) =
+        config_snapshots::rollback(&data.pool, &data.settings, guild_id, name.trim()).await?
+    else {
+        ctx.reply(format!("There's no snapshot called `{}`.", name.trim()))
+            .await?;
+        return Ok(());
+    };
+
+    ctx.reply(format!(
+        "Restored the configuration from `{}` ({count} entries).",
+        name.trim()
+    ))
+    .await?;
+
+    preview_routing(ctx, before).await
+}
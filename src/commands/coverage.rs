@@ -0,0 +1,170 @@
+use poise::CreateReply;
+use serenity::{
+    all::{ChannelId, GatewayIntents, Permissions},
+    builder::CreateEmbed,
+};
+
+use crate::{
+    client::{self, Context, Error},
+    commands::LogType,
+    logging::mutes,
+};
+
+/// The intents the gateway needs to deliver an event, by its snake case name.
+fn required_intents(event: &str) -> GatewayIntents {
+    match event {
+        "message" | "message_update" => {
+            GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT
+        }
+        "message_delete" | "message_delete_bulk" => GatewayIntents::GUILD_MESSAGES,
+        "guild_member_addition" | "guild_member_removal" | "guild_member_update" => {
+            GatewayIntents::GUILD_MEMBERS
+        }
+        "guild_ban_addition" | "guild_ban_removal" | "guild_audit_log_entry_create" => {
+            GatewayIntents::GUILD_MODERATION
+        }
+        "invite_create" | "invite_delete" => GatewayIntents::GUILD_INVITES,
+        "integration_create" | "integration_update" | "integration_delete" => {
+            GatewayIntents::GUILD_INTEGRATIONS
+        }
+        "guild_emojis_update"
+        | "guild_stickers_update"
+        | "guild_soundboard_sound_create"
+        | "guild_soundboard_sound_update"
+        | "guild_soundboard_sound_delete" => GatewayIntents::GUILD_EMOJIS_AND_STICKERS,
+        "voice_state_update" => GatewayIntents::GUILD_VOICE_STATES,
+        "auto_moderation_rule_create"
+        | "auto_moderation_rule_update"
+        | "auto_moderation_rule_delete" => GatewayIntents::AUTO_MODERATION_CONFIGURATION,
+        "reaction_add" | "reaction_remove" | "reaction_remove_all" | "reaction_remove_emoji" => {
+            GatewayIntents::GUILD_MESSAGE_REACTIONS
+        }
+        "channel_create"
+        | "channel_update"
+        | "channel_delete"
+        | "thread_create"
+        | "thread_update"
+        | "thread_delete"
+        | "guild_role_create"
+        | "guild_role_update"
+        | "guild_role_delete"
+        | "guild_update"
+        | "channel_pins_update"
+        | "voice_channel_status_update" => GatewayIntents::GUILDS,
+        // events the bot raises itself, like member reports, don't come from the gateway.
+        _ => GatewayIntents::empty(),
+    }
+}
+
+/// The bot's permissions in the guild, and in `channel` if given. `None` if the guild isn't cached.
+fn bot_permissions(
+    ctx: Context<'_>,
+    channel: Option<ChannelId>,
+) -> Option<(Permissions, Option<Permissions>)> {
+    let bot_id = ctx.cache().current_user().id;
+    let guild = ctx.guild()?;
+    let member = guild.members.get(&bot_id)?;
+
+    let in_channel = channel
+        .and_then(|channel| guild.channels.get(&channel))
+        .map(|channel| guild.user_permissions_in(channel, member));
+
+    Some((guild.member_permissions(member), in_channel))
+}
+
+/// Lists every kind of log, where it goes, and whether anything stops it from being posted.
+#[poise::command(slash_command, guild_only, default_member_permissions = "MANAGE_GUILD")]
+pub async fn coverage(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let data = ctx.data();
+    let intents = client::intents();
+
+    let mut embed = CreateEmbed::new().title("Logging coverage");
+
+    for log_type in LogType::ALL {
+        let formatters = data
+            .formatters
+            .all()
+            .into_iter()
+            .filter(|formatter| {
+                formatter.default_route().as_column_name() == log_type.as_column_name()
+            })
+            .collect::<Vec<_>>();
+
+        if formatters.is_empty() {
+            continue;
+        }
+
+        let channel = log_type.fetch_channel(&data.pool, guild_id).await;
+        let muted = mutes::is_muted(&data.pool, guild_id, log_type).await?;
+        let permissions = bot_permissions(ctx, channel);
+
+        let mut problems = Vec::new();
+
+        match (channel, &permissions) {
+            (None, _) => problems.push("No channel set".to_string()),
+            (Some(_), Some((_, None))) => problems.push("Channel no longer exists".to_string()),
+            (Some(_), Some((_, Some(in_channel)))) => {
+                let missing = (Permissions::VIEW_CHANNEL
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::EMBED_LINKS)
+                    - *in_channel;
+
+                if !missing.is_empty() {
+                    problems.push(format!("Missing {missing} in the channel"));
+                }
+            }
+            (Some(_), None) => {}
+        }
+
+        if muted {
+            problems.push("Muted".to_string());
+        }
+
+        let mut lines = vec![match channel {
+            Some(channel) => format!("Posted to <#{channel}>"),
+            None => "Not posted anywhere".to_string(),
+        }];
+
+        for formatter in formatters {
+            let mut notes = Vec::new();
+
+            let missing_intents = required_intents(formatter.event()) - intents;
+            if !missing_intents.is_empty() {
+                notes.push(format!("missing intents: {missing_intents:?}"));
+            }
+
+            if formatter.uses_audit_log()
+                && permissions
+                    .as_ref()
+                    .is_some_and(|(guild, _)| !guild.view_audit_log())
+            {
+                notes.push("can't attribute without View Audit Log".to_string());
+            }
+
+            let enabled = problems.is_empty() && missing_intents.is_empty();
+
+            lines.push(format!(
+                "{} {} (`{}`){}",
+                if enabled { "✅" } else { "❌" },
+                formatter.title(),
+                formatter.kind(),
+                if notes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" - {}", notes.join(", "))
+                }
+            ));
+        }
+
+        if !problems.is_empty() {
+            lines.push(format!("⚠️ {}", problems.join(", ")));
+        }
+
+        embed = embed.field(log_type.to_string(), lines.join("\n"), false);
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use poise::{serenity_prelude::*, CreateReply};
+
+use crate::{
+    client::{Context, Error},
+    dashboard::{self, HOURS},
+    sparkline,
+};
+
+const EVENTS_COLOR: [u8; 3] = [0x58, 0x65, 0xf2];
+const FAILURES_COLOR: [u8; 3] = [0xed, 0x42, 0x45];
+
+/// Shows log volume, backlogs and failures across every guild the bot is in.
+#[poise::command(
+    slash_command,
+    owners_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn dashboard(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let overview = dashboard::overview(&ctx.data().pool).await?;
+
+    let top_guilds = overview
+        .top_guilds
+        .iter()
+        .enumerate()
+        .map(|(index, (guild_id, count))| {
+            let name = guild_id
+                .parse::<u64>()
+                .ok()
+                .and_then(|guild_id| ctx.cache().guild(guild_id).map(|guild| guild.name.clone()))
+                .unwrap_or_else(|| guild_id.clone());
+
+            format!("{}. {name} ({guild_id}): {count}", index + 1)
+        })
+        .collect::<Vec<_>>();
+
+    let volume = CreateEmbed::new()
+        .title("Dashboard")
+        .description(format!(
+            "Across {} guilds, over the last {HOURS} hours.",
+            ctx.cache().guild_count()
+        ))
+        .field(
+            "Events/min",
+            format!("{:.1}", overview.events_per_minute),
+            true,
+        )
+        .field("Events", overview.total_events().to_string(), true)
+        .field(
+            "Queued",
+            format!(
+                "{} for maintenance\n{} for digests",
+                overview.maintenance_backlog, overview.digest_backlog
+            ),
+            true,
+        )
+        .field(
+            "Top guilds",
+            if top_guilds.is_empty() {
+                "None".to_string()
+            } else {
+                top_guilds.join("\n")
+            },
+            false,
+        )
+        .image("attachment://events.png");
+
+    let failures = CreateEmbed::new()
+        .title("Failures")
+        .field("Error rate", format!("{:.2}%", overview.error_rate()), true)
+        .field("Failed posts", overview.total_failures().to_string(), true)
+        .image("attachment://failures.png");
+
+    ctx.send(
+        CreateReply::default()
+            .embed(volume)
+            .embed(failures)
+            .attachment(CreateAttachment::bytes(
+                sparkline::render(&overview.hourly_events, EVENTS_COLOR),
+                "events.png",
+            ))
+            .attachment(CreateAttachment::bytes(
+                sparkline::render(&overview.hourly_failures, FAILURES_COLOR),
+                "failures.png",
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
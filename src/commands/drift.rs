@@ -0,0 +1,69 @@
+use serenity::all::{ChannelId, GuildChannel};
+
+use crate::{
+    client::{Context, Error},
+    logging::drift,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("watch", "unwatch", "list"),
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn drift(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn watch(ctx: Context<'_>, channel: GuildChannel) -> Result<(), Error> {
+    drift::snapshot(&ctx.data().pool, &channel).await?;
+
+    ctx.reply(format!(
+        "Took a snapshot of <#{}>'s permissions. Changes the bot doesn't see happen will be flagged daily.",
+        channel.id
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn unwatch(ctx: Context<'_>, channel: ChannelId) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    if drift::unwatch(&ctx.data().pool, guild_id, channel).await? {
+        ctx.reply(format!(
+            "<#{channel}> is no longer watched for permission drift."
+        ))
+        .await?;
+    } else {
+        ctx.reply(format!("<#{channel}> isn't being watched."))
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let channels = drift::watched(&ctx.data().pool, guild_id).await?;
+
+    if channels.is_empty() {
+        ctx.reply("No channels are watched for permission drift.")
+            .await?;
+        return Ok(());
+    }
+
+    let lines = channels
+        .into_iter()
+        .map(|(channel, taken_at)| format!("<#{channel}>, snapshot from <t:{taken_at}:R>"))
+        .collect::<Vec<_>>();
+
+    ctx.reply(format!("Watched channels\n{}", lines.join("\n")))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use poise::CreateReply;
+use serenity::{all::Emoji, builder::CreateEmbed};
+
+use crate::{
+    client::{Context, Error},
+    emoji_stats,
+};
+
+/// How many emojis each list shows.
+const LIST_LENGTH: usize = 10;
+
+fn describe(usage: &[(Emoji, i64)]) -> String {
+    if usage.is_empty() {
+        return "None".to_string();
+    }
+
+    usage
+        .iter()
+        .map(|(emoji, count)| format!("{emoji} `:{}:` - {count}", emoji.name))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lists the server's most and least used custom emojis, to help pick which ones to remove.
+#[poise::command(slash_command, guild_only, default_member_permissions = "MANAGE_GUILD")]
+pub async fn emojistats(
+    ctx: Context<'_>,
+    #[description = "How many days back to count. Defaults to 30."]
+    #[min = 1]
+    #[max = 365]
+    days: Option<u32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let days = days.unwrap_or(30);
+
+    let emojis = ctx
+        .guild()
+        .map(|guild| guild.emojis.values().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    if emojis.is_empty() {
+        ctx.reply("This server has no custom emojis.").await?;
+        return Ok(());
+    }
+
+    let usage = emoji_stats::usage(&ctx.data().pool, guild_id, days as u64).await?;
+
+    // emojis nobody used are the most useful to see, so they're counted as 0 rather than left out.
+    let mut usage = emojis
+        .into_iter()
+        .map(|emoji| {
+            let count = usage.get(&emoji.id).copied().unwrap_or(0);
+            (emoji, count)
+        })
+        .collect::<Vec<_>>();
+
+    usage.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.name.cmp(&b.name)));
+
+    let most_used = usage.iter().take(LIST_LENGTH).cloned().collect::<Vec<_>>();
+    let least_used = usage
+        .iter()
+        .rev()
+        .take(LIST_LENGTH.min(usage.len().saturating_sub(LIST_LENGTH)))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("Emoji usage over the last {days} days"))
+        .field("Most used", describe(&most_used), false);
+
+    // with only a few emojis, they all fit in the first list.
+    if !least_used.is_empty() {
+        embed = embed.field("Least used", describe(&least_used), false);
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
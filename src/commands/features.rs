@@ -0,0 +1,124 @@
+use poise::ChoiceParameter;
+
+use crate::{
+    client::{Context, Error},
+    features::{self, Feature, GLOBAL_SCOPE},
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("list", "set", "clear"),
+    owners_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn features(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Resolves the optional `guild` argument to a flag scope, replying with an error if it's not a valid ID.
+async fn scope(ctx: Context<'_>, guild: Option<String>) -> Result<Option<String>, Error> {
+    match guild {
+        None => Ok(Some(GLOBAL_SCOPE.to_string())),
+        Some(guild) if guild.parse::<u64>().is_ok() => Ok(Some(guild)),
+        Some(guild) => {
+            ctx.reply(format!("{guild} is not a valid guild ID."))
+                .await?;
+            Ok(None)
+        }
+    }
+}
+
+#[poise::command(slash_command)]
+async fn list(
+    ctx: Context<'_>,
+    #[description = "Guild ID to show effective flags for. Omit for the current guild."]
+    guild: Option<String>,
+) -> Result<(), Error> {
+    let pool = &ctx.data().pool;
+
+    let Some(guild_id) = guild
+        .and_then(|guild| guild.parse::<u64>().ok())
+        .map(Into::into)
+        .or(ctx.guild_id())
+    else {
+        ctx.reply("Pass a guild ID, or run this in a guild.")
+            .await?;
+        return Ok(());
+    };
+
+    let mut lines = Vec::new();
+
+    for feature in Feature::ALL {
+        let enabled = features::is_enabled(pool, guild_id, feature).await;
+        lines.push(format!(
+            "{} **{}**",
+            if enabled { "✅" } else { "❌" },
+            feature.name()
+        ));
+    }
+
+    ctx.reply(format!("Features for {guild_id}\n{}", lines.join("\n")))
+        .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn set(
+    ctx: Context<'_>,
+    feature: Feature,
+    enabled: bool,
+    #[description = "Guild ID to set the flag for. Omit to set the global default."] guild: Option<
+        String,
+    >,
+) -> Result<(), Error> {
+    let Some(scope) = scope(ctx, guild).await? else {
+        return Ok(());
+    };
+    let key = feature.as_key();
+
+    sqlx::query!(
+        "INSERT INTO feature_flags (scope, feature, enabled) VALUES (?, ?, ?)
+        ON CONFLICT (scope, feature) DO UPDATE SET enabled = excluded.enabled",
+        scope,
+        key,
+        enabled
+    )
+    .execute(&ctx.data().pool)
+    .await?;
+
+    ctx.reply(format!(
+        "{} {} for {scope}.",
+        feature.name(),
+        if enabled { "enabled" } else { "disabled" }
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn clear(
+    ctx: Context<'_>,
+    feature: Feature,
+    #[description = "Guild ID to clear the flag for. Omit to clear the global default."]
+    guild: Option<String>,
+) -> Result<(), Error> {
+    let Some(scope) = scope(ctx, guild).await? else {
+        return Ok(());
+    };
+    let key = feature.as_key();
+
+    sqlx::query!(
+        "DELETE FROM feature_flags WHERE scope = ? AND feature = ?",
+        scope,
+        key
+    )
+    .execute(&ctx.data().pool)
+    .await?;
+
+    ctx.reply(format!("Cleared the {} flag for {scope}.", feature.name()))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use poise::CreateReply;
+use serenity::{all::User, builder::CreateEmbed};
+
+use crate::{
+    cases,
+    client::{Context, Error},
+    user_reports,
+};
+
+/// Shows a member's moderation cases and report activity.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "MODERATE_MEMBERS"
+)]
+pub async fn history(ctx: Context<'_>, user: User) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = &ctx.data().pool;
+
+    let cases = cases::for_target(pool, guild_id, &user.id.to_string(), 10).await?;
+    let reports = user_reports::stats(pool, guild_id, user.id).await?;
+
+    let cases = if cases.is_empty() {
+        "None".to_string()
+    } else {
+        cases
+            .iter()
+            .map(|case| {
+                format!(
+                    "#{} **{}** by <@{}> <t:{}:R>{}",
+                    case.case_number,
+                    case.action,
+                    case.moderator,
+                    case.created_at,
+                    case.reason
+                        .as_ref()
+                        .map(|reason| format!(": {reason}"))
+                        .unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!("History for {}", user.name))
+        .description(format!("<@{}>", user.id))
+        .field("Cases", cases, false)
+        .field("Reports Received", reports.received.to_string(), true)
+        .field("Reports Filed", reports.filed.to_string(), true)
+        .field("Handled", reports.handled.to_string(), true)
+        .field("False Positives", reports.false_positives.to_string(), true);
+
+    // who filed reports is confidential, so this stays between the moderators running the command.
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use poise::ChoiceParameter;
+
+use crate::{
+    client::{Context, Error},
+    features::GLOBAL_SCOPE,
+    logging::{ignores, ignores::IgnoreKind},
+};
+
+/// Authors whose messages are never logged, in one guild or across every guild the bot is in.
+#[poise::command(
+    slash_command,
+    subcommands("add", "remove", "list"),
+    owners_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn ignores(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Resolves the optional `guild` argument to an ignore scope, replying with an error if it's not a valid ID.
+async fn scope(ctx: Context<'_>, guild: Option<String>) -> Result<Option<String>, Error> {
+    match guild {
+        None => Ok(Some(GLOBAL_SCOPE.to_string())),
+        Some(guild) if guild.parse::<u64>().is_ok() => Ok(Some(guild)),
+        Some(guild) => {
+            ctx.reply(format!("{guild} is not a valid guild ID."))
+                .await?;
+            Ok(None)
+        }
+    }
+}
+
+#[poise::command(slash_command)]
+async fn add(
+    ctx: Context<'_>,
+    kind: IgnoreKind,
+    #[description = "The user, bot or application ID to ignore."] id: String,
+    #[description = "Guild ID to ignore it in. Omit to ignore it in every guild."] guild: Option<
+        String,
+    >,
+) -> Result<(), Error> {
+    let Some(scope) = scope(ctx, guild).await? else {
+        return Ok(());
+    };
+
+    if id.parse::<u64>().is_err() {
+        ctx.reply(format!("{id} is not a valid ID.")).await?;
+        return Ok(());
+    }
+
+    if ignores::add(&ctx.data().pool, &scope, kind, &id, ctx.author().id).await? {
+        ctx.reply(format!(
+            "Messages from {} `{id}` will no longer be logged in {scope}.",
+            kind.name().to_lowercase()
+        ))
+        .await?;
+    } else {
+        ctx.reply(format!("`{id}` is already ignored in {scope}."))
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn remove(
+    ctx: Context<'_>,
+    kind: IgnoreKind,
+    id: String,
+    #[description = "Guild ID to stop ignoring it in. Omit for instance-wide ignores."]
+    guild: Option<String>,
+) -> Result<(), Error> {
+    let Some(scope) = scope(ctx, guild).await? else {
+        return Ok(());
+    };
+
+    if ignores::remove(&ctx.data().pool, &scope, kind, &id).await? {
+        ctx.reply(format!("`{id}` is no longer ignored in {scope}."))
+            .await?;
+    } else {
+        ctx.reply(format!("`{id}` isn't ignored in {scope}."))
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn list(
+    ctx: Context<'_>,
+    #[description = "Guild ID to list ignores for. Omit for instance-wide ignores."] guild: Option<
+        String,
+    >,
+) -> Result<(), Error> {
+    let Some(scope) = scope(ctx, guild).await? else {
+        return Ok(());
+    };
+
+    let ignores = ignores::list(&ctx.data().pool, &scope).await?;
+
+    if ignores.is_empty() {
+        ctx.reply(format!("Nothing is ignored in {scope}.")).await?;
+        return Ok(());
+    }
+
+    let lines = ignores
+        .iter()
+        .map(|ignore| {
+            format!(
+                "{} `{}`, added by <@{}> <t:{}:R>",
+                ignore.kind.name(),
+                ignore.target_id,
+                ignore.added_by,
+                ignore.added_at
+            )
+        })
+        .collect::<Vec<_>>();
+
+    ctx.reply(format!("Ignored in {scope}\n{}", lines.join("\n")))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::{
+    client::{Context, Error},
+    logging::incidents::{self, Resolution},
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("show", "resolve"),
+    guild_only,
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn incident(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Lists every log in an incident, since each log only links back to the one before it.
+#[poise::command(slash_command)]
+async fn show(ctx: Context<'_>, id: i64) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let Some(incident) = incidents::logs(&ctx.data().pool, guild_id, id).await? else {
+        ctx.reply(format!("There's no incident #{id} in this server."))
+            .await?;
+        return Ok(());
+    };
+
+    let status = match incident.resolved_at {
+        Some(resolved_at) => format!("resolved <t:{resolved_at}:R>"),
+        None => "open".to_string(),
+    };
+
+    let mut reply = format!(
+        "Incident #{id} about <@{}>, opened <t:{}:R>, {status}",
+        incident.subject, incident.opened_at
+    );
+
+    if incident.messages.is_empty() {
+        reply += "
+No logs were posted for it.";
+    }
+
+    // replies are capped at 2000 characters, and message links are long.
+    for (index, (channel_id, message_id)) in incident.messages.iter().enumerate() {
+        let line = format!(
+            "\n{}. {}",
+            index + 1,
+            message_id.link(*channel_id, Some(guild_id))
+        );
+
+        if reply.len() + line.len() > 1900 {
+            reply += &format!("\n...and {} more", incident.messages.len() - index);
+            break;
+        }
+
+        reply += &line;
+    }
+
+    ctx.reply(reply).await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn resolve(ctx: Context<'_>, id: i64) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    match incidents::resolve(&ctx.data().pool, guild_id, id).await? {
+        Resolution::NotFound => {
+            ctx.reply(format!("There's no incident #{id} in this server."))
+                .await?;
+        }
+        Resolution::AlreadyResolved => {
+            ctx.reply(format!("Incident #{id} is already resolved."))
+                .await?;
+        }
+        Resolution::Resolved { pinned } => {
+            // the alert may have been deleted or unpinned by hand already; the incident is resolved either way.
+            let unpinned = match pinned {
+                Some((channel_id, message_id)) => channel_id.unpin(ctx, message_id).await.is_ok(),
+                None => true,
+            };
+
+            ctx.reply(if unpinned {
+                format!("Marked incident #{id} as resolved.")
+            } else {
+                format!("Marked incident #{id} as resolved, but its alert couldn't be unpinned.")
+            })
+            .await?;
+        }
+    }
+
+    Ok(())
+}
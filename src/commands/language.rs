@@ -0,0 +1,124 @@
+use serenity::all::ChannelId;
+use whatlang::Lang;
+
+use crate::{
+    client::{Context, Error},
+    commands::preview_routing,
+    logging::{language, routing::Routing},
+    settings::keys,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("tags", "route", "ignore"),
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn language(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Resolves an ISO 639-3 code, replying with an error if it's unknown.
+async fn parse_language(ctx: Context<'_>, code: &str) -> Result<Option<Lang>, Error> {
+    let language = Lang::from_code(code.trim().to_lowercase());
+
+    if language.is_none() {
+        ctx.reply(format!(
+            "{code} is not a known language code. Use three-letter ISO 639-3 codes, like eng, deu or spa."
+        ))
+        .await?;
+    }
+
+    Ok(language)
+}
+
+#[poise::command(slash_command)]
+async fn tags(ctx: Context<'_>, enabled: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::LANGUAGE_TAGS, &enabled)
+        .await?;
+
+    ctx.reply(if enabled {
+        "Message logs will be tagged with the detected language of their content."
+    } else {
+        "Message logs will no longer be tagged with a language. Language routes and ignores are paused."
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn route(
+    ctx: Context<'_>,
+    #[description = "ISO 639-3 language code, e.g. eng."] code: String,
+    #[description = "Channel to send logs about content in this language to. Omit to use the usual routes."]
+    #[channel_types("Text")]
+    channel: Option<ChannelId>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    let Some(lang) = parse_language(ctx, &code).await? else {
+        return Ok(());
+    };
+    let key = language::channel_key(lang.code());
+    let before = Routing::snapshot(ctx.data(), guild_id, Some(lang)).await?;
+
+    match channel {
+        Some(channel) => {
+            settings
+                .set_raw(guild_id, &key, channel.to_string())
+                .await?;
+            ctx.reply(format!(
+                "Logs about {} content will now be sent to <#{channel}>.",
+                lang.eng_name()
+            ))
+            .await?;
+        }
+        None => {
+            settings.unset(guild_id, &key).await?;
+            ctx.reply(format!(
+                "Logs about {} content will use the usual routes again.",
+                lang.eng_name()
+            ))
+            .await?;
+        }
+    }
+
+    preview_routing(ctx, before).await
+}
+
+#[poise::command(slash_command)]
+async fn ignore(
+    ctx: Context<'_>,
+    #[description = "ISO 639-3 language code, e.g. eng."] code: String,
+    ignored: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    let Some(lang) = parse_language(ctx, &code).await? else {
+        return Ok(());
+    };
+    let key = language::ignored_key(lang.code());
+    let before = Routing::snapshot(ctx.data(), guild_id, Some(lang)).await?;
+
+    if ignored {
+        settings.set_raw(guild_id, &key, "true".to_string()).await?;
+    } else {
+        settings.unset(guild_id, &key).await?;
+    }
+
+    ctx.reply(format!(
+        "Logs about {} content will {}be logged.",
+        lang.eng_name(),
+        if ignored { "no longer " } else { "" }
+    ))
+    .await?;
+
+    preview_routing(ctx, before).await
+}
@@ -0,0 +1,182 @@
+use serenity::all::{ChannelId, ChannelType, GuildChannel};
+
+use crate::{
+    cases,
+    client::{Context, Error},
+    commands::parse_duration,
+    lockdown,
+    logging::now,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("channel", "all", "lift"),
+    guild_only,
+    default_member_permissions = "MANAGE_CHANNELS"
+)]
+pub async fn lockdown(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Opens a case for the lockdown and locks `channels`, logging the result. Replies with an error for bad durations.
+async fn lock_channels(
+    ctx: Context<'_>,
+    channels: Vec<GuildChannel>,
+    target: String,
+    duration: Option<String>,
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let data = ctx.data();
+
+    let until = match duration {
+        None => None,
+        Some(duration) => match parse_duration(&duration) {
+            Some(seconds) => Some((now() + seconds) as i64),
+            None => {
+                ctx.reply(format!(
+                    "{duration} is not a valid duration. Try something like 30m, 2h or 1d."
+                ))
+                .await?;
+                return Ok(());
+            }
+        },
+    };
+
+    ctx.defer().await?;
+
+    let case_number = cases::open(
+        &data.pool,
+        guild_id,
+        "lockdown",
+        ctx.author().id,
+        Some(target),
+        reason.clone(),
+    )
+    .await?;
+
+    let mut locked = Vec::new();
+    for channel in channels.iter() {
+        if lockdown::lock(ctx.http(), &data.pool, channel, case_number, until).await? {
+            locked.push(format!("<#{}>", channel.id));
+        }
+    }
+
+    if locked.is_empty() {
+        ctx.reply("Everything you asked to lock down is already locked.")
+            .await?;
+        return Ok(());
+    }
+
+    let until = match until {
+        Some(until) => format!("until <t:{until}:f>"),
+        None => "until lifted with /lockdown lift".to_string(),
+    };
+
+    lockdown::log(
+        ctx.http(),
+        &data.pool,
+        &data.settings,
+        guild_id,
+        case_number,
+        "Lockdown",
+        format!(
+            "<@{}> locked down {} {until}.{}",
+            ctx.author().id,
+            locked.join(", "),
+            reason
+                .map(|reason| format!("\n**Reason**: {reason}"))
+                .unwrap_or_default()
+        ),
+    )
+    .await;
+
+    ctx.reply(format!(
+        "Locked down {} channel(s) {until}. Case #{case_number}.",
+        locked.len()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn channel(
+    ctx: Context<'_>,
+    #[channel_types("Text", "News")] channel: GuildChannel,
+    #[description = "How long to lock the channel for, e.g. 30m or 2h. Omit to lock until lifted."]
+    duration: Option<String>,
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let target = format!("<#{}>", channel.id);
+
+    lock_channels(ctx, vec![channel], target, duration, reason).await
+}
+
+#[poise::command(slash_command)]
+async fn all(
+    ctx: Context<'_>,
+    #[description = "How long to lock the server for, e.g. 30m or 2h. Omit to lock until lifted."]
+    duration: Option<String>,
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let channels = guild_id
+        .to_guild_cached(ctx.cache())
+        .map(|guild| {
+            guild
+                .channels
+                .values()
+                .filter(|channel| matches!(channel.kind, ChannelType::Text | ChannelType::News))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    lock_channels(ctx, channels, "all channels".to_string(), duration, reason).await
+}
+
+#[poise::command(slash_command)]
+async fn lift(
+    ctx: Context<'_>,
+    #[description = "Channel to unlock. Omit to lift every active lockdown."]
+    #[channel_types("Text", "News")]
+    channel: Option<ChannelId>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let data = ctx.data();
+
+    ctx.defer().await?;
+
+    let lifted = lockdown::lift(ctx.http(), &data.pool, guild_id, channel).await?;
+
+    if lifted.is_empty() {
+        ctx.reply("There's no active lockdown to lift.").await?;
+        return Ok(());
+    }
+
+    for (channel_id, case_number) in lifted.iter() {
+        lockdown::log(
+            ctx.http(),
+            &data.pool,
+            &data.settings,
+            guild_id,
+            *case_number,
+            "Lockdown lifted",
+            format!(
+                "<@{}> lifted the lockdown of <#{channel_id}>.",
+                ctx.author().id
+            ),
+        )
+        .await;
+    }
+
+    ctx.reply(format!(
+        "Lifted the lockdown of {} channel(s).",
+        lifted.len()
+    ))
+    .await?;
+
+    Ok(())
+}
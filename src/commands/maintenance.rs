@@ -0,0 +1,110 @@
+use crate::{
+    client::{Context, Error},
+    features::GLOBAL_SCOPE,
+    logging::maintenance,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("start", "stop", "status"),
+    owners_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn maintenance(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Resolves the optional `guild` argument to a maintenance scope, replying with an error if it's not a valid ID.
+async fn scope(ctx: Context<'_>, guild: Option<String>) -> Result<Option<String>, Error> {
+    match guild {
+        None => Ok(Some(GLOBAL_SCOPE.to_string())),
+        Some(guild) if guild.parse::<u64>().is_ok() => Ok(Some(guild)),
+        Some(guild) => {
+            ctx.reply(format!("{guild} is not a valid guild ID."))
+                .await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Pauses log posting. Logs are still archived, and queued to be posted once maintenance ends.
+#[poise::command(slash_command)]
+async fn start(
+    ctx: Context<'_>,
+    #[description = "Guild ID to pause logs for. Omit to pause every guild."] guild: Option<String>,
+) -> Result<(), Error> {
+    let Some(scope) = scope(ctx, guild).await? else {
+        return Ok(());
+    };
+
+    if maintenance::start(&ctx.data().pool, &scope, ctx.author().id).await? {
+        ctx.reply(format!(
+            "Maintenance started for {scope}. Logs will be queued until it's stopped."
+        ))
+        .await?;
+    } else {
+        ctx.reply(format!("{scope} is already in maintenance."))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Resumes log posting. Queued logs are posted in the order they happened.
+#[poise::command(slash_command)]
+async fn stop(
+    ctx: Context<'_>,
+    #[description = "Guild ID to resume logs for. Omit to end instance-wide maintenance."]
+    guild: Option<String>,
+) -> Result<(), Error> {
+    let Some(scope) = scope(ctx, guild).await? else {
+        return Ok(());
+    };
+    let pool = &ctx.data().pool;
+
+    if maintenance::stop(pool, &scope).await? {
+        let guild_id = scope.parse::<u64>().ok().map(Into::into);
+        let backlog = maintenance::backlog(pool, guild_id).await?;
+
+        ctx.reply(format!(
+            "Maintenance stopped for {scope}. {backlog} queued logs will be posted shortly."
+        ))
+        .await?;
+    } else {
+        ctx.reply(format!("{scope} isn't in maintenance.")).await?;
+    }
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn status(
+    ctx: Context<'_>,
+    #[description = "Guild ID to check. Omit for instance-wide maintenance."] guild: Option<String>,
+) -> Result<(), Error> {
+    let Some(scope) = scope(ctx, guild).await? else {
+        return Ok(());
+    };
+    let pool = &ctx.data().pool;
+
+    let guild_id = scope.parse::<u64>().ok().map(Into::into);
+    let backlog = maintenance::backlog(pool, guild_id).await?;
+
+    match maintenance::get(pool, &scope).await? {
+        Some(active) => {
+            ctx.reply(format!(
+                "{scope} has been in maintenance since <t:{}:f>, started by <@{}>. {backlog} logs are queued.",
+                active.started_at, active.started_by
+            ))
+            .await?
+        }
+        None => {
+            ctx.reply(format!(
+                "{scope} isn't in maintenance. {backlog} logs are queued."
+            ))
+            .await?
+        }
+    };
+
+    Ok(())
+}
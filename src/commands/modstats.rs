@@ -0,0 +1,84 @@
+use poise::{ChoiceParameter, CreateReply};
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+use crate::{
+    client::{Context, Error},
+    logging::now,
+    mod_stats,
+};
+
+/// How many moderators the list shows.
+const LIST_LENGTH: i64 = 15;
+
+#[derive(Debug, poise::ChoiceParameter, Clone, Copy)]
+pub enum Window {
+    #[name = "Last 24 hours"]
+    Day,
+    #[name = "Last 7 days"]
+    Week,
+    #[name = "Last 30 days"]
+    Month,
+    #[name = "Last 90 days"]
+    Quarter,
+}
+
+impl Window {
+    fn seconds(&self) -> i64 {
+        const DAY: i64 = 24 * 60 * 60;
+
+        match self {
+            Self::Day => DAY,
+            Self::Week => 7 * DAY,
+            Self::Month => 30 * DAY,
+            Self::Quarter => 90 * DAY,
+        }
+    }
+}
+
+/// Shows how many bans, kicks, timeouts and message deletions each moderator made.
+#[poise::command(slash_command, guild_only, default_member_permissions = "MANAGE_GUILD")]
+pub async fn modstats(
+    ctx: Context<'_>,
+    #[description = "How far back to count. Defaults to the last 7 days."] window: Option<Window>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let window = window.unwrap_or(Window::Week);
+    let since = now() as i64 - window.seconds();
+
+    let stats = mod_stats::since(&ctx.data().pool, guild_id, since, LIST_LENGTH).await?;
+
+    let description = if stats.is_empty() {
+        "No moderation actions were attributed to anyone in this time.".to_string()
+    } else {
+        stats
+            .iter()
+            .map(|stats| {
+                format!(
+                    "<@{}> - **{}** ({} bans, {} kicks, {} timeouts, {} deletes)",
+                    stats.moderator,
+                    stats.total(),
+                    stats.bans,
+                    stats.kicks,
+                    stats.timeouts,
+                    stats.deletes
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!(
+            "Moderation actions, {}",
+            window.name().to_lowercase()
+        ))
+        .description(description)
+        .footer(CreateEmbedFooter::new(
+            "Only counts actions the bot could attribute through the audit log.",
+        ));
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use crate::{
+    client::{Context, Error},
+    commands::{parse_duration, preview_routing, LogType},
+    logging::{mutes, now, routing::Routing},
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("route", "lift", "list"),
+    guild_only,
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn mute(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn route(
+    ctx: Context<'_>,
+    log_type: LogType,
+    #[description = "How long to mute the route for, e.g. 30m, 2h or 1d."] duration: String,
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let Some(seconds) = parse_duration(&duration) else {
+        ctx.reply(format!(
+            "{duration} is not a valid duration. Try something like 30m, 2h or 1d."
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let until = (now() + seconds) as i64;
+    let before = Routing::snapshot(ctx.data(), guild_id, None).await?;
+
+    mutes::mute(
+        &ctx.data().pool,
+        guild_id,
+        log_type,
+        ctx.author().id,
+        reason,
+        until,
+    )
+    .await?;
+
+    ctx.reply(format!(
+        "{} are muted until <t:{until}:f>. They'll resume automatically, or use /mute lift to end the mute early.",
+        log_type.to_string()
+    ))
+    .await?;
+
+    preview_routing(ctx, before).await
+}
+
+#[poise::command(slash_command)]
+async fn lift(ctx: Context<'_>, log_type: LogType) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let before = Routing::snapshot(ctx.data(), guild_id, None).await?;
+
+    if mutes::lift(&ctx.data().pool, guild_id, log_type, ctx.author().id).await? {
+        ctx.reply(format!("{} are no longer muted.", log_type.to_string()))
+            .await?;
+    } else {
+        ctx.reply(format!("{} aren't muted.", log_type.to_string()))
+            .await?;
+        return Ok(());
+    }
+
+    preview_routing(ctx, before).await
+}
+
+#[poise::command(slash_command)]
+async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let now = now() as i64;
+
+    let mutes = mutes::recent(&ctx.data().pool, guild_id, 10).await?;
+
+    if mutes.is_empty() {
+        ctx.reply("No routes have been muted in this server.")
+            .await?;
+        return Ok(());
+    }
+
+    let lines = mutes
+        .into_iter()
+        .map(|mute| {
+            let status = match (mute.until > now, mute.lifted_by) {
+                (true, _) => format!("**active** until <t:{}:f>", mute.until),
+                (false, Some(lifted_by)) => {
+                    format!("lifted <t:{}:R> by <@{lifted_by}>", mute.until)
+                }
+                (false, None) => format!("ended <t:{}:R>", mute.until),
+            };
+
+            format!(
+                "{} muted by <@{}> <t:{}:R>, {status}{}",
+                mute.log_type.to_string(),
+                mute.muted_by,
+                mute.muted_at,
+                mute.reason
+                    .map(|reason| format!(": {reason}"))
+                    .unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>();
+
+    ctx.reply(format!("Recent route mutes\n{}", lines.join("\n")))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use poise::CreateReply;
+use serenity::builder::{CreateAttachment, CreateEmbed, CreateEmbedFooter};
+
+use crate::{
+    client::{Context, Error},
+    disclosure,
+    settings::keys,
+};
+
+/// Shows what this server's logs keep about you, and lets you export it if the server allows.
+#[poise::command(slash_command, guild_only)]
+pub async fn mydata(
+    ctx: Context<'_>,
+    #[description = "Also send a copy of the records kept about you."] export: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let data = ctx.data();
+    let user_id = ctx.author().id;
+
+    ctx.defer_ephemeral().await?;
+
+    let exports = data.settings.get(guild_id, &keys::DATA_EXPORTS).await;
+
+    let mut embed = CreateEmbed::new().title("Your data on this server");
+
+    for (name, value) in disclosure::categories(data, guild_id, user_id).await {
+        embed = embed.field(name, value, false);
+    }
+
+    embed = embed.footer(CreateEmbedFooter::new(if exports {
+        "Use /mydata export:True to get a copy of these records."
+    } else {
+        "This server doesn't offer exports. Ask its moderators if you'd like a copy."
+    }));
+
+    let mut reply = CreateReply::default().embed(embed).ephemeral(true);
+
+    if export.unwrap_or(false) {
+        if !exports {
+            reply = reply.content("This server doesn't allow exporting your records.");
+        } else {
+            let (archive, count) = disclosure::export(&data.pool, guild_id, user_id).await?;
+
+            reply = reply
+                .content(format!("Attached are the {count} records kept about you."))
+                .attachment(CreateAttachment::bytes(
+                    archive.into_bytes(),
+                    format!("data-{guild_id}-{user_id}.json"),
+                ));
+        }
+    }
+
+    ctx.send(reply).await?;
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use serenity::all::User;
+
+use crate::{
+    client::{Context, Error},
+    oncall,
+    settings::keys,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("add", "remove", "list", "next", "escalation"),
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn oncall(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn add(ctx: Context<'_>, user: User) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    if oncall::add(&ctx.data().pool, guild_id, user.id).await? {
+        ctx.reply(format!("<@{}> was added to the on-call rotation.", user.id))
+            .await?;
+    } else {
+        ctx.reply(format!(
+            "<@{}> is already in the on-call rotation.",
+            user.id
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn remove(ctx: Context<'_>, user: User) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    if oncall::remove(&ctx.data().pool, guild_id, user.id).await? {
+        ctx.reply(format!(
+            "<@{}> was removed from the on-call rotation.",
+            user.id
+        ))
+        .await?;
+    } else {
+        ctx.reply(format!("<@{}> isn't in the on-call rotation.", user.id))
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let data = ctx.data();
+
+    let rotation = oncall::rotation(&data.pool, guild_id).await?;
+
+    if rotation.is_empty() {
+        ctx.reply("Nobody is in the on-call rotation. Add moderators with /oncall add.")
+            .await?;
+        return Ok(());
+    }
+
+    let pointer = oncall::pointer(&data.settings, guild_id, &rotation).await;
+
+    let lines = rotation
+        .iter()
+        .enumerate()
+        .map(|(index, user_id)| {
+            if index == pointer {
+                format!("{}. <@{user_id}> (on call)", index + 1)
+            } else {
+                format!("{}. <@{user_id}>", index + 1)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    ctx.reply(lines.join("\n")).await?;
+
+    Ok(())
+}
+
+/// Hands on-call over to the next moderator in the rotation.
+#[poise::command(slash_command)]
+async fn next(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let data = ctx.data();
+
+    match oncall::advance(&data.pool, &data.settings, guild_id).await? {
+        Some(user_id) => ctx.reply(format!("<@{user_id}> is now on call.")).await?,
+        None => {
+            ctx.reply("Nobody is in the on-call rotation. Add moderators with /oncall add.")
+                .await?
+        }
+    };
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn escalation(
+    ctx: Context<'_>,
+    #[description = "Minutes to wait for an acknowledgement before paging the next moderator."]
+    #[min = 1]
+    minutes: u32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(
+            guild_id,
+            &keys::ONCALL_ESCALATION_MINUTES,
+            &(minutes as i64),
+        )
+        .await?;
+
+    ctx.reply(format!(
+        "Unacknowledged pages will escalate to the next moderator after {minutes} minutes."
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use serenity::all::ChannelId;
+
+use crate::{
+    client::{Context, Error},
+    commands::parse_duration,
+    logging::now,
+    settings::keys,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("on", "off"),
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn panic(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn on(
+    ctx: Context<'_>,
+    #[description = "How long to stay in panic mode, e.g. 30m or 2h. Defaults to 1h."]
+    duration: Option<String>,
+    #[description = "Staff channel to send all logs to while panic mode is on. Remembered for next time."]
+    #[channel_types("Text")]
+    staff_channel: Option<ChannelId>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    let duration = duration.unwrap_or_else(|| "1h".to_string());
+    let Some(seconds) = parse_duration(&duration) else {
+        ctx.reply(format!(
+            "{duration} is not a valid duration. Try something like 30m or 2h."
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    if staff_channel.is_some() {
+        settings
+            .set(guild_id, &keys::STAFF_CHANNEL, &staff_channel)
+            .await?;
+    }
+
+    let until = (now() + seconds) as i64;
+    settings.set(guild_id, &keys::PANIC_UNTIL, &until).await?;
+
+    let routing = match settings.get(guild_id, &keys::STAFF_CHANNEL).await {
+        Some(channel) => format!("All logs will be sent to <#{channel}>."),
+        None => "Logs keep their usual channels.".to_string(),
+    };
+
+    ctx.reply(format!(
+        "Panic mode is on until <t:{until}:f>. Every event is logged, with filters, damping and route mutes bypassed. {routing}"
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn off(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .unset(guild_id, keys::PANIC_UNTIL.name)
+        .await?;
+
+    ctx.reply("Panic mode is off. Logging is back to normal.")
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use crate::{
+    client::{Context, Error},
+    commands::LogType,
+    logging::quiet_hours,
+    settings::keys,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("set", "clear", "list", "timezone"),
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn quiet_hours(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Holds low-severity logs for a digest during quiet hours, posting only alerts right away.
+#[poise::command(slash_command)]
+async fn set(
+    ctx: Context<'_>,
+    log_type: LogType,
+    #[description = "When quiet hours start, in the server's timezone, e.g. 22:00."] start: String,
+    #[description = "When quiet hours end, e.g. 07:00."] end: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    let (Some(start), Some(end)) = (
+        quiet_hours::parse_time(&start),
+        quiet_hours::parse_time(&end),
+    ) else {
+        ctx.reply("Times should look like 22:00 or 7:30.").await?;
+        return Ok(());
+    };
+
+    if start == end {
+        ctx.reply("Quiet hours need to start and end at different times.")
+            .await?;
+        return Ok(());
+    }
+
+    quiet_hours::set_window(settings, guild_id, log_type, Some((start, end))).await?;
+
+    let offset = settings.get(guild_id, &keys::TIMEZONE_OFFSET).await;
+
+    ctx.reply(format!(
+        "{} will be quiet from {} to {} ({}). Set the server's timezone with /quiet_hours timezone.",
+        log_type.to_string(),
+        quiet_hours::format_time(start),
+        quiet_hours::format_time(end),
+        quiet_hours::format_offset(offset)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn clear(ctx: Context<'_>, log_type: LogType) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    quiet_hours::set_window(&ctx.data().settings, guild_id, log_type, None).await?;
+
+    ctx.reply(format!(
+        "{} no longer have quiet hours. Anything already held back will be posted shortly.",
+        log_type.to_string()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    let mut lines = Vec::new();
+
+    for log_type in LogType::ALL {
+        if let Some((start, end)) = quiet_hours::window(settings, guild_id, log_type).await {
+            lines.push(format!(
+                "{}: {} to {}",
+                log_type.to_string(),
+                quiet_hours::format_time(start),
+                quiet_hours::format_time(end)
+            ));
+        }
+    }
+
+    if lines.is_empty() {
+        ctx.reply("No route has quiet hours.").await?;
+        return Ok(());
+    }
+
+    let offset = settings.get(guild_id, &keys::TIMEZONE_OFFSET).await;
+    lines.push(format!("Timezone: {}", quiet_hours::format_offset(offset)));
+
+    ctx.reply(lines.join("\n")).await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn timezone(
+    ctx: Context<'_>,
+    #[description = "The server's UTC offset, e.g. +02:00 or -5. Daylight saving time isn't adjusted for."]
+    offset: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let Some(offset) = quiet_hours::parse_offset(&offset) else {
+        ctx.reply(format!(
+            "{offset} is not a valid UTC offset. Try something like +02:00 or -5."
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::TIMEZONE_OFFSET, &offset)
+        .await?;
+
+    ctx.reply(format!(
+        "Quiet hours will now use {}.",
+        quiet_hours::format_offset(offset)
+    ))
+    .await?;
+
+    Ok(())
+}
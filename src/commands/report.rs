@@ -0,0 +1,98 @@
+use poise::CreateReply;
+use serenity::all::Message;
+
+use crate::{
+    client::{Context, Error},
+    commands::LogType,
+    logging::{self, formatters::MemberReport, now},
+    settings::keys,
+    user_reports,
+};
+
+const HOUR_SECS: i64 = 60 * 60;
+
+#[poise::command(context_menu_command = "Report to mods", guild_only)]
+pub async fn report(ctx: Context<'_>, message: Message) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let data = ctx.data();
+
+    if LogType::Reports
+        .fetch_channel(&data.pool, guild_id)
+        .await
+        .is_none()
+    {
+        ctx.send(
+            CreateReply::default()
+                .content("This server doesn't take reports through logsalot. Please contact a moderator directly.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if message.author.id == ctx.author().id || message.author.bot {
+        ctx.send(
+            CreateReply::default()
+                .content("You can't report that message.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let limit = data
+        .settings
+        .get(guild_id, &keys::REPORT_LIMIT_PER_HOUR)
+        .await;
+
+    if limit > 0
+        && user_reports::filed_since(
+            &data.pool,
+            guild_id,
+            ctx.author().id,
+            now() as i64 - HOUR_SECS,
+        )
+        .await?
+            >= limit
+    {
+        ctx.send(
+            CreateReply::default()
+                .content("You've sent a lot of reports recently. Please wait a while before reporting again, or contact a moderator directly.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.defer_ephemeral().await?;
+
+    let reporter = user_reports::stats(&data.pool, guild_id, ctx.author().id).await?;
+    let report_id = user_reports::file(&data.pool, guild_id, ctx.author().id, &message).await?;
+
+    let posted = logging::deliver(
+        ctx.serenity_context(),
+        data,
+        &MemberReport,
+        MemberReport::entry(
+            guild_id,
+            report_id,
+            &message,
+            &reporter,
+            data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await,
+        ),
+    )
+    .await?;
+
+    if let Some(log) = posted {
+        user_reports::attach_log(&data.pool, report_id, log).await?;
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .content("Thanks, the moderators have been notified. Your report is confidential.")
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use poise::CreateReply;
+use serenity::{builder::CreateEmbed, utils::parse_message_url};
+
+use crate::{
+    client::{Context, Error},
+    logging::log_messages,
+};
+
+/// Embed fields of our own logs that hold a copy of the message's content.
+const CONTENT_FIELDS: [&str; 3] = ["Content", "Previous", "New"];
+
+fn truncate(content: &str, max: usize) -> String {
+    match content.char_indices().nth(max) {
+        Some((index, _)) => format!("{}…", &content[..index]),
+        None => content.to_string(),
+    }
+}
+
+/// Shows what logsalot knows about a message, even a deleted one, from a message link.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn resolve(
+    ctx: Context<'_>,
+    #[description = "Link to the message, e.g. from a report."] link: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let Some((link_guild, channel_id, message_id)) = parse_message_url(link.trim()) else {
+        ctx.reply(format!("{link} is not a message link.")).await?;
+        return Ok(());
+    };
+
+    if link_guild != guild_id {
+        ctx.reply("That message is from a different server.")
+            .await?;
+        return Ok(());
+    }
+
+    ctx.defer_ephemeral().await?;
+
+    // still-existing messages come from the cache or the API; deleted ones only live on in their logs.
+    let cached = ctx
+        .cache()
+        .message(channel_id, message_id)
+        .map(|message| message.clone());
+    let live = match cached {
+        Some(message) => Some(message),
+        None => channel_id.message(ctx, message_id).await.ok(),
+    };
+
+    let logs = log_messages::about_message(&ctx.data().pool, guild_id, message_id).await?;
+
+    let mut embed = CreateEmbed::new()
+        .title("Message Lookup")
+        .description(format!(
+            "<#{channel_id}> · sent <t:{}:f>",
+            message_id.created_at().unix_timestamp()
+        ));
+
+    let author = live
+        .as_ref()
+        .map(|message| message.author.id)
+        .or_else(|| logs.iter().find_map(|log| log.subject));
+
+    embed = embed.field(
+        "Author",
+        author.map_or("Unknown".to_string(), |author| format!("<@{author}>")),
+        true,
+    );
+    embed = embed.field(
+        "Status",
+        if live.is_some() {
+            "Exists"
+        } else {
+            "Deleted or inaccessible"
+        },
+        true,
+    );
+
+    if let Some(message) = &live {
+        embed = embed.field(
+            "Current Content",
+            if message.content.is_empty() {
+                "None".to_string()
+            } else {
+                truncate(&message.content, 1000)
+            },
+            false,
+        );
+    }
+
+    let mut revisions = Vec::new();
+    let mut related = Vec::new();
+
+    for log in &logs {
+        related.push(format!(
+            "[{}]({}) <t:{}:R>{}",
+            log.kind,
+            log.message_id.link(log.channel_id, Some(guild_id)),
+            log.created_at,
+            log.status
+                .as_ref()
+                .map(|status| format!(" · {status}"))
+                .unwrap_or_default()
+        ));
+
+        let Ok(log_message) = log.channel_id.message(ctx, log.message_id).await else {
+            continue;
+        };
+
+        for field in log_message.embeds.iter().flat_map(|embed| &embed.fields) {
+            if CONTENT_FIELDS.contains(&field.name.as_str()) {
+                revisions.push(format!(
+                    "**{}** (<t:{}:f>): {}",
+                    field.name,
+                    log.created_at,
+                    truncate(&field.value, 200)
+                ));
+            }
+        }
+    }
+
+    if !revisions.is_empty() {
+        embed = embed.field(
+            "Archived Content",
+            truncate(&revisions.join("\n"), 1000),
+            false,
+        );
+    }
+
+    embed = embed.field(
+        "Related Logs",
+        if related.is_empty() {
+            "None".to_string()
+        } else {
+            truncate(&related.join("\n"), 1000)
+        },
+        false,
+    );
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use serenity::all::ChannelId;
+
+use crate::{
+    client::{Context, Error},
+    retention,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("set", "clear", "list"),
+    guild_only,
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn retention(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn set(
+    ctx: Context<'_>,
+    #[channel_types("Text")] channel: ChannelId,
+    #[description = "Delete messages older than this many days. Pinned messages are kept."]
+    #[min = 1]
+    #[max = 3650]
+    days: u32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    retention::set(&ctx.data().pool, guild_id, channel, Some(days as i64)).await?;
+
+    ctx.reply(format!(
+        "Messages in <#{channel}> older than {days} days will be deleted. This runs hourly and is logged as housekeeping."
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn clear(ctx: Context<'_>, #[channel_types("Text")] channel: ChannelId) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    retention::set(&ctx.data().pool, guild_id, channel, None).await?;
+
+    ctx.reply(format!(
+        "Messages in <#{channel}> will no longer be deleted automatically."
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let channels = retention::list(&ctx.data().pool, guild_id).await?;
+
+    if channels.is_empty() {
+        ctx.reply("No channels have a retention period set.")
+            .await?;
+        return Ok(());
+    }
+
+    let lines = channels
+        .into_iter()
+        .map(|(channel, days)| format!("<#{channel}>: {days} days"))
+        .collect::<Vec<_>>();
+
+    ctx.reply(format!("Channel retention\n{}", lines.join("\n")))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use serenity::all::User;
+
+use crate::{
+    client::{Context, Error},
+    logging::{log_messages, triage::TriageStatus},
+};
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn search(
+    ctx: Context<'_>,
+    #[description = "Only show logs moderators triaged with this status."] status: Option<
+        TriageStatus,
+    >,
+    #[description = "Only show logs of this kind, e.g. message_delete."] kind: Option<String>,
+    #[description = "Only show logs about this member."] user: Option<User>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let logs = log_messages::search(
+        &ctx.data().pool,
+        guild_id,
+        status.map(|status| status.as_key()),
+        kind.as_deref(),
+        user.map(|user| user.id),
+        20,
+    )
+    .await?;
+
+    if logs.is_empty() {
+        ctx.reply("No logs match that search.").await?;
+        return Ok(());
+    }
+
+    let lines = logs
+        .into_iter()
+        .map(|log| {
+            format!(
+                "[{}]({}) <t:{}:R>{}{}",
+                log.kind,
+                log.message_id.link(log.channel_id, Some(guild_id)),
+                log.created_at,
+                log.subject
+                    .map(|subject| format!(" about <@{subject}>"))
+                    .unwrap_or_default(),
+                log.status
+                    .map(|status| format!(" · {status}"))
+                    .unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>();
+
+    ctx.reply(lines.join("\n")).await?;
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use poise::CreateReply;
+
+use crate::{
+    client::{Context, Error},
+    logging,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("preview"),
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn template(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn autocomplete_kind<'a>(ctx: Context<'a>, partial: &'a str) -> Vec<&'static str> {
+    ctx.data()
+        .formatters
+        .kinds()
+        .into_iter()
+        .filter(|kind| kind.contains(partial))
+        .take(25)
+        .collect()
+}
+
+#[poise::command(slash_command)]
+async fn preview(
+    ctx: Context<'_>,
+    #[description = "The kind of log to preview, e.g. message_delete."]
+    #[autocomplete = "autocomplete_kind"]
+    kind: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let reply = match logging::preview(ctx.data(), guild_id, &kind, ctx.author()).await {
+        Some(embed) => CreateReply::default().embed(embed),
+        None => CreateReply::default().content(format!("There's no log kind called {kind}.")),
+    };
+
+    ctx.send(reply.ephemeral(true)).await?;
+
+    Ok(())
+}
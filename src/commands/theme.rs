@@ -0,0 +1,240 @@
+use poise::ChoiceParameter;
+
+use crate::{
+    client::{Context, Error},
+    commands::LogType,
+    logging::theme,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("show", "set", "reset", "footer", "footer_reset"),
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn theme(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[derive(Debug, poise::ChoiceParameter, Clone, Copy)]
+pub enum ThemeTarget {
+    #[name = "Added"]
+    Added,
+    #[name = "Changed"]
+    Changed,
+    #[name = "Removed"]
+    Removed,
+    #[name = "Moderation"]
+    Moderation,
+    #[name = "Warning"]
+    Warning,
+    #[name = "Critical"]
+    Critical,
+}
+
+impl ThemeTarget {
+    const ALL: [Self; 6] = [
+        Self::Added,
+        Self::Changed,
+        Self::Removed,
+        Self::Moderation,
+        Self::Warning,
+        Self::Critical,
+    ];
+
+    fn as_key(&self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Changed => "changed",
+            Self::Removed => "removed",
+            Self::Moderation => "moderation",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+fn parse_colour(value: &str) -> Option<u32> {
+    u32::from_str_radix(value.trim().trim_start_matches('#'), 16)
+        .ok()
+        .filter(|colour| *colour <= 0xFFFFFF)
+}
+
+#[poise::command(slash_command)]
+async fn show(ctx: Context<'_>) -> Result<(), Error> {
+    let settings = &ctx.data().settings;
+    let guild_id = ctx.guild_id().unwrap();
+
+    let mut lines = Vec::new();
+
+    for target in ThemeTarget::ALL {
+        let style = theme::style(settings, guild_id, target.as_key()).await;
+
+        lines.push(format!(
+            "{} **{}**: `#{:06X}`",
+            style.emoji,
+            target.name(),
+            style.colour.0
+        ));
+    }
+
+    for log_type in LogType::ALL {
+        let footer = settings
+            .get_raw(guild_id, &theme::footer_key(Some(log_type), "text"))
+            .await;
+        if let Some(footer) = footer {
+            lines.push(format!("**{} footer**: {footer}", log_type.name()));
+        }
+    }
+
+    if let Some(footer) = settings
+        .get_raw(guild_id, &theme::footer_key(None, "text"))
+        .await
+    {
+        lines.push(format!("**Footer**: {footer}"));
+    }
+
+    ctx.reply(format!("Log theme\n{}", lines.join("\n")))
+        .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn set(
+    ctx: Context<'_>,
+    target: ThemeTarget,
+    #[description = "Hex colour, e.g. #FF0000"] colour: Option<String>,
+    emoji: Option<String>,
+) -> Result<(), Error> {
+    let settings = &ctx.data().settings;
+    let guild_id = ctx.guild_id().unwrap();
+
+    let colour = match colour.as_deref().map(parse_colour) {
+        Some(None) => {
+            ctx.reply("That's not a valid hex colour.").await?;
+            return Ok(());
+        }
+        Some(Some(colour)) => Some(colour),
+        None => None,
+    };
+
+    let key = target.as_key();
+
+    if let Some(colour) = colour {
+        settings
+            .set_raw(guild_id, &theme::colour_key(key), colour.to_string())
+            .await?;
+    }
+
+    if let Some(emoji) = emoji {
+        settings
+            .set_raw(guild_id, &theme::emoji_key(key), emoji)
+            .await?;
+    }
+
+    ctx.reply(format!("Updated the {} style.", target.name()))
+        .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn reset(ctx: Context<'_>, target: ThemeTarget) -> Result<(), Error> {
+    let settings = &ctx.data().settings;
+    let guild_id = ctx.guild_id().unwrap();
+    let key = target.as_key();
+
+    settings.unset(guild_id, &theme::colour_key(key)).await?;
+    settings.unset(guild_id, &theme::emoji_key(key)).await?;
+
+    ctx.reply(format!("Reset the {} style to its default.", target.name()))
+        .await?;
+
+    Ok(())
+}
+
+/// Sets the footer shown on log embeds, either for every log type or just one.
+#[poise::command(slash_command)]
+async fn footer(
+    ctx: Context<'_>,
+    #[description = "Footer text, e.g. the server or team name"] text: Option<String>,
+    #[description = "Link to a footer icon"] icon: Option<String>,
+    #[description = "Only brand this log type. Leave empty for all of them."] route: Option<
+        LogType,
+    >,
+) -> Result<(), Error> {
+    let settings = &ctx.data().settings;
+    let guild_id = ctx.guild_id().unwrap();
+
+    if text.is_none() && icon.is_none() {
+        ctx.reply("Give a footer text, an icon, or both.").await?;
+        return Ok(());
+    }
+
+    if icon
+        .as_deref()
+        .is_some_and(|icon| !icon.starts_with("https://") && !icon.starts_with("http://"))
+    {
+        ctx.reply("The icon has to be a link to an image.").await?;
+        return Ok(());
+    }
+
+    if text
+        .as_deref()
+        .is_some_and(|text| text.chars().count() > 2048)
+    {
+        ctx.reply("Footers can be at most 2048 characters long.")
+            .await?;
+        return Ok(());
+    }
+
+    if let Some(text) = text {
+        settings
+            .set_raw(guild_id, &theme::footer_key(route, "text"), text)
+            .await?;
+    }
+
+    if let Some(icon) = icon {
+        settings
+            .set_raw(guild_id, &theme::footer_key(route, "icon"), icon)
+            .await?;
+    }
+
+    ctx.reply(match route {
+        Some(route) => format!("Updated the footer for {}.", route.name()),
+        None => "Updated the footer for all logs.".to_string(),
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Removes a custom log footer.
+#[poise::command(slash_command)]
+async fn footer_reset(
+    ctx: Context<'_>,
+    #[description = "The log type to reset. Leave empty for the footer shared by all of them."]
+    route: Option<LogType>,
+) -> Result<(), Error> {
+    let settings = &ctx.data().settings;
+    let guild_id = ctx.guild_id().unwrap();
+
+    settings
+        .unset(guild_id, &theme::footer_key(route, "text"))
+        .await?;
+    settings
+        .unset(guild_id, &theme::footer_key(route, "icon"))
+        .await?;
+
+    ctx.reply(match route {
+        Some(route) => format!(
+            "Removed the footer for {}. It uses the shared footer again, if there is one.",
+            route.name()
+        ),
+        None => "Removed the shared log footer.".to_string(),
+    })
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use poise::CreateReply;
+use serenity::all::{RoleId, User};
+
+use crate::{
+    client::{Context, Error},
+    logging::trusted,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("add", "remove", "list", "review"),
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn trusted(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn add(ctx: Context<'_>, role: RoleId) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    trusted::set_trusted(&ctx.data().pool, guild_id, role, true).await?;
+
+    ctx.reply(format!(
+        "Message edits and deletions by members with <@&{role}> will be archived instead of posted. Review them with /trusted review."
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn remove(ctx: Context<'_>, role: RoleId) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    if trusted::set_trusted(&ctx.data().pool, guild_id, role, false).await? {
+        ctx.reply(format!("<@&{role}> is no longer trusted."))
+            .await?;
+    } else {
+        ctx.reply(format!("<@&{role}> wasn't trusted.")).await?;
+    }
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let roles = trusted::trusted_roles(&ctx.data().pool, guild_id).await?;
+
+    if roles.is_empty() {
+        ctx.reply("No roles are trusted.").await?;
+        return Ok(());
+    }
+
+    ctx.reply(format!(
+        "Trusted roles: {}",
+        roles
+            .iter()
+            .map(|role| format!("<@&{role}>"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn review(
+    ctx: Context<'_>,
+    #[description = "Only show logs about this member."] user: Option<User>,
+    #[description = "How many logs to show, newest first."]
+    #[min = 1]
+    #[max = 10]
+    limit: Option<u8>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let embeds = trusted::review(
+        &ctx.data().pool,
+        guild_id,
+        user.map(|user| user.id),
+        limit.unwrap_or(5) as i64,
+    )
+    .await?;
+
+    let reply = if embeds.is_empty() {
+        CreateReply::default().content("There are no suppressed logs to review.")
+    } else {
+        embeds
+            .into_iter()
+            .fold(CreateReply::default(), |reply, embed| reply.embed(embed))
+    };
+
+    ctx.send(reply.ephemeral(true)).await?;
+
+    Ok(())
+}
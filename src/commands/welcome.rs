@@ -0,0 +1,92 @@
+use poise::CreateReply;
+use serenity::all::ChannelId;
+
+use crate::{
+    client::{Context, Error},
+    member_counts,
+    settings::keys,
+    welcomes,
+};
+
+#[poise::command(
+    slash_command,
+    subcommands("enable", "disable", "preview"),
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn welcome(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn enable(
+    ctx: Context<'_>,
+    #[description = "Channel to greet new members in. Omit to use the server's system channel."]
+    #[channel_types("Text")]
+    channel: Option<ChannelId>,
+    #[description = "Message template. Supports {user}, {name}, {server} and {member_count}."]
+    template: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let settings = &ctx.data().settings;
+
+    settings.set(guild_id, &keys::WELCOMES, &true).await?;
+    settings
+        .set(guild_id, &keys::WELCOME_CHANNEL, &channel)
+        .await?;
+
+    if let Some(template) = template {
+        settings
+            .set(guild_id, &keys::WELCOME_TEMPLATE, &template)
+            .await?;
+    }
+
+    ctx.reply(match channel {
+        Some(channel) => format!("New members will be welcomed in <#{channel}>. Use /welcome preview to see how it looks."),
+        None => "New members will be welcomed in the system channel. Use /welcome preview to see how it looks.".to_string(),
+    })
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    ctx.data()
+        .settings
+        .set(guild_id, &keys::WELCOMES, &false)
+        .await?;
+
+    ctx.reply("New members will no longer be welcomed publicly. Member logs are unaffected.")
+        .await?;
+
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+async fn preview(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+
+    let template = ctx
+        .data()
+        .settings
+        .get(guild_id, &keys::WELCOME_TEMPLATE)
+        .await;
+
+    let member_count = member_counts::get(&ctx.data().pool, guild_id).await?;
+
+    let content = guild_id
+        .to_guild_cached(ctx.cache())
+        .map(|guild| {
+            let member_count = member_count.map_or(guild.member_count, |count| count as u64);
+            welcomes::render(&template, ctx.author(), &guild, member_count)
+        })
+        .unwrap_or(template);
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use serde_json::{Map, Value};
+use serenity::all::{GuildId, UserId};
+use sqlx::{Pool, Row, Sqlite};
+
+use crate::{
+    client::Error,
+    logging::now,
+    settings::{keys, Settings},
+    transfer,
+};
+
+/// Settings that track what the bot is doing rather than how it's configured, so rolling back leaves them alone.
+const STATE_KEYS: [&str; 4] = [
+    keys::LAST_WEEKLY_REPORT.name,
+    keys::LAST_ANOMALY_CHECK.name,
+    keys::ONCALL_POINTER.name,
+    keys::PANIC_UNTIL.name,
+];
+
+/// The tables holding a guild's configuration, and the condition selecting its rows, with the guild ID bound as `?1`.
+/// Logs, cases and other history aren't configuration and are never touched by a rollback.
+fn config_tables() -> Vec<(&'static str, String)> {
+    let state_keys = STATE_KEYS
+        .iter()
+        .map(|key| format!("'{key}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    vec![
+        (
+            "guild_settings",
+            format!("guild_id = ?1 AND key NOT IN ({state_keys})"),
+        ),
+        ("log_channels", "guild_id = ?1".to_string()),
+        ("trusted_roles", "guild_id = ?1".to_string()),
+        ("channel_retention", "guild_id = ?1".to_string()),
+        ("oncall_rotation", "guild_id = ?1".to_string()),
+        ("feature_flags", "scope = ?1".to_string()),
+        ("ignores", "scope = ?1".to_string()),
+    ]
+}
+
+/// Saves `guild_id`'s current configuration as `name`, replacing any snapshot by that name.
+/// Returns how many rows were saved.
+pub async fn save(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    name: &str,
+    created_by: UserId,
+) -> Result<usize, Error> {
+    let tables = transfer::tables(pool).await?;
+    let mut saved = Map::new();
+    let mut count = 0;
+
+    for (table, condition) in config_tables() {
+        let Some((_, columns)) = tables.iter().find(|(name, _)| name == table) else {
+            continue;
+        };
+
+        let fields = columns
+            .iter()
+            .map(|column| format!("'{column}', \"{column}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let rows: String = sqlx::query(&format!(
+            "SELECT json_group_array(json_object({fields})) AS rows FROM \"{table}\" WHERE {condition}"
+        ))
+        .bind(guild_id.to_string())
+        .fetch_one(pool)
+        .await?
+        .get("rows");
+
+        let rows: Value = serde_json::from_str(&rows)?;
+        count += rows.as_array().map_or(0, Vec::len);
+        saved.insert(table.to_string(), rows);
+    }
+
+    let guild_id = guild_id.to_string();
+    let created_by = created_by.to_string();
+    let now = now() as i64;
+    let data = Value::Object(saved).to_string();
+
+    sqlx::query!(
+        "INSERT INTO config_snapshots (guild_id, name, created_by, created_at, data) VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT (guild_id, name) DO UPDATE SET created_by = excluded.created_by, created_at = excluded.created_at, data = excluded.data",
+        guild_id,
+        name,
+        created_by,
+        now,
+        data
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(count)
+}
+
+/// Replaces `guild_id`'s configuration with the snapshot called `name`.
+/// Returns how many rows were restored, or `None` if there's no such snapshot.
+pub async fn rollback(
+    pool: &Pool<Sqlite>,
+    settings: &Settings,
+    guild_id: GuildId,
+    name: &str,
+) -> Result<Option<usize>, Error> {
+    let guild_id_string = guild_id.to_string();
+
+    let Some(row) = sqlx::query!(
+        "SELECT data FROM config_snapshots WHERE guild_id = ? AND name = ?",
+        guild_id_string,
+        name
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let data: Value = serde_json::from_str(&row.data)?;
+    let tables = transfer::tables(pool).await?;
+
+    let mut transaction = pool.begin().await?;
+    let mut count = 0;
+
+    for (table, condition) in config_tables() {
+        let Some((_, columns)) = tables.iter().find(|(name, _)| name == table) else {
+            continue;
+        };
+
+        sqlx::query(&format!("DELETE FROM \"{table}\" WHERE {condition}"))
+            .bind(&guild_id_string)
+            .execute(&mut *transaction)
+            .await?;
+
+        let Some(rows) = data.get(table).and_then(Value::as_array) else {
+            continue;
+        };
+
+        let Some(first) = rows.first().and_then(Value::as_object) else {
+            continue;
+        };
+
+        // snapshots taken before a migration may lack newer columns, which are left at their defaults.
+        let columns = columns
+            .iter()
+            .filter(|column| first.contains_key(*column))
+            .collect::<Vec<_>>();
+
+        let names = columns
+            .iter()
+            .map(|column| format!("\"{column}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let values = columns
+            .iter()
+            .map(|column| format!("json_extract(value, '$.\"{column}\"')"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let result = sqlx::query(&format!(
+            "INSERT INTO \"{table}\" ({names}) SELECT {values} FROM json_each(?)"
+        ))
+        .bind(Value::Array(rows.clone()).to_string())
+        .execute(&mut *transaction)
+        .await?;
+
+        count += result.rows_affected() as usize;
+    }
+
+    transaction.commit().await?;
+
+    settings.invalidate(guild_id);
+
+    Ok(Some(count))
+}
+
+/// The names of `guild_id`'s snapshots, newest first.
+pub async fn names(pool: &Pool<Sqlite>, guild_id: GuildId) -> Result<Vec<String>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    let rows = sqlx::query!(
+        "SELECT name FROM config_snapshots WHERE guild_id = ? ORDER BY created_at DESC",
+        guild_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.name).collect())
+}
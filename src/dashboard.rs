@@ -0,0 +1,125 @@
+use sqlx::{Pool, Sqlite};
+
+use crate::logging::{anomalies::current_hour, maintenance, now};
+
+/// How many hours the dashboard covers, one sparkline column each.
+pub const HOURS: i64 = 24;
+const TOP_GUILDS: i64 = 5;
+
+/// Counts a log that failed to post towards the current hour.
+pub async fn record_failure(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+    let hour = current_hour();
+    let cutoff = hour - HOURS;
+
+    sqlx::query!(
+        "INSERT INTO delivery_failures (hour, count) VALUES (?, 1)
+        ON CONFLICT (hour) DO UPDATE SET count = count + 1",
+        hour
+    )
+    .execute(pool)
+    .await?;
+
+    // failures are rare enough that pruning as they come in is cheaper than a task of its own.
+    sqlx::query!("DELETE FROM delivery_failures WHERE hour < ?", cutoff)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub struct Overview {
+    /// Logs per minute so far this hour.
+    pub events_per_minute: f64,
+    /// Logs per hour, oldest first, ending with the current hour.
+    pub hourly_events: Vec<i64>,
+    /// Failed posts per hour, lined up with `hourly_events`.
+    pub hourly_failures: Vec<i64>,
+    /// Logs queued for the end of maintenance.
+    pub maintenance_backlog: i64,
+    /// Logs held back for quiet hours digests.
+    pub digest_backlog: i64,
+    /// The guilds with the most logs over the covered hours, and how many each had.
+    pub top_guilds: Vec<(String, i64)>,
+}
+
+impl Overview {
+    pub fn total_events(&self) -> i64 {
+        self.hourly_events.iter().sum()
+    }
+
+    pub fn total_failures(&self) -> i64 {
+        self.hourly_failures.iter().sum()
+    }
+
+    /// The share of logs that failed to post, as a percentage.
+    pub fn error_rate(&self) -> f64 {
+        self.total_failures() as f64 * 100.0 / self.total_events().max(1) as f64
+    }
+}
+
+/// Lines hourly counts up into one value per covered hour, filling in hours without any.
+fn by_hour(rows: impl IntoIterator<Item = (i64, i64)>, current_hour: i64) -> Vec<i64> {
+    let first_hour = current_hour - HOURS + 1;
+    let mut counts = vec![0; HOURS as usize];
+
+    for (hour, count) in rows {
+        if let Some(slot) = hour
+            .checked_sub(first_hour)
+            .and_then(|offset| counts.get_mut(offset as usize))
+        {
+            *slot = count;
+        }
+    }
+
+    counts
+}
+
+/// Instance-wide log volume and health over the last [`HOURS`] hours.
+pub async fn overview(pool: &Pool<Sqlite>) -> Result<Overview, sqlx::Error> {
+    let hour = current_hour();
+    let since = hour - HOURS + 1;
+
+    let events = sqlx::query!(
+        r#"SELECT hour AS "hour!: i64", SUM(count) AS "count!: i64" FROM event_counts
+        WHERE hour >= ? GROUP BY hour"#,
+        since
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let failures = sqlx::query!(
+        r#"SELECT hour AS "hour!: i64", count AS "count!: i64" FROM delivery_failures WHERE hour >= ?"#,
+        since
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let top_guilds = sqlx::query!(
+        r#"SELECT guild_id, SUM(count) AS "count!: i64" FROM event_counts
+        WHERE hour >= ? GROUP BY guild_id ORDER BY SUM(count) DESC LIMIT ?"#,
+        since,
+        TOP_GUILDS
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let digest_backlog = sqlx::query!(r#"SELECT COUNT(*) AS "count!: i64" FROM digest_entries"#)
+        .fetch_one(pool)
+        .await?
+        .count;
+
+    let hourly_events = by_hour(events.iter().map(|row| (row.hour, row.count)), hour);
+    let minutes_this_hour = (now() % (60 * 60)) / 60 + 1;
+
+    Ok(Overview {
+        events_per_minute: *hourly_events.last().unwrap_or(&0) as f64 / minutes_this_hour as f64,
+        hourly_events,
+        hourly_failures: by_hour(failures.iter().map(|row| (row.hour, row.count)), hour),
+        maintenance_backlog: maintenance::backlog(pool, None).await?,
+        digest_backlog,
+        top_guilds: top_guilds
+            .into_iter()
+            .map(|row| (row.guild_id, row.count))
+            .collect(),
+    })
+}
@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+#[derive(Clone, Debug)]
+pub struct AsymmetricDiff<T> {
+    pub added: Vec<T>,
+    pub removed: Vec<T>,
+}
+
+impl<T> AsymmetricDiff<T> {
+    /// Pairs up removed and added items that share a key (e.g. an attachment re-uploaded under the same filename)
+    /// and takes them out of `removed`/`added`. Pairs are returned as `(removed, added)`, in removal order.
+    pub fn extract_moved<K: Eq + Hash>(&mut self, key: impl Fn(&T) -> K) -> Vec<(T, T)> {
+        let mut added_by_key: HashMap<K, usize> = HashMap::new();
+        for (index, item) in self.added.iter().enumerate() {
+            added_by_key.entry(key(item)).or_insert(index);
+        }
+
+        let mut pairs = Vec::new();
+        let mut taken = HashSet::new();
+
+        for (removed_index, item) in self.removed.iter().enumerate() {
+            if let Some(&added_index) = added_by_key.get(&key(item))
+                && taken.insert(added_index)
+            {
+                pairs.push((removed_index, added_index));
+            }
+        }
+
+        let mut removed = std::mem::take(&mut self.removed)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        let mut added = std::mem::take(&mut self.added)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        let moved = pairs
+            .into_iter()
+            .map(|(r, a)| (removed[r].take().unwrap(), added[a].take().unwrap()))
+            .collect();
+
+        self.removed = removed.into_iter().flatten().collect();
+        self.added = added.into_iter().flatten().collect();
+
+        moved
+    }
+}
+
+/// Diffs two collections by `key`, keeping the original order of `from` for removals and of `to` for additions.
+pub fn asymmetric_diff_by<T: Clone, K: Eq + Hash>(
+    from: &[T],
+    to: &[T],
+    key: impl Fn(&T) -> K,
+) -> AsymmetricDiff<T> {
+    let from_keys = from.iter().map(&key).collect::<HashSet<_>>();
+    let to_keys = to.iter().map(&key).collect::<HashSet<_>>();
+
+    let mut seen = HashSet::new();
+    let removed = from
+        .iter()
+        .filter(|item| !to_keys.contains(&key(item)))
+        .filter(|item| seen.insert(key(item)))
+        .cloned()
+        .collect();
+
+    let mut seen = HashSet::new();
+    let added = to
+        .iter()
+        .filter(|item| !from_keys.contains(&key(item)))
+        .filter(|item| seen.insert(key(item)))
+        .cloned()
+        .collect();
+
+    AsymmetricDiff { added, removed }
+}
+
+pub fn asymmetric_diff<T: Clone + Eq + Hash>(from: &[T], to: &[T]) -> AsymmetricDiff<T> {
+    asymmetric_diff_by(from, to, |item| item.clone())
+}
+
+/// Items present on both sides under the same key whose contents differ (e.g. a renamed emoji), as `(old, new)`.
+pub fn changed_by<T: Clone + PartialEq, K: Eq + Hash>(
+    from: &[T],
+    to: &[T],
+    key: impl Fn(&T) -> K,
+) -> Vec<(T, T)> {
+    let old_by_key = from
+        .iter()
+        .map(|item| (key(item), item))
+        .collect::<HashMap<_, _>>();
+
+    to.iter()
+        .filter_map(|new| {
+            let old = old_by_key.get(&key(new))?;
+            (*old != new).then(|| ((*old).clone(), new.clone()))
+        })
+        .collect()
+}
+
+/// Above this many line pairs, lines between the common start and end are shown as replaced wholesale
+/// instead of being diffed, to bound the memory the diff takes.
+const MAX_LINE_DIFF_CELLS: usize = 1_000_000;
+
+/// A line-by-line diff of `from` and `to` in the format of a `diff` code block:
+/// removed lines start with `-`, added lines with `+`, and unchanged lines with a space.
+pub fn line_diff(from: &str, to: &str) -> String {
+    let from = from.lines().collect::<Vec<_>>();
+    let to = to.lines().collect::<Vec<_>>();
+
+    let prefix = from
+        .iter()
+        .zip(&to)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = from[prefix..]
+        .iter()
+        .rev()
+        .zip(to[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let old = &from[prefix..from.len() - suffix];
+    let new = &to[prefix..to.len() - suffix];
+
+    let mut lines = from[..prefix]
+        .iter()
+        .map(|line| format!("  {line}"))
+        .collect::<Vec<_>>();
+
+    if old.len() * new.len() > MAX_LINE_DIFF_CELLS {
+        lines.extend(old.iter().map(|line| format!("- {line}")));
+        lines.extend(new.iter().map(|line| format!("+ {line}")));
+    } else {
+        // longest[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+        let mut longest = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                longest[i][j] = if old[i] == new[j] {
+                    longest[i + 1][j + 1] + 1
+                } else {
+                    longest[i + 1][j].max(longest[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+
+        while i < old.len() || j < new.len() {
+            if i < old.len() && j < new.len() && old[i] == new[j] {
+                lines.push(format!("  {}", old[i]));
+                i += 1;
+                j += 1;
+            } else if i < old.len() && (j == new.len() || longest[i + 1][j] >= longest[i][j + 1]) {
+                lines.push(format!("- {}", old[i]));
+                i += 1;
+            } else {
+                lines.push(format!("+ {}", new[j]));
+                j += 1;
+            }
+        }
+    }
+
+    lines.extend(
+        from[from.len() - suffix..]
+            .iter()
+            .map(|line| format!("  {line}")),
+    );
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (id, filename)
+    type File = (u32, &'static str);
+
+    fn diff(from: &[File], to: &[File]) -> AsymmetricDiff<File> {
+        asymmetric_diff_by(from, to, |(id, _)| *id)
+    }
+
+    #[test]
+    fn additions_keep_their_order() {
+        let difference = diff(&[(1, "a")], &[(3, "c"), (1, "a"), (2, "b")]);
+
+        assert_eq!(difference.added, vec![(3, "c"), (2, "b")]);
+        assert!(difference.removed.is_empty());
+    }
+
+    #[test]
+    fn removals_are_deduplicated_by_key() {
+        let difference = diff(&[(1, "a"), (2, "b"), (2, "b"), (3, "c")], &[(3, "c")]);
+
+        assert_eq!(difference.removed, vec![(1, "a"), (2, "b")]);
+        assert!(difference.added.is_empty());
+    }
+
+    #[test]
+    fn moved_items_are_paired_in_removal_order() {
+        let mut difference = diff(
+            &[(1, "b.png"), (2, "a.png"), (3, "gone.png")],
+            &[(4, "a.png"), (5, "b.png"), (6, "new.png")],
+        );
+
+        let moved = difference.extract_moved(|(_, name)| *name);
+
+        assert_eq!(
+            moved,
+            vec![((1, "b.png"), (5, "b.png")), ((2, "a.png"), (4, "a.png"))]
+        );
+        assert_eq!(difference.removed, vec![(3, "gone.png")]);
+        assert_eq!(difference.added, vec![(6, "new.png")]);
+    }
+
+    #[test]
+    fn each_addition_replaces_at_most_one_removal() {
+        let mut difference = diff(&[(1, "a.png"), (2, "a.png")], &[(3, "a.png")]);
+
+        let moved = difference.extract_moved(|(_, name)| *name);
+
+        assert_eq!(moved, vec![((1, "a.png"), (3, "a.png"))]);
+        assert_eq!(difference.removed, vec![(2, "a.png")]);
+        assert!(difference.added.is_empty());
+    }
+
+    #[test]
+    fn changes_are_matched_by_key() {
+        let changed = changed_by(
+            &[(1, "old"), (2, "same"), (3, "removed")],
+            &[(2, "same"), (1, "new"), (4, "added")],
+            |(id, _)| *id,
+        );
+
+        assert_eq!(changed, vec![((1, "old"), (1, "new"))]);
+    }
+}
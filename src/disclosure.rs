@@ -0,0 +1,202 @@
+use poise::ChoiceParameter;
+use serde_json::{json, Map, Value};
+use serenity::all::{GuildId, UserId};
+use sqlx::{Pool, Row, Sqlite};
+
+use crate::{
+    client::{Data, Error},
+    commands::LogType,
+    logging::now,
+    retention,
+    settings::keys,
+    transfer,
+};
+
+/// The tables holding records about a member, the column naming them, and how they're described to the member.
+/// Reports about a member are left out, since they'd reveal who reported them.
+const PERSONAL_RECORDS: [(&str, &str, &str); 8] = [
+    ("cases", "target", "Moderation cases about you"),
+    ("log_messages", "subject_id", "Logs about you"),
+    (
+        "suppressed_logs",
+        "user_id",
+        "Logs about you held back for review",
+    ),
+    ("incidents", "subject_id", "Alerts about you"),
+    (
+        "member_verification",
+        "user_id",
+        "When you joined and verified",
+    ),
+    ("boosts", "user_id", "When you boosted the server"),
+    (
+        "edit_versions",
+        "author_id",
+        "Full versions of your long edited messages",
+    ),
+    ("user_reports", "reporter_id", "Messages you reported"),
+];
+
+/// How many records each of [`PERSONAL_RECORDS`] holds about `user_id`, leaving out empty ones.
+async fn record_counts(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<Vec<(&'static str, i64)>, sqlx::Error> {
+    let mut counts = Vec::new();
+
+    for (table, column, description) in PERSONAL_RECORDS {
+        let count: i64 = sqlx::query(&format!(
+            "SELECT COUNT(*) AS count FROM \"{table}\" WHERE guild_id = ? AND \"{column}\" = ?"
+        ))
+        .bind(guild_id.to_string())
+        .bind(user_id.to_string())
+        .fetch_one(pool)
+        .await?
+        .get("count");
+
+        if count > 0 {
+            counts.push((description, count));
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Describes what the guild's logs capture about members, as configured right now,
+/// and what the bot keeps about `user_id` in particular.
+pub async fn categories(data: &Data, guild_id: GuildId, user_id: UserId) -> Vec<(String, String)> {
+    let settings = &data.settings;
+    let mut categories = Vec::new();
+
+    let mut routes = Vec::new();
+    for log_type in LogType::ALL {
+        if let Some(channel) = log_type.fetch_channel(&data.pool, guild_id).await {
+            routes.push((log_type, channel));
+        }
+    }
+
+    let logged = |log_type: LogType| routes.iter().any(|(route, _)| *route == log_type);
+
+    if logged(LogType::Chat) {
+        let content = if settings.get(guild_id, &keys::METADATA_ONLY).await {
+            "Only the length and a fingerprint of your deleted and edited messages are logged, not what they said."
+        } else {
+            "The content and attachments of your deleted and edited messages are copied to the logs."
+        };
+        categories.push(("Message content".to_string(), content.to_string()));
+
+        if settings.get(guild_id, &keys::LOG_REACTIONS).await {
+            categories.push((
+                "Reactions".to_string(),
+                "Reactions you add and remove are logged.".to_string(),
+            ));
+        }
+    }
+
+    if logged(LogType::Member) {
+        categories.push((
+            "Membership".to_string(),
+            "Joining, leaving, and changes to your roles, nickname and boosts are logged."
+                .to_string(),
+        ));
+    }
+
+    if logged(LogType::Voice) {
+        categories.push((
+            "Voice".to_string(),
+            "Joining, leaving and moving between voice channels is logged.".to_string(),
+        ));
+    }
+
+    if logged(LogType::Moderation) {
+        categories.push((
+            "Moderation".to_string(),
+            "Bans, kicks, timeouts and the cases opened for them are logged.".to_string(),
+        ));
+    }
+
+    let retained = retention::list(&data.pool, guild_id)
+        .await
+        .unwrap_or_default();
+
+    let retention = match routes.is_empty() {
+        true => "This server doesn't log anything.".to_string(),
+        false => routes
+            .iter()
+            .map(|(log_type, channel)| {
+                let days = retained
+                    .iter()
+                    .find(|(retained, _)| retained == channel)
+                    .map(|(_, days)| *days);
+
+                match days {
+                    Some(days) => format!("{}: kept for {days} days", log_type.name()),
+                    None => format!("{}: kept until moderators remove them", log_type.name()),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+
+    categories.push(("Retention".to_string(), retention));
+
+    let records = match record_counts(&data.pool, guild_id, user_id).await {
+        Ok(counts) if counts.is_empty() => "None".to_string(),
+        Ok(counts) => counts
+            .into_iter()
+            .map(|(description, count)| format!("{description}: {count}"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Err(_) => "Unknown".to_string(),
+    };
+
+    categories.push(("Records kept about you".to_string(), records));
+
+    categories
+}
+
+/// Every record about `user_id` in `guild_id`, as a JSON archive. Returns the archive and how many records it holds.
+pub async fn export(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<(String, usize), Error> {
+    let tables = transfer::tables(pool).await?;
+    let mut exported = Map::new();
+    let mut count = 0;
+
+    for (table, column, _) in PERSONAL_RECORDS {
+        let Some((_, columns)) = tables.iter().find(|(name, _)| name == table) else {
+            continue;
+        };
+
+        let fields = columns
+            .iter()
+            .map(|column| format!("'{column}', \"{column}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let rows: String = sqlx::query(&format!(
+            "SELECT json_group_array(json_object({fields})) AS rows FROM \"{table}\" WHERE guild_id = ? AND \"{column}\" = ?"
+        ))
+        .bind(guild_id.to_string())
+        .bind(user_id.to_string())
+        .fetch_one(pool)
+        .await?
+        .get("rows");
+
+        let rows: Value = serde_json::from_str(&rows)?;
+        count += rows.as_array().map_or(0, Vec::len);
+        exported.insert(table.to_string(), rows);
+    }
+
+    let archive = json!({
+        "guild_id": guild_id.to_string(),
+        "user_id": user_id.to_string(),
+        "exported_at": now(),
+        "records": exported,
+    });
+
+    Ok((serde_json::to_string_pretty(&archive)?, count))
+}
@@ -0,0 +1,111 @@
+use std::{collections::HashMap, str::FromStr};
+
+use poise::serenity_prelude::*;
+use sqlx::{Pool, Sqlite};
+
+use crate::{client::Data, logging::now};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Counts the custom emojis in `content`, by ID.
+fn count_emojis(content: &str) -> HashMap<EmojiId, i64> {
+    let mut counts = HashMap::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+
+        if let Some(emoji) = serenity::utils::parse_emoji(&rest[..=end]) {
+            *counts.entry(emoji.id).or_default() += 1;
+            rest = &rest[end + 1..];
+        } else {
+            rest = &rest[1..];
+        }
+    }
+
+    counts
+}
+
+async fn record(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    counts: HashMap<EmojiId, i64>,
+) -> Result<(), sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let day = (now() / SECONDS_PER_DAY) as i64;
+
+    for (emoji_id, count) in counts {
+        let emoji_id = emoji_id.to_string();
+
+        sqlx::query!(
+            "INSERT INTO emoji_usage (guild_id, emoji_id, day, count) VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id, emoji_id, day) DO UPDATE SET count = count + excluded.count",
+            guild_id,
+            emoji_id,
+            day,
+            count
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// How often each emoji was used in the last `days` days. Emojis that weren't used are left out.
+pub async fn usage(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    days: u64,
+) -> Result<HashMap<EmojiId, i64>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let since = (now() / SECONDS_PER_DAY).saturating_sub(days) as i64;
+
+    let rows = sqlx::query!(
+        r#"SELECT emoji_id, SUM(count) AS "count!: i64" FROM emoji_usage WHERE guild_id = ? AND day > ? GROUP BY emoji_id"#,
+        guild_id,
+        since
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| Some((EmojiId::from_str(&row.emoji_id).ok()?, row.count)))
+        .collect())
+}
+
+/// Counts the guild's own custom emojis in new messages. Emojis from other servers aren't tracked.
+pub async fn on_event(ctx: &Context, event: &FullEvent, data: &Data) -> Result<(), sqlx::Error> {
+    let FullEvent::Message { new_message } = event else {
+        return Ok(());
+    };
+
+    let Some(guild_id) = new_message.guild_id else {
+        return Ok(());
+    };
+
+    if new_message.author.bot {
+        return Ok(());
+    }
+
+    let mut counts = count_emojis(&new_message.content);
+
+    if counts.is_empty() {
+        return Ok(());
+    }
+
+    {
+        let Some(guild) = ctx.cache.guild(guild_id) else {
+            return Ok(());
+        };
+
+        counts.retain(|emoji_id, _| guild.emojis.contains_key(emoji_id));
+    }
+
+    record(&data.pool, guild_id, counts).await
+}
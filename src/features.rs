@@ -0,0 +1,64 @@
+use serenity::all::GuildId;
+use sqlx::{Pool, Sqlite};
+
+pub const GLOBAL_SCOPE: &str = "global";
+
+/// Subsystems the operator can roll out gradually.
+/// A guild-specific flag wins over the global one, which wins over the built-in default.
+#[derive(Debug, poise::ChoiceParameter, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    #[name = "Audit log correlation"]
+    AuditCorrelation,
+    #[name = "Incident grouping"]
+    Incidents,
+    #[name = "Noisy user damping"]
+    Damping,
+    #[name = "Audit log polling"]
+    AuditPolling,
+}
+
+impl Feature {
+    pub const ALL: [Self; 4] = [
+        Self::AuditCorrelation,
+        Self::Incidents,
+        Self::Damping,
+        Self::AuditPolling,
+    ];
+
+    pub fn as_key(&self) -> &'static str {
+        match self {
+            Self::AuditCorrelation => "audit_correlation",
+            Self::Incidents => "incidents",
+            Self::Damping => "damping",
+            Self::AuditPolling => "audit_polling",
+        }
+    }
+
+    /// Whether the feature is on when no flag is set. Features that shipped before flags existed default to on.
+    pub fn default_enabled(&self) -> bool {
+        match self {
+            Self::AuditCorrelation | Self::Incidents | Self::Damping => true,
+            // polling costs an audit log request per guild every few minutes, so it's opted into.
+            Self::AuditPolling => false,
+        }
+    }
+}
+
+pub async fn is_enabled(pool: &Pool<Sqlite>, guild_id: GuildId, feature: Feature) -> bool {
+    let guild_id = guild_id.to_string();
+    let key = feature.as_key();
+
+    sqlx::query!(
+        "SELECT enabled FROM feature_flags WHERE feature = ? AND scope IN (?, ?)
+        ORDER BY scope = ? LIMIT 1",
+        key,
+        guild_id,
+        GLOBAL_SCOPE,
+        GLOBAL_SCOPE
+    )
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()
+    .map_or(feature.default_enabled(), |row| row.enabled)
+}
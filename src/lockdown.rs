@@ -0,0 +1,246 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use poise::serenity_prelude::*;
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    commands::LogType,
+    logging::{maintenance, now, theme},
+    settings::Settings,
+};
+
+const REVERT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Everything that lets @everyone post in a channel or its threads.
+const LOCKED_PERMISSIONS: Permissions = Permissions::SEND_MESSAGES
+    .union(Permissions::SEND_MESSAGES_IN_THREADS)
+    .union(Permissions::CREATE_PUBLIC_THREADS)
+    .union(Permissions::CREATE_PRIVATE_THREADS)
+    .union(Permissions::ADD_REACTIONS);
+
+/// Denies posting for @everyone in `channel`, remembering the previous overwrite so it can be restored.
+/// Returns `false` if the channel is already locked.
+pub async fn lock(
+    http: &Http,
+    pool: &Pool<Sqlite>,
+    channel: &GuildChannel,
+    case_number: i64,
+    until: Option<i64>,
+) -> Result<bool, crate::client::Error> {
+    let guild_id = channel.guild_id.to_string();
+    let channel_id = channel.id.to_string();
+
+    let already_locked = sqlx::query!(
+        "SELECT id FROM lockdowns WHERE channel_id = ? AND lifted_at IS NULL",
+        channel_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .is_some();
+
+    if already_locked {
+        return Ok(false);
+    }
+
+    let everyone = RoleId::new(channel.guild_id.get());
+    let previous = channel
+        .permission_overwrites
+        .iter()
+        .find(|overwrite| overwrite.kind == PermissionOverwriteType::Role(everyone));
+
+    let (allow, deny) = previous
+        .map_or((Permissions::empty(), Permissions::empty()), |overwrite| {
+            (overwrite.allow, overwrite.deny)
+        });
+
+    channel
+        .id
+        .create_permission(
+            http,
+            PermissionOverwrite {
+                allow: allow - LOCKED_PERMISSIONS,
+                deny: deny | LOCKED_PERMISSIONS,
+                kind: PermissionOverwriteType::Role(everyone),
+            },
+        )
+        .await?;
+
+    let previous_allow = previous.map(|overwrite| overwrite.allow.bits().to_string());
+    let previous_deny = previous.map(|overwrite| overwrite.deny.bits().to_string());
+
+    sqlx::query!(
+        "INSERT INTO lockdowns (guild_id, channel_id, case_number, previous_allow, previous_deny, until) VALUES (?, ?, ?, ?, ?, ?)",
+        guild_id,
+        channel_id,
+        case_number,
+        previous_allow,
+        previous_deny,
+        until
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(true)
+}
+
+/// Restores the pre-lockdown overwrites for active lockdowns in `guild_id` (limited to `channel_id` if given),
+/// returning the channels that were unlocked and the case numbers they were locked under.
+pub async fn lift(
+    http: &Http,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    channel_id: Option<ChannelId>,
+) -> Result<Vec<(ChannelId, i64)>, crate::client::Error> {
+    let guild_id_string = guild_id.to_string();
+    let channel_id = channel_id.map(|id| id.to_string());
+
+    let lockdowns = sqlx::query!(
+        "SELECT id, channel_id, case_number, previous_allow, previous_deny FROM lockdowns
+        WHERE guild_id = ? AND (? IS NULL OR channel_id = ?) AND lifted_at IS NULL",
+        guild_id_string,
+        channel_id,
+        channel_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let everyone = PermissionOverwriteType::Role(RoleId::new(guild_id.get()));
+    let now = now() as i64;
+    let mut lifted = Vec::new();
+
+    for lockdown in lockdowns {
+        let Ok(channel_id) = ChannelId::from_str(&lockdown.channel_id) else {
+            continue;
+        };
+
+        let previous = lockdown
+            .previous_allow
+            .zip(lockdown.previous_deny)
+            .and_then(|(allow, deny)| {
+                Some((allow.parse::<u64>().ok()?, deny.parse::<u64>().ok()?))
+            });
+
+        let result = match previous {
+            Some((allow, deny)) => {
+                channel_id
+                    .create_permission(
+                        http,
+                        PermissionOverwrite {
+                            allow: Permissions::from_bits_truncate(allow),
+                            deny: Permissions::from_bits_truncate(deny),
+                            kind: everyone,
+                        },
+                    )
+                    .await
+            }
+            None => channel_id.delete_permission(http, everyone).await,
+        };
+
+        // a deleted channel can't be unlocked, but shouldn't stay "locked" forever either.
+        if let Err(error) = result {
+            println!("Failed to lift lockdown in channel {channel_id}: {error}");
+        }
+
+        sqlx::query!(
+            "UPDATE lockdowns SET lifted_at = ? WHERE id = ?",
+            now,
+            lockdown.id
+        )
+        .execute(pool)
+        .await?;
+
+        lifted.push((channel_id, lockdown.case_number));
+    }
+
+    Ok(lifted)
+}
+
+pub async fn log(
+    http: &Http,
+    pool: &Pool<Sqlite>,
+    settings: &Settings,
+    guild_id: GuildId,
+    case_number: i64,
+    title: &str,
+    description: String,
+) {
+    let Some(channel) = LogType::Server.fetch_channel(pool, guild_id).await else {
+        return;
+    };
+
+    let style = theme::style(settings, guild_id, "moderation").await;
+
+    let embed = CreateEmbed::new()
+        .title(format!("{} {title}", style.emoji))
+        .colour(style.colour)
+        .description(description)
+        .field("Case", format!("#{case_number}"), true);
+
+    if let Err(error) = maintenance::send(
+        http,
+        pool,
+        settings,
+        guild_id,
+        LogType::Server,
+        channel,
+        embed,
+    )
+    .await
+    {
+        println!("Failed to send lockdown log: {error}");
+    }
+}
+
+/// Periodically lifts lockdowns whose duration has run out, and logs the revert.
+pub async fn revert_expired(http: Arc<Http>, pool: Pool<Sqlite>, settings: Settings) {
+    let mut interval = tokio::time::interval(REVERT_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let now = now() as i64;
+        let expired = match sqlx::query!(
+            "SELECT DISTINCT guild_id, channel_id FROM lockdowns WHERE lifted_at IS NULL AND until <= ?",
+            now
+        )
+        .fetch_all(&pool)
+        .await
+        {
+            Ok(expired) => expired,
+            Err(error) => {
+                println!("Failed to fetch expired lockdowns: {error}");
+                continue;
+            }
+        };
+
+        for row in expired {
+            let (Ok(guild_id), Ok(channel_id)) = (
+                GuildId::from_str(&row.guild_id),
+                ChannelId::from_str(&row.channel_id),
+            ) else {
+                continue;
+            };
+
+            let lifted = match lift(&http, &pool, guild_id, Some(channel_id)).await {
+                Ok(lifted) => lifted,
+                Err(error) => {
+                    println!("Failed to lift expired lockdown: {error}");
+                    continue;
+                }
+            };
+
+            for (channel_id, case_number) in lifted {
+                log(
+                    &http,
+                    &pool,
+                    &settings,
+                    guild_id,
+                    case_number,
+                    "Lockdown lifted",
+                    format!("The lockdown of <#{channel_id}> ran out and has been reverted."),
+                )
+                .await;
+            }
+        }
+    }
+}
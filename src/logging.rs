@@ -1,357 +1,553 @@
 use poise::FrameworkContext;
 use serenity::{
-    all::{client::Context, FullEvent, GuildId, User},
-    builder::{
-        CreateAllowedMentions, CreateAttachment, CreateEmbed, CreateEmbedAuthor, CreateMessage,
-    },
-    model::Colour,
+    all::{client::Context, ChannelId, FullEvent, GuildId, MessageId, User},
+    builder::{CreateAllowedMentions, CreateEmbed, CreateMessage},
+};
+use std::fmt::Display;
+
+pub mod anomalies;
+mod audit;
+pub mod audit_poll;
+pub mod boosts;
+pub mod bulk_roles;
+pub mod cache_boost;
+pub mod channel_export;
+mod context;
+pub mod damping;
+pub mod drift;
+pub mod edit_versions;
+mod filters;
+mod formatter;
+pub mod formatters;
+pub mod housekeeping;
+pub mod ignores;
+pub mod incidents;
+pub mod language;
+pub mod log_messages;
+pub mod maintenance;
+#[cfg(test)]
+mod mock;
+pub mod mutes;
+pub mod outbound;
+mod panic;
+mod permissions;
+pub mod pins;
+pub mod push;
+mod quarantine;
+pub mod quiet_hours;
+pub mod routing;
+pub mod snapshots;
+pub mod soundboard;
+#[cfg(test)]
+mod tests;
+pub mod theme;
+pub mod timestamps;
+pub mod triage;
+pub mod trusted;
+pub mod verification;
+pub mod voice_hops;
+
+use context::{Delivery, EventContext};
+pub use formatter::FormatterRegistry;
+use language::LanguageRoute;
+
+use crate::{
+    client::Data,
+    commands::LogType,
+    features::{self, Feature},
+    mod_stats,
+    settings::keys,
 };
-use std::hash::Hash;
-use std::{collections::HashSet, fmt::Display};
-
-use crate::{client::Data, commands::LogType};
 
-fn display_name(user: &User) -> String {
-    let nick = user
-        .member
-        .as_ref()
-        .and_then(|member| member.nick.clone())
-        .or_else(|| user.global_name.clone());
-    let name = format!("@{}", user.name.clone());
+pub use formatter::{Category, EventFormatter, LogEntry, Severity};
 
-    if let Some(nick) = nick {
-        format!("{name} ({nick})")
-    } else {
-        name
-    }
+pub(crate) fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
 }
 
-fn base_embed(user: &User) -> CreateEmbed {
-    CreateEmbed::new().author(
-        CreateEmbedAuthor::new(display_name(user)).icon_url(
-            user.avatar_url()
-                .unwrap_or_else(|| user.default_avatar_url()),
-        ),
-    )
+#[derive(Debug, Copy, Clone)]
+struct NoLogChannelSet {
+    #[allow(unused)]
+    pub log_type: LogType,
+    #[allow(unused)]
+    pub guild_id: GuildId,
 }
 
-#[derive(Clone, Debug)]
-struct AsymmetricDiff<T: PartialEq + Hash + Clone> {
-    added: HashSet<T>,
-    removed: HashSet<T>,
+impl Display for NoLogChannelSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
 }
 
-fn asymmetric_diff(from: Vec<String>, to: Vec<String>) -> AsymmetricDiff<String> {
-    let mut removed = from.clone().into_iter().collect::<HashSet<_>>();
-    let mut added = to.clone().into_iter().collect::<HashSet<_>>();
-
-    for item in from.iter() {
-        added.remove(item);
-    }
+impl std::error::Error for NoLogChannelSet {}
 
-    for item in to.iter() {
-        removed.remove(item);
+/// Applies the guild's theme to a log embed: the category/severity colour, the emoji in front of the title,
+/// and the footer configured for `log_type`, after `note` if there is one.
+async fn styled(
+    data: &Data,
+    guild_id: GuildId,
+    formatter: &dyn EventFormatter,
+    log_type: LogType,
+    severity: Severity,
+    note: Option<&str>,
+    embed: CreateEmbed,
+) -> CreateEmbed {
+    let style = theme::resolve(&data.settings, guild_id, formatter.category(), severity).await;
+
+    let mut embed = embed
+        .title(format!("{} {}", style.emoji, formatter.title()))
+        .colour(style.colour);
+
+    if let Some(footer) = theme::footer(&data.settings, guild_id, log_type, note).await {
+        embed = embed.footer(footer);
     }
 
-    AsymmetricDiff { removed, added }
+    embed
 }
 
-fn pluralize<'a>(singular: &'a str, plural: &'a str, count: usize) -> &'a str {
-    match count {
-        1 => singular,
-        _ => plural,
-    }
+/// Renders a sample log of `kind` the way it would currently look in `guild_id`, with `author` standing in for the subject.
+pub async fn preview(
+    data: &Data,
+    guild_id: GuildId,
+    kind: &str,
+    author: &User,
+) -> Option<CreateEmbed> {
+    let formatter = data.formatters.by_kind(kind)?;
+    let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+    let embed = formatters::base_embed(author)
+        .description(format!(
+            "This is a sample **{}** log, using this server's current theme and settings.",
+            formatter.title()
+        ))
+        .field("Timestamp", timestamps.format(now() as i64), true)
+        .field("Route", formatter.default_route().to_string(), true);
+
+    Some(
+        styled(
+            data,
+            guild_id,
+            formatter,
+            formatter.default_route(),
+            formatter.severity(),
+            Some("Preview"),
+            embed,
+        )
+        .await,
+    )
 }
 
-async fn make_embed(
+pub async fn handle_logging_events(
     ctx: &Context,
     event: &FullEvent,
     _framework_ctx: FrameworkContext<'_, Data, crate::client::Error>,
-    _data: &Data,
-) -> Option<(CreateMessage, LogType, GuildId, Option<Vec<CreateMessage>>)> {
-    match event {
-        FullEvent::MessageDelete {
-            channel_id,
-            deleted_message_id,
-            guild_id,
-        } => {
-            let guild_id = *(guild_id.as_ref()?);
-            let message = ctx.cache.message(channel_id, deleted_message_id)?.clone();
+    data: &Data,
+) -> Result<(), crate::client::Error> {
+    if let FullEvent::GuildMemberUpdate {
+        old_if_available: Some(old),
+        event,
+        ..
+    } = event
+    {
+        data.bulk_roles
+            .observe(event.guild_id, event.user.id, &old.roles, &event.roles);
+    }
 
-            if message.author.bot {
-                return None;
-            }
+    if let FullEvent::GuildMemberAddition { new_member } = event {
+        verification::record_join(&data.pool, new_member).await?;
+    }
 
-            let message_content = if !message.content.is_empty() {
-                message.content
-            } else {
-                "None".into()
-            };
+    if let FullEvent::GuildCreate { guild, .. } = event {
+        data.emojis.replace(guild.id, &guild.emojis);
+        data.stickers.replace(guild.id, &guild.stickers);
+    }
 
-            let mut followups = Vec::new();
+    if let FullEvent::ChannelUpdate { new, .. } = event {
+        drift::observe_update(&data.pool, new).await?;
+    }
 
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
+    data.cache_boost.observe(event);
 
-            let mut log_embed = base_embed(&message.author)
-                .colour(Colour::RED)
-                .description(format!(
-                    "A message by <@{}> (**{}**) was deleted in <#{}>.",
-                    message.author.id, message.author.name, message.channel_id
-                ))
-                .field("Content", message_content, false)
-                .field("Timestamp", format!("<t:{}>", timestamp), true);
+    // polling may have gotten to the entry first, e.g. right after a reconnect.
+    if let FullEvent::GuildAuditLogEntryCreate { entry, guild_id } = event
+        && !audit_poll::mark_seen(&data.pool, *guild_id, entry.id).await?
+    {
+        return Ok(());
+    }
 
-            let mut log_message = CreateMessage::new();
+    triage::on_event(ctx, event, data).await?;
+    edit_versions::on_event(ctx, event, data).await?;
 
-            if !message.attachments.is_empty() {
-                log_embed = log_embed.field(
-                    "No. Attachments",
-                    format!("{}", message.attachments.len()),
-                    true,
-                );
+    process(ctx, event, data).await
+}
 
-                let mut followup_message = CreateMessage::new();
+/// Formats `event` into logs and delivers each of them. One log failing doesn't hold up the others
+/// from the same event; what failed is reported together once all of them were tried.
+pub async fn process(
+    ctx: &dyn Delivery,
+    event: &FullEvent,
+    data: &Data,
+) -> Result<(), crate::client::Error> {
+    let entries = data.formatters.format(ctx, event, data).await;
+    let mut failures = Vec::new();
 
-                for attachment in message.attachments.iter() {
-                    let attachment_builder =
-                        CreateAttachment::url(ctx, &attachment.url).await.unwrap();
+    for (formatter, entry) in entries {
+        if let Err(error) = anomalies::record(&data.pool, entry.guild_id, formatter.kind()).await {
+            failures.push(format!("{}: {error}", formatter.kind()));
+        }
 
-                    followup_message = followup_message.add_file(attachment_builder);
-                }
+        // the action happened whether or not its log gets posted, just like its case was opened.
+        if let Some(action) = &entry.mod_action
+            && let Err(error) = mod_stats::record(
+                &data.pool,
+                entry.guild_id,
+                action.moderator,
+                action.action,
+                action.count,
+            )
+            .await
+        {
+            failures.push(format!("{}: {error}", formatter.kind()));
+        }
 
-                followups.push(followup_message);
-            }
+        match deliver(ctx, data, formatter, entry).await {
+            // the guild left this route unset, which only concerns this one log.
+            Err(error) if error.is::<NoLogChannelSet>() => {}
+            Err(error) => failures.push(format!("{}: {error}", formatter.kind())),
+            Ok(_) => {}
+        }
+    }
 
-            log_message = log_message.embed(log_embed);
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to deliver logs for {}: {}",
+            event.snake_case_name(),
+            failures.join("; ")
+        )
+        .into())
+    }
+}
 
-            Some((log_message, LogType::Chat, guild_id, Some(followups)))
-        }
-        FullEvent::MessageUpdate {
-            old_if_available,
-            new,
-            event: _,
-        } => {
-            let old = old_if_available.as_ref()?.clone();
-
-            if old.author.bot {
-                return None;
-            }
-
-            let guild_id = old.guild_id?;
-            let new = new.as_ref()?.clone();
-
-            let mut followups = Vec::new();
-
-            let mut description = format!(
-                "<@{}> (**{}**) updated their message in <#{}>.\n [Jump to message]({})",
-                new.author.id,
-                new.author.name,
-                new.channel_id,
-                new.link()
-            );
+/// Routes, styles and posts a single log entry. Returns where the log was posted,
+/// or `None` if it was muted, archived, damped, held back for a digest or queued for maintenance.
+pub async fn deliver(
+    ctx: &dyn Delivery,
+    data: &Data,
+    formatter: &dyn EventFormatter,
+    entry: LogEntry,
+) -> Result<Option<(ChannelId, MessageId)>, crate::client::Error> {
+    let result = post(ctx, data, formatter, entry).await;
+
+    // a guild without a log channel for the route chose not to have these logs; nothing failed.
+    if let Err(error) = &result
+        && !error.is::<NoLogChannelSet>()
+    {
+        crate::dashboard::record_failure(&data.pool).await.ok();
+    }
 
-            let mut log_embed = base_embed(&old.author).colour(Colour::FADED_PURPLE);
-
-            let content_changed = old.content != new.content;
-
-            if content_changed {
-                log_embed = log_embed.field("New", new.content, false).field(
-                    "Previous",
-                    old.content,
-                    false,
-                );
-            } else {
-                description += "\n\n Message content hasn't changed. Check followup message(s) for attachment changes."
-            }
-
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            log_embed = log_embed.field("Timestamp", format!("<t:{}>", timestamp), true);
-
-            let attachments_could_have_changed =
-                !old.attachments.is_empty() || !new.attachments.is_empty();
-
-            if attachments_could_have_changed {
-                let difference = asymmetric_diff(
-                    old.attachments.iter().map(|a| a.url.clone()).collect(),
-                    new.attachments.iter().map(|a| a.url.clone()).collect(),
-                );
-
-                log_embed = log_embed.field(
-                    "Attachments",
-                    format!(
-                        "**Removed**: {} | **Added**: {}",
-                        difference.removed.len(),
-                        difference.added.len()
-                    ),
-                    true,
-                );
-
-                if !difference.added.is_empty() {
-                    let mut added_attachments = CreateMessage::new().content(format!(
-                        "Added {}:",
-                        pluralize("attachment", "attachments", difference.added.len())
-                    ));
-
-                    for url in difference.added.iter() {
-                        added_attachments =
-                            added_attachments.add_file(CreateAttachment::url(ctx, url).await.ok()?);
-                    }
-
-                    followups.push(added_attachments);
-                }
-
-                if !difference.removed.is_empty() {
-                    let mut removed_attachments = CreateMessage::new().content(format!(
-                        "Removed {}:",
-                        pluralize("attachment", "attachments", difference.removed.len())
-                    ));
-
-                    for url in difference.removed.iter() {
-                        removed_attachments = removed_attachments
-                            .add_file(CreateAttachment::url(ctx, url).await.ok()?);
-                    }
-
-                    followups.push(removed_attachments);
-                }
-            }
-
-            // slightly hacky workaround - we don't want to log embed deletions (yet).
-            if content_changed || attachments_could_have_changed {
-                Some((
-                    CreateMessage::new().embed(log_embed.description(description)),
-                    LogType::Chat,
-                    guild_id,
-                    Some(followups),
-                ))
-            } else {
-                None
-            }
-        }
-        // USERS
-        FullEvent::GuildMemberAddition { new_member: member } => {
-            let embed = base_embed(&member.user)
-                .colour(Colour::DARK_GREEN)
-                .description(format!(
-                    "<@{}> ({}) joined.",
-                    member.user.id, member.user.name
-                ))
-                .field(
-                    "Joined At",
-                    format!("<t:{}:R>", member.joined_at?.timestamp()),
-                    true,
-                )
-                .field(
-                    "Created At",
-                    format!("<t:{}:R>", member.user.created_at().timestamp()),
-                    true,
-                );
-
-            Some((
-                CreateMessage::new().embed(embed),
-                LogType::Member,
-                member.guild_id,
+    result
+}
+
+async fn post(
+    ctx: &dyn Delivery,
+    data: &Data,
+    formatter: &dyn EventFormatter,
+    entry: LogEntry,
+) -> Result<Option<(ChannelId, MessageId)>, crate::client::Error> {
+    let log_type = formatter.default_route();
+    let guild_id = entry.guild_id;
+
+    // content is rendered before anything is archived, so metadata-only guilds never have it stored either.
+    let metadata_only = data.settings.get(guild_id, &keys::METADATA_ONLY).await;
+    let entry = entry.render_content(metadata_only);
+
+    let panic_mode = panic::active(&data.settings, guild_id).await;
+
+    if panic_mode.is_none() && mutes::is_muted(&data.pool, guild_id, log_type).await? {
+        return Ok(None);
+    }
+
+    if panic_mode.is_none()
+        && formatter.respects_trusted_roles()
+        && let Some(subject) = entry.subject
+    {
+        let roles = ctx
+            .cached_member(guild_id, subject)
+            .map(|member| member.roles)
+            .unwrap_or_default();
+
+        if trusted::is_trusted(&data.pool, guild_id, &roles).await? {
+            let severity = entry.severity.unwrap_or(formatter.severity());
+            let embed = styled(
+                data,
+                guild_id,
+                formatter,
+                log_type,
+                severity,
                 None,
-            ))
+                entry.embed,
+            )
+            .await;
+
+            trusted::archive(&data.pool, guild_id, formatter.kind(), subject, &embed).await?;
+            return Ok(None);
         }
-        FullEvent::GuildMemberRemoval {
+    }
+
+    let language_route = match entry.language {
+        Some(language) => language::route(&data.settings, guild_id, language).await,
+        None => LanguageRoute::Default,
+    };
+
+    let channel = match (
+        panic_mode.as_ref().and_then(|panic| panic.staff_channel),
+        language_route,
+    ) {
+        (Some(staff_channel), _) => staff_channel,
+        (None, LanguageRoute::Ignored) => return Ok(None),
+        (None, LanguageRoute::Channel(channel)) => channel,
+        (None, LanguageRoute::Default) => log_type
+            .fetch_channel(&data.pool, guild_id)
+            .await
+            .ok_or(NoLogChannelSet { log_type, guild_id })?,
+    };
+
+    let severity = entry.severity.unwrap_or(formatter.severity());
+
+    // a burst of warnings about one account, e.g. a compromised one, is exactly what shouldn't be collapsed.
+    if panic_mode.is_none()
+        && severity == Severity::Info
+        && let Some(subject) = entry.subject
+        && features::is_enabled(&data.pool, guild_id, Feature::Damping).await
+        && !data.damper.allow(guild_id, subject, formatter, log_type)
+    {
+        return Ok(None);
+    }
+
+    let mut embed = styled(
+        data,
+        guild_id,
+        formatter,
+        log_type,
+        severity,
+        None,
+        entry.embed,
+    )
+    .await;
+
+    if panic_mode.is_none()
+        && severity < Severity::Warning
+        && quiet_hours::is_quiet(&data.settings, guild_id, log_type).await
+    {
+        quiet_hours::hold(
+            &data.pool,
             guild_id,
-            user,
-            member_data_if_available,
-        } => {
-            // TODO: shit's fucked. Members are not gonna be cached. We may be able to fetch guilds on startup?
-            let member = member_data_if_available.as_ref()?;
-
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-
-            let embed = base_embed(user)
-                .colour(Colour::DARK_RED)
-                .description(format!("<@{}> ({}) left.", user.id, user.name))
-                .field(
-                    "Joined At",
-                    format!("<t:{}:R>", member.joined_at?.timestamp()),
-                    true,
-                )
-                .field(
-                    "Created At",
-                    format!("<t:{}:R>", user.created_at().timestamp()),
-                    true,
-                )
-                .field("Left At", format!("<t:{}:R>", now), true);
-
-            Some((
-                CreateMessage::new().embed(embed),
-                LogType::Member,
-                *guild_id,
-                None,
-            ))
+            log_type,
+            channel,
+            formatter.kind(),
+            &embed,
+        )
+        .await?;
+        return Ok(None);
+    }
+
+    // moderation actions and alerts open incidents; anything else only joins one that's already open.
+    let opens_incident =
+        formatter.category() == Category::Moderation || severity >= Severity::Warning;
+    let incident = if features::is_enabled(&data.pool, guild_id, Feature::Incidents).await {
+        incidents::assign(&data.pool, guild_id, entry.subject, opens_incident).await?
+    } else {
+        None
+    };
+
+    if let Some(incident) = &incident {
+        let note = format!("Incident #{}", incident.id);
+        if let Some(footer) = theme::footer(&data.settings, guild_id, log_type, Some(&note)).await {
+            embed = embed.footer(footer);
         }
-        FullEvent::GuildMemberUpdate {
-            old_if_available,
-            new: _,
-            event: _,
-        } => {
-            let _old = old_if_available.as_ref()?;
-
-            None
+
+        if let Some((previous_channel, previous_message)) = incident.previous {
+            embed = embed.field(
+                "Related",
+                format!(
+                    "[Previous log in this incident]({}), `/incident show {}` lists them all",
+                    previous_message.link(previous_channel, Some(guild_id)),
+                    incident.id
+                ),
+                false,
+            );
         }
-        _ => None,
     }
-}
 
-#[derive(Debug, Copy, Clone)]
-struct NoLogChannelSet {
-    #[allow(unused)]
-    pub log_type: LogType,
-    #[allow(unused)]
-    pub guild_id: GuildId,
-}
+    let mut message = CreateMessage::new()
+        .embed(embed)
+        .components(entry.components);
+
+    if entry.mention.is_some() || entry.mention_role.is_some() {
+        let pings = entry
+            .mention
+            .map(|user| format!("<@{user}>"))
+            .into_iter()
+            .chain(entry.mention_role.map(|role| format!("<@&{role}>")))
+            .collect::<Vec<_>>();
+
+        message = message.content(pings.join(" ")).allowed_mentions(
+            CreateAllowedMentions::new()
+                .users(entry.mention)
+                .roles(entry.mention_role),
+        );
+    }
 
-impl Display for NoLogChannelSet {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(self, f)
+    let recorded = log_messages::RecordedLog {
+        guild_id,
+        kind: formatter.kind(),
+        subject: entry.subject,
+        about: entry.message,
+        incident_id: incident.as_ref().map(|incident| incident.id),
+    };
+
+    // archiving above still happens during maintenance; only the post itself (and what hangs off it) waits.
+    if maintenance::is_active(&data.pool, guild_id).await? {
+        let followups = entry
+            .followups
+            .into_iter()
+            .map(|followup| followup.allowed_mentions(CreateAllowedMentions::new().empty_users()))
+            .collect::<Vec<_>>();
+
+        maintenance::queue(
+            &data.pool,
+            guild_id,
+            channel,
+            Some(&recorded),
+            &message,
+            &followups,
+        )
+        .await?;
+
+        // none of these go through Discord, so alerts still get out while the post waits.
+        alert_externally(
+            ctx,
+            data,
+            formatter,
+            quarantine::Detection {
+                guild_id,
+                kind: formatter.kind(),
+                severity,
+                executor: entry.subject,
+                incident_id: incident.as_ref().map(|incident| incident.id),
+                message: None,
+            },
+        )
+        .await?;
+
+        return Ok(None);
     }
-}
 
-impl std::error::Error for NoLogChannelSet {}
+    let message_id = ctx.send_message(channel, message).await?;
 
-pub async fn handle_logging_events(
-    ctx: &Context,
-    event: &FullEvent,
-    framework_ctx: FrameworkContext<'_, Data, crate::client::Error>,
+    log_messages::record(&data.pool, channel, message_id, &recorded).await?;
+
+    if let Some(case_number) = entry.case {
+        crate::cases::link_log(&data.pool, guild_id, case_number, channel, message_id).await?;
+    }
+
+    if let Some(incident) = &incident {
+        incidents::record_message(&data.pool, incident.id, channel, message_id).await?;
+
+        if severity == Severity::Critical
+            && data
+                .settings
+                .get(guild_id, &keys::PIN_CRITICAL_ALERTS)
+                .await
+            && incidents::claim_pin(&data.pool, incident.id, channel, message_id).await?
+            && let Err(error) = ctx.pin_message(channel, message_id).await
+        {
+            // missing Manage Messages or a channel that's out of pins shouldn't hold up the rest of the alert.
+            println!("Failed to pin critical alert {message_id} in {channel}: {error}");
+        }
+    }
+
+    alert_externally(
+        ctx,
+        data,
+        formatter,
+        quarantine::Detection {
+            guild_id,
+            kind: formatter.kind(),
+            severity,
+            executor: entry.subject,
+            incident_id: incident.as_ref().map(|incident| incident.id),
+            message: Some((channel, message_id)),
+        },
+    )
+    .await?;
+
+    for followup in entry.followups.into_iter() {
+        ctx.send_message(
+            channel,
+            followup
+                .reference_message((channel, message_id))
+                .allowed_mentions(CreateAllowedMentions::new().empty_users()),
+        )
+        .await?;
+    }
+
+    Ok(Some((channel, message_id)))
+}
+
+/// Sends what a log alerts about outside of Discord: push notifications for critical logs, and the
+/// quarantine webhook and on-call paging for threat detections. `detection.message` is `None` when the
+/// log itself was queued for maintenance.
+async fn alert_externally(
+    ctx: &dyn Delivery,
     data: &Data,
+    formatter: &dyn EventFormatter,
+    detection: quarantine::Detection,
 ) -> Result<(), crate::client::Error> {
-    let payload = make_embed(ctx, event, framework_ctx, data).await;
+    let guild_id = detection.guild_id;
+    let alert = detection.message;
+    let critical = detection.severity == Severity::Critical;
 
-    if let Some((message, log_type, guild_id, followups)) = payload {
-        let channel = log_type
-            .fetch_channel(&data.pool, guild_id)
-            .await
-            .ok_or(NoLogChannelSet { log_type, guild_id })?;
+    if critical {
+        let guild_name = ctx
+            .guild_name(guild_id)
+            .unwrap_or_else(|| guild_id.to_string());
 
-        let message = channel.send_message(ctx, message).await?;
+        push::notify(
+            &data.settings,
+            guild_id,
+            formatter.title(),
+            match alert {
+                Some(_) => format!("Critical {} alert in {guild_name}.", formatter.kind()),
+                None => format!(
+                    "Critical {} alert in {guild_name} (queued for maintenance).",
+                    formatter.kind()
+                ),
+            },
+            alert,
+        )
+        .await;
+    }
 
-        if let Some(followups) = followups
-            && !followups.is_empty()
-        {
-            for followup in followups.into_iter() {
-                channel
-                    .send_message(
-                        ctx,
-                        followup
-                            .reference_message(&message)
-                            .allowed_mentions(CreateAllowedMentions::new().empty_users()),
-                    )
-                    .await?;
-            }
+    if formatter.is_threat_detection() {
+        quarantine::notify(&data.settings, detection).await;
+
+        if critical {
+            crate::oncall::page(
+                ctx.http(),
+                &data.pool,
+                &data.settings,
+                guild_id,
+                formatter.kind(),
+                formatter.title(),
+                alert,
+            )
+            .await?;
         }
     }
 
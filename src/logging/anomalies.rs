@@ -0,0 +1,165 @@
+use std::{str::FromStr, time::Duration};
+
+use serenity::all::{Context, GuildId};
+use sqlx::{Pool, Sqlite};
+
+use super::{formatters::UnusualActivity, now};
+use crate::{client::Data, settings::keys};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const SECONDS_PER_HOUR: u64 = 60 * 60;
+/// How many hours before the checked one make up the baseline.
+const BASELINE_HOURS: i64 = 7 * 24;
+/// Guilds with less history than this don't have a meaningful baseline yet.
+const MIN_HISTORY_HOURS: i64 = 24;
+/// How many times the usual hourly rate counts as unusual.
+const RATE_FACTOR: f64 = 10.0;
+/// Below this many events in an hour, nothing is unusual enough to alert on, whatever the baseline.
+const MIN_EVENTS: i64 = 20;
+
+pub fn current_hour() -> i64 {
+    (now() / SECONDS_PER_HOUR) as i64
+}
+
+/// Counts one log of `kind` towards the guild's current hour.
+pub async fn record(pool: &Pool<Sqlite>, guild_id: GuildId, kind: &str) -> Result<(), sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let hour = current_hour();
+
+    sqlx::query!(
+        "INSERT INTO event_counts (guild_id, kind, hour, count) VALUES (?, ?, ?, 1)
+        ON CONFLICT (guild_id, kind, hour) DO UPDATE SET count = count + 1",
+        guild_id,
+        kind,
+        hour
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub struct Anomaly {
+    pub kind: String,
+    pub count: i64,
+    pub baseline: f64,
+}
+
+/// Kinds of log whose count in `hour` far exceeds their average over the week before.
+async fn find_anomalies(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    hour: i64,
+) -> Result<Vec<Anomaly>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let since = hour - BASELINE_HOURS;
+
+    let first_hour = sqlx::query!(
+        r#"SELECT MIN(hour) AS "hour: i64" FROM event_counts WHERE guild_id = ?"#,
+        guild_id
+    )
+    .fetch_one(pool)
+    .await?
+    .hour;
+
+    let Some(first_hour) = first_hour.filter(|first_hour| hour - first_hour >= MIN_HISTORY_HOURS)
+    else {
+        return Ok(Vec::new());
+    };
+
+    let rows = sqlx::query!(
+        r#"SELECT kind, count,
+        (SELECT COALESCE(SUM(count), 0) FROM event_counts AS history
+            WHERE history.guild_id = latest.guild_id AND history.kind = latest.kind
+            AND history.hour >= ? AND history.hour < ?) AS "history!: i64"
+        FROM event_counts AS latest WHERE guild_id = ? AND hour = ? AND count >= ?"#,
+        since,
+        hour,
+        guild_id,
+        hour,
+        MIN_EVENTS
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // hours before the guild's first count don't drag the average down.
+    let hours = hour - since.max(first_hour);
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Anomaly {
+            kind: row.kind,
+            count: row.count,
+            baseline: row.history as f64 / hours as f64,
+        })
+        .filter(|anomaly| anomaly.count as f64 >= RATE_FACTOR * anomaly.baseline.max(1.0))
+        .collect())
+}
+
+/// Once an hour, compares each guild's log volume for the hour that just ended against its usual rate,
+/// alerting the server logs about kinds that spiked, e.g. a sudden wave of deletions.
+pub async fn check(ctx: Context, data: Data) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let hour = current_hour() - 1;
+
+        let cutoff = hour - BASELINE_HOURS;
+        if let Err(error) = sqlx::query!("DELETE FROM event_counts WHERE hour < ?", cutoff)
+            .execute(&data.pool)
+            .await
+        {
+            println!("Failed to prune event counts: {error}");
+        }
+
+        let guilds =
+            match sqlx::query!("SELECT guild_id FROM log_channels WHERE server_logs IS NOT NULL")
+                .fetch_all(&data.pool)
+                .await
+            {
+                Ok(guilds) => guilds,
+                Err(error) => {
+                    println!("Failed to fetch guilds for anomaly checks: {error}");
+                    continue;
+                }
+            };
+
+        for row in guilds {
+            let Ok(guild_id) = GuildId::from_str(&row.guild_id) else {
+                continue;
+            };
+
+            if data.settings.get(guild_id, &keys::LAST_ANOMALY_CHECK).await >= hour {
+                continue;
+            }
+
+            if let Err(error) = data
+                .settings
+                .set(guild_id, &keys::LAST_ANOMALY_CHECK, &hour)
+                .await
+            {
+                println!("Failed to store anomaly check time: {error}");
+                continue;
+            }
+
+            let anomalies = match find_anomalies(&data.pool, guild_id, hour).await {
+                Ok(anomalies) if !anomalies.is_empty() => anomalies,
+                Ok(_) => continue,
+                Err(error) => {
+                    println!("Failed to check guild {guild_id} for anomalies: {error}");
+                    continue;
+                }
+            };
+
+            let start = hour * SECONDS_PER_HOUR as i64;
+            let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+            let entry = UnusualActivity::entry(guild_id, start, anomalies, timestamps);
+
+            if let Err(error) = super::deliver(&ctx, &data, &UnusualActivity, entry).await {
+                println!("Failed to log unusual activity: {error}");
+            }
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use std::{collections::HashMap, time::Duration};
+
+use serde_json::Value;
+use serenity::all::{
+    audit_log::{Action, Change, MemberAction, MessageAction},
+    AuditLogEntry, AuditLogs, ChannelId, GuildId, MessageId, UserId,
+};
+use sqlx::{Pool, Sqlite};
+
+use super::{now, EventContext};
+use crate::features::{self, Feature};
+
+/// Every lookup goes through here, so guilds that turned audit log correlation off never have their audit log read.
+pub(super) async fn audit_logs(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    action: Option<Action>,
+    user_id: Option<UserId>,
+    limit: Option<u8>,
+) -> Option<AuditLogs> {
+    if !features::is_enabled(pool, guild_id, Feature::AuditCorrelation).await {
+        return None;
+    }
+
+    ctx.audit_logs(guild_id, action, user_id, limit).await.ok()
+}
+
+/// Discord folds repeated deletions by the same moderator into one entry, so matches can be a few minutes old.
+const MESSAGE_DELETE_WINDOW_SECS: i64 = 5 * 60;
+
+/// Finds a recent audit entry for someone else deleting a message by `author` in `channel_id`.
+/// Discord doesn't create entries for self-deletions, so `None` usually means the author deleted it themselves.
+pub async fn find_message_delete(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    author: UserId,
+    channel_id: ChannelId,
+) -> Option<AuditLogEntry> {
+    let logs = audit_logs(
+        ctx,
+        pool,
+        guild_id,
+        Some(Action::Message(MessageAction::Delete)),
+        None,
+        Some(10),
+    )
+    .await?;
+
+    let cutoff = now() as i64 - MESSAGE_DELETE_WINDOW_SECS;
+
+    logs.entries.into_iter().find(|entry| {
+        entry.target_id.map(|target| target.get()) == Some(author.get())
+            && entry
+                .options
+                .as_ref()
+                .and_then(|options| options.channel_id)
+                == Some(channel_id)
+            && entry.id.created_at().unix_timestamp() >= cutoff
+    })
+}
+
+/// Whoever made the most audit log entries of the given kinds since `since`, e.g. to name the account behind a nuke.
+pub async fn find_frequent_executor(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    actions: &[Action],
+    since: i64,
+) -> Option<UserId> {
+    let mut counts: HashMap<UserId, usize> = HashMap::new();
+
+    for action in actions {
+        let Some(logs) = audit_logs(ctx, pool, guild_id, Some(*action), None, Some(50)).await
+        else {
+            continue;
+        };
+
+        for entry in logs.entries {
+            if entry.id.created_at().unix_timestamp() >= since {
+                *counts.entry(entry.user_id).or_default() += 1;
+            }
+        }
+    }
+
+    counts
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(user_id, _)| user_id)
+}
+
+/// Finds the most recent audit entry for `user_id` being timed out.
+pub async fn find_timeout(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Option<AuditLogEntry> {
+    find_timeout_change(ctx, pool, guild_id, user_id, true).await
+}
+
+/// Finds the most recent audit entry for `user_id`'s timeout being removed early.
+pub async fn find_timeout_removal(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Option<AuditLogEntry> {
+    find_timeout_change(ctx, pool, guild_id, user_id, false).await
+}
+
+async fn find_timeout_change(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+    applied: bool,
+) -> Option<AuditLogEntry> {
+    let logs = audit_logs(
+        ctx,
+        pool,
+        guild_id,
+        Some(Action::Member(MemberAction::Update)),
+        None,
+        Some(25),
+    )
+    .await?;
+
+    logs.entries.into_iter().find(|entry| {
+        entry.target_id.map(|target| target.get()) == Some(user_id.get())
+            && entry.changes.iter().flatten().any(|change| {
+                matches!(
+                    change,
+                    Change::CommunicationDisabledUntil { new, .. } if new.is_some() == applied
+                )
+            })
+    })
+}
+
+/// Discord can take a moment to write audit entries, and bans or purges often come in quick succession.
+const MEMBER_ACTION_WINDOW_SECS: i64 = 60;
+
+/// Finds the audit entry for a recent `action` (e.g. a ban) targeting `user_id`, for the moderator and reason.
+pub async fn find_member_action(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+    action: Action,
+) -> Option<AuditLogEntry> {
+    find_target_action(ctx, pool, guild_id, user_id.get(), action).await
+}
+
+/// How long to wait before each further lookup when an audit entry hasn't been written yet.
+const LAGGING_ENTRY_RETRIES: [Duration; 2] = [Duration::from_secs(2), Duration::from_secs(5)];
+
+/// Like [`find_member_action`], but retries a couple of times if no entry turns up. Discord sometimes writes
+/// the audit entry for a ban or kick only after dispatching its gateway event.
+pub async fn wait_for_member_action(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+    action: Action,
+) -> Option<AuditLogEntry> {
+    if let Some(entry) = find_member_action(ctx, pool, guild_id, user_id, action).await {
+        return Some(entry);
+    }
+
+    for delay in LAGGING_ENTRY_RETRIES {
+        tokio::time::sleep(delay).await;
+
+        if let Some(entry) = find_member_action(ctx, pool, guild_id, user_id, action).await {
+            return Some(entry);
+        }
+    }
+
+    None
+}
+
+/// Like [`find_member_action`], for targets that aren't members, e.g. integrations.
+pub async fn find_target_action(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    target_id: u64,
+    action: Action,
+) -> Option<AuditLogEntry> {
+    let logs = audit_logs(ctx, pool, guild_id, Some(action), None, Some(10)).await?;
+
+    let cutoff = now() as i64 - MEMBER_ACTION_WINDOW_SECS;
+
+    logs.entries.into_iter().find(|entry| {
+        entry.target_id.map(|target| target.get()) == Some(target_id)
+            && entry.id.created_at().unix_timestamp() >= cutoff
+    })
+}
+
+/// Who took the first of `actions` found on `target_id`, e.g. a channel update or one of its permission overwrites.
+pub async fn find_executor(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    target_id: u64,
+    actions: &[Action],
+) -> Option<UserId> {
+    for action in actions {
+        if let Some(entry) = find_target_action(ctx, pool, guild_id, target_id, *action).await {
+            return Some(entry.user_id);
+        }
+    }
+
+    None
+}
+
+/// Finds a recent audit entry for messages being bulk deleted (purged) in `channel_id`.
+pub async fn find_bulk_delete(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Option<AuditLogEntry> {
+    let logs = audit_logs(
+        ctx,
+        pool,
+        guild_id,
+        Some(Action::Message(MessageAction::BulkDelete)),
+        None,
+        Some(10),
+    )
+    .await?;
+
+    let cutoff = now() as i64 - MEMBER_ACTION_WINDOW_SECS;
+
+    logs.entries.into_iter().find(|entry| {
+        entry.target_id.map(|target| target.get()) == Some(channel_id.get())
+            && entry.id.created_at().unix_timestamp() >= cutoff
+    })
+}
+
+/// Finds a recent audit entry for `message_id` in `channel_id` being pinned, or unpinned if `pinned` is false.
+pub async fn find_pin(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    pinned: bool,
+) -> Option<AuditLogEntry> {
+    let action = match pinned {
+        true => MessageAction::Pin,
+        false => MessageAction::Unpin,
+    };
+
+    let logs = audit_logs(
+        ctx,
+        pool,
+        guild_id,
+        Some(Action::Message(action)),
+        None,
+        Some(10),
+    )
+    .await?;
+
+    let cutoff = now() as i64 - MEMBER_ACTION_WINDOW_SECS;
+
+    logs.entries.into_iter().find(|entry| {
+        entry.options.as_ref().is_some_and(|options| {
+            options.channel_id == Some(channel_id) && options.message_id == Some(message_id)
+        }) && entry.id.created_at().unix_timestamp() >= cutoff
+    })
+}
+
+/// The changes of `entry` as Discord sent them, including the keys serenity doesn't model and drops.
+pub async fn raw_changes(
+    ctx: &dyn EventContext,
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    entry: &AuditLogEntry,
+) -> Option<Vec<Value>> {
+    if !features::is_enabled(pool, guild_id, Feature::AuditCorrelation).await {
+        return None;
+    }
+
+    let entries = ctx
+        .raw_audit_logs(guild_id, entry.action, Some(10))
+        .await
+        .ok()?;
+
+    let id = entry.id.to_string();
+
+    entries
+        .into_iter()
+        .find(|raw| raw["id"].as_str() == Some(id.as_str()))
+        .and_then(|mut raw| match raw["changes"].take() {
+            Value::Array(changes) => Some(changes),
+            _ => None,
+        })
+}
@@ -0,0 +1,123 @@
+use std::{str::FromStr, time::Duration};
+
+use serenity::all::{client::Context, AuditLogEntryId, FullEvent, GuildId};
+use sqlx::{Pool, Sqlite};
+
+use super::{context::Delivery, now, process};
+use crate::{
+    client::{Data, Error},
+    features::{self, Feature},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// The most entries a single request returns, which is plenty for a few minutes of activity.
+const POLL_LIMIT: u8 = 100;
+/// Entries older than this are neither logged nor remembered, so the table doesn't grow forever.
+const SEEN_RETENTION_SECS: i64 = 3 * 24 * 60 * 60;
+
+/// Remembers that `entry_id` was handed to the formatters. Returns false if it already was.
+pub async fn mark_seen(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    entry_id: AuditLogEntryId,
+) -> Result<bool, sqlx::Error> {
+    let entry_id = entry_id.to_string();
+    let guild_id = guild_id.to_string();
+    let now = now() as i64;
+
+    let result = sqlx::query!(
+        "INSERT OR IGNORE INTO seen_audit_entries (entry_id, guild_id, seen_at) VALUES (?, ?, ?)",
+        entry_id,
+        guild_id,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+async fn has_seen_any(pool: &Pool<Sqlite>, guild_id: GuildId) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    Ok(sqlx::query!(
+        "SELECT entry_id FROM seen_audit_entries WHERE guild_id = ? LIMIT 1",
+        guild_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .is_some())
+}
+
+/// Logs the entries in `guild_id`'s audit log that haven't been seen yet, oldest first, as if they had
+/// just come in over the gateway. The first poll of a guild only takes note of what's there, rather
+/// than logging its whole history.
+pub async fn poll_guild(ctx: &dyn Delivery, data: &Data, guild_id: GuildId) -> Result<(), Error> {
+    let logs = ctx
+        .audit_logs(guild_id, None, None, Some(POLL_LIMIT))
+        .await?;
+
+    let first_poll = !has_seen_any(&data.pool, guild_id).await?;
+    let cutoff = now() as i64 - SEEN_RETENTION_SECS;
+
+    for entry in logs.entries.into_iter().rev() {
+        if entry.id.created_at().unix_timestamp() < cutoff
+            || !mark_seen(&data.pool, guild_id, entry.id).await?
+            || first_poll
+        {
+            continue;
+        }
+
+        process(
+            ctx,
+            &FullEvent::GuildAuditLogEntryCreate { entry, guild_id },
+            data,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Every few minutes, catches up on the audit logs of guilds that opted into polling. This picks up
+/// entries the bot missed while it was offline or disconnected.
+pub async fn poll(ctx: Context, data: Data) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let cutoff = now() as i64 - SEEN_RETENTION_SECS;
+        if let Err(error) = sqlx::query!("DELETE FROM seen_audit_entries WHERE seen_at < ?", cutoff)
+            .execute(&data.pool)
+            .await
+        {
+            println!("Failed to prune seen audit entries: {error}");
+        }
+
+        let guilds = match sqlx::query!("SELECT guild_id FROM log_channels")
+            .fetch_all(&data.pool)
+            .await
+        {
+            Ok(guilds) => guilds,
+            Err(error) => {
+                println!("Failed to fetch guilds for audit log polling: {error}");
+                continue;
+            }
+        };
+
+        for row in guilds {
+            let Ok(guild_id) = GuildId::from_str(&row.guild_id) else {
+                continue;
+            };
+
+            if !features::is_enabled(&data.pool, guild_id, Feature::AuditPolling).await {
+                continue;
+            }
+
+            if let Err(error) = poll_guild(&ctx, &data, guild_id).await {
+                println!("Failed to poll the audit log of {guild_id}: {error}");
+            }
+        }
+    }
+}
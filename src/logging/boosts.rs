@@ -0,0 +1,72 @@
+use serenity::all::{GuildId, UserId};
+use sqlx::{Pool, Sqlite};
+
+use super::now;
+
+/// Records that a member started boosting at `started_at`. Returns `false` if they already were.
+pub async fn start(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+    started_at: i64,
+) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let user_id = user_id.to_string();
+
+    let result = sqlx::query!(
+        "INSERT INTO boosts (guild_id, user_id, started_at) SELECT ?1, ?2, ?3
+        WHERE NOT EXISTS (SELECT 1 FROM boosts WHERE guild_id = ?1 AND user_id = ?2 AND ended_at IS NULL)",
+        guild_id,
+        user_id,
+        started_at
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Records that a member stopped boosting. Returns `false` if they weren't known to be boosting.
+pub async fn stop(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let user_id = user_id.to_string();
+    let now = now() as i64;
+
+    let result = sqlx::query!(
+        "UPDATE boosts SET ended_at = ? WHERE guild_id = ? AND user_id = ? AND ended_at IS NULL",
+        now,
+        guild_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// How long a member has boosted in total, in seconds, across every period the bot saw.
+pub async fn total(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<i64, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let user_id = user_id.to_string();
+    let now = now() as i64;
+
+    let row = sqlx::query!(
+        r#"SELECT COALESCE(SUM(COALESCE(ended_at, ?) - started_at), 0) AS "total!: i64"
+        FROM boosts WHERE guild_id = ? AND user_id = ?"#,
+        now,
+        guild_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.total)
+}
@@ -0,0 +1,203 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serenity::all::{
+    audit_log::{Action, Change, MemberAction},
+    Context, GuildId, RoleId, UserId,
+};
+use sqlx::{Pool, Sqlite};
+
+use super::{
+    anomalies, audit,
+    formatter::{EventFormatter, Severity},
+    formatters::BulkRoleChange,
+    permissions,
+};
+use crate::{client::Data, settings::keys};
+
+/// How many members have to gain or lose the same role within `WINDOW` for it to count as a bulk change.
+const THRESHOLD: usize = 5;
+const WINDOW: Duration = Duration::from_secs(60);
+/// A bulk change is summarised once the role has been left alone for this long.
+const QUIET: Duration = Duration::from_secs(20);
+const FLUSH_INTERVAL: Duration = Duration::from_secs(15);
+
+struct Tracker {
+    window_start: Instant,
+    /// `window_start` as a unix timestamp, to match against audit log entries.
+    started_at: i64,
+    last_change: Instant,
+    members: Vec<UserId>,
+}
+
+pub struct BulkChange {
+    pub guild_id: GuildId,
+    pub role_id: RoleId,
+    pub added: bool,
+    pub members: Vec<UserId>,
+    pub started_at: i64,
+}
+
+#[derive(Default)]
+pub struct BulkRoles {
+    trackers: Mutex<HashMap<(GuildId, RoleId, bool), Tracker>>,
+}
+
+impl BulkRoles {
+    /// Records every role `user_id` gained or lost between `old` and `new`.
+    pub fn observe(&self, guild_id: GuildId, user_id: UserId, old: &[RoleId], new: &[RoleId]) {
+        let now = Instant::now();
+        let mut trackers = self.trackers.lock().unwrap();
+
+        let added = new
+            .iter()
+            .filter(|role| !old.contains(role))
+            .map(|role| (*role, true));
+        let removed = old
+            .iter()
+            .filter(|role| !new.contains(role))
+            .map(|role| (*role, false));
+
+        for (role_id, added) in added.chain(removed) {
+            let tracker = trackers
+                .entry((guild_id, role_id, added))
+                .or_insert_with(|| Tracker {
+                    window_start: now,
+                    started_at: super::now() as i64,
+                    last_change: now,
+                    members: Vec::new(),
+                });
+
+            if tracker.members.len() < THRESHOLD
+                && now.duration_since(tracker.window_start) > WINDOW
+            {
+                tracker.window_start = now;
+                tracker.started_at = super::now() as i64;
+                tracker.members.clear();
+            }
+
+            tracker.last_change = now;
+            if !tracker.members.contains(&user_id) {
+                tracker.members.push(user_id);
+            }
+        }
+    }
+
+    /// Whether gaining (or losing) `role_id` is currently part of a bulk change,
+    /// in which case individual member logs for it should be left to the summary.
+    pub fn is_bulk(&self, guild_id: GuildId, role_id: RoleId, added: bool) -> bool {
+        self.trackers
+            .lock()
+            .unwrap()
+            .get(&(guild_id, role_id, added))
+            .is_some_and(|tracker| tracker.members.len() >= THRESHOLD)
+    }
+
+    /// Drains bulk changes that have calmed down and forgets trackers that never became one.
+    pub fn take_finished(&self) -> Vec<BulkChange> {
+        let now = Instant::now();
+        let mut trackers = self.trackers.lock().unwrap();
+        let mut finished = Vec::new();
+
+        trackers.retain(|(guild_id, role_id, added), tracker| {
+            if now.duration_since(tracker.last_change) < QUIET {
+                return true;
+            }
+
+            if tracker.members.len() >= THRESHOLD {
+                finished.push(BulkChange {
+                    guild_id: *guild_id,
+                    role_id: *role_id,
+                    added: *added,
+                    members: std::mem::take(&mut tracker.members),
+                    started_at: tracker.started_at,
+                });
+                return false;
+            }
+
+            now.duration_since(tracker.window_start) <= WINDOW
+        });
+
+        finished
+    }
+}
+
+/// Whoever made the most of the recent audit log entries handing out or taking away `role_id`.
+async fn find_executor(ctx: &Context, pool: &Pool<Sqlite>, change: &BulkChange) -> Option<UserId> {
+    let logs = audit::audit_logs(
+        ctx,
+        pool,
+        change.guild_id,
+        Some(Action::Member(MemberAction::RoleUpdate)),
+        None,
+        Some(100),
+    )
+    .await?;
+
+    let mut counts: HashMap<UserId, usize> = HashMap::new();
+
+    for entry in logs.entries {
+        if entry.id.created_at().unix_timestamp() < change.started_at {
+            continue;
+        }
+
+        let touches_role = entry.changes.iter().flatten().any(|entry_change| {
+            let roles = match entry_change {
+                Change::RolesAdded { new, .. } if change.added => new,
+                Change::RolesRemove { new, .. } if !change.added => new,
+                _ => return false,
+            };
+
+            roles.iter().flatten().any(|role| role.id == change.role_id)
+        });
+
+        if touches_role {
+            *counts.entry(entry.user_id).or_default() += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(user_id, _)| user_id)
+}
+
+/// Periodically logs one summary per bulk role change instead of a log per member.
+pub async fn flush(ctx: Context, data: Data) {
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        for change in data.bulk_roles.take_finished() {
+            let severity = change
+                .guild_id
+                .roles(&ctx.http)
+                .await
+                .ok()
+                .and_then(|roles| roles.get(&change.role_id).cloned())
+                .and_then(|role| permissions::grant_context(&[role]))
+                .map_or(Severity::Warning, |grant| grant.severity);
+
+            let executor = find_executor(&ctx, &data.pool, &change).await;
+            let timestamps = data
+                .settings
+                .get(change.guild_id, &keys::TIMESTAMP_STYLE)
+                .await;
+            let entry = BulkRoleChange::entry(&change, executor, severity, timestamps);
+
+            if let Err(error) =
+                anomalies::record(&data.pool, change.guild_id, BulkRoleChange.kind()).await
+            {
+                println!("Failed to record bulk role change: {error}");
+            }
+
+            if let Err(error) = super::deliver(&ctx, &data, &BulkRoleChange, entry).await {
+                println!("Failed to log bulk role change: {error}");
+            }
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serenity::{
+    all::{ChannelId, ChannelType, FullEvent, GetMessages, GuildId, Http, Message, MessageId},
+    builder::CreateEmbed,
+};
+use sqlx::{Pool, Sqlite};
+
+use super::{maintenance, now, theme};
+use crate::{commands::LogType, settings::Settings};
+
+/// How many messages a boosted guild keeps per channel, on top of the regular message cache.
+pub const BOOSTED_MESSAGES_PER_CHANNEL: usize = 1_000;
+/// How many recent messages are fetched per channel when a boost starts.
+const PREFETCH_LIMIT: u8 = 100;
+const EXPIRE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct Boost {
+    until: i64,
+    channels: HashMap<ChannelId, VecDeque<Message>>,
+}
+
+impl Boost {
+    fn insert(&mut self, message: Message) {
+        let messages = self.channels.entry(message.channel_id).or_default();
+
+        if let Some(existing) = messages.iter_mut().find(|cached| cached.id == message.id) {
+            *existing = message;
+            return;
+        }
+
+        if messages.len() == BOOSTED_MESSAGES_PER_CHANNEL {
+            messages.pop_front();
+        }
+
+        messages.push_back(message);
+    }
+}
+
+/// Guilds that temporarily keep far more messages than the regular cache does, e.g. during an incident,
+/// so deletions of older messages can still be logged with their content.
+///
+/// serenity's own cache can't shrink back once its limit is lowered, so boosted messages are kept here instead.
+#[derive(Default)]
+pub struct CacheBoost {
+    guilds: Mutex<HashMap<GuildId, Boost>>,
+}
+
+impl CacheBoost {
+    /// Boosts `guild_id` until `until`. Returns `false` if it was already boosted, in which case only the end moves.
+    pub fn start(&self, guild_id: GuildId, until: i64) -> bool {
+        let mut guilds = self.guilds.lock().unwrap();
+
+        match guilds.get_mut(&guild_id) {
+            Some(boost) => {
+                boost.until = until;
+                false
+            }
+            None => {
+                guilds.insert(
+                    guild_id,
+                    Boost {
+                        until,
+                        ..Default::default()
+                    },
+                );
+                true
+            }
+        }
+    }
+
+    /// Ends `guild_id`'s boost, dropping its messages. Returns `false` if it wasn't boosted.
+    pub fn stop(&self, guild_id: GuildId) -> bool {
+        self.guilds.lock().unwrap().remove(&guild_id).is_some()
+    }
+
+    fn insert(&self, guild_id: GuildId, message: Message) {
+        if let Some(boost) = self.guilds.lock().unwrap().get_mut(&guild_id) {
+            boost.insert(message);
+        }
+    }
+
+    /// Keeps new and edited messages in boosted guilds.
+    pub fn observe(&self, event: &FullEvent) {
+        match event {
+            FullEvent::Message { new_message } => {
+                if let Some(guild_id) = new_message.guild_id {
+                    self.insert(guild_id, new_message.clone());
+                }
+            }
+            FullEvent::MessageUpdate {
+                new: Some(message), ..
+            } => {
+                if let Some(guild_id) = message.guild_id {
+                    self.insert(guild_id, message.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn message(&self, channel_id: ChannelId, message_id: MessageId) -> Option<Message> {
+        self.guilds.lock().unwrap().values().find_map(|boost| {
+            boost
+                .channels
+                .get(&channel_id)?
+                .iter()
+                .find(|message| message.id == message_id)
+                .cloned()
+        })
+    }
+
+    fn expire(&self) -> Vec<GuildId> {
+        let now = now() as i64;
+        let mut guilds = self.guilds.lock().unwrap();
+
+        let expired = guilds
+            .iter()
+            .filter(|(_, boost)| boost.until <= now)
+            .map(|(guild_id, _)| *guild_id)
+            .collect::<Vec<_>>();
+
+        for guild_id in &expired {
+            guilds.remove(guild_id);
+        }
+
+        expired
+    }
+}
+
+/// Fills a newly boosted guild with the recent history of each of its text channels,
+/// so messages sent before the boost started are covered too. Returns how many messages were kept.
+pub async fn prefetch(
+    http: &Http,
+    boost: &CacheBoost,
+    guild_id: GuildId,
+) -> Result<usize, serenity::Error> {
+    let channels = guild_id.channels(http).await?;
+    let mut kept = 0;
+
+    for channel in channels.values() {
+        if !matches!(channel.kind, ChannelType::Text | ChannelType::News) {
+            continue;
+        }
+
+        // channels the bot can't read are skipped rather than failing the whole boost.
+        let Ok(mut messages) = channel
+            .id
+            .messages(http, GetMessages::new().limit(PREFETCH_LIMIT))
+            .await
+        else {
+            continue;
+        };
+
+        // pages come newest first, and the oldest are dropped first once a channel is full.
+        messages.reverse();
+        kept += messages.len();
+
+        for message in messages {
+            boost.insert(guild_id, message);
+        }
+    }
+
+    Ok(kept)
+}
+
+/// Ends boosts once their time is up, noting it in the server logs.
+pub async fn expire(
+    http: Arc<Http>,
+    pool: Pool<Sqlite>,
+    settings: Settings,
+    boost: Arc<CacheBoost>,
+) {
+    let mut interval = tokio::time::interval(EXPIRE_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        for guild_id in boost.expire() {
+            let Some(channel) = LogType::Server.fetch_channel(&pool, guild_id).await else {
+                continue;
+            };
+
+            let style = theme::style(&settings, guild_id, "changed").await;
+
+            let embed = CreateEmbed::new()
+                .title(format!("{} Cache boost ended", style.emoji))
+                .colour(style.colour)
+                .description("Messages are cached as usual again.");
+
+            if let Err(error) = maintenance::send(
+                &http,
+                &pool,
+                &settings,
+                guild_id,
+                LogType::Server,
+                channel,
+                embed,
+            )
+            .await
+            {
+                println!("Failed to announce end of cache boost: {error}");
+            }
+        }
+    }
+}
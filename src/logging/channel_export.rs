@@ -0,0 +1,82 @@
+use serenity::all::{ChannelId, GetMessages, Http, Message, UserId};
+
+/// How far back an export looks, counting everyone's messages, not just the bot's.
+const MAX_SCANNED: usize = 10_000;
+
+fn render(message: &Message) -> String {
+    let mut lines = vec![format!("[{}]", message.timestamp)];
+
+    if !message.content.is_empty() {
+        lines.push(message.content.clone());
+    }
+
+    for embed in &message.embeds {
+        if let Some(title) = &embed.title {
+            lines.push(format!("# {title}"));
+        }
+
+        if let Some(description) = &embed.description {
+            lines.push(description.clone());
+        }
+
+        for field in &embed.fields {
+            lines.push(format!("{}: {}", field.name, field.value));
+        }
+
+        if let Some(footer) = &embed.footer {
+            lines.push(footer.text.clone());
+        }
+    }
+
+    for attachment in &message.attachments {
+        lines.push(format!("attachment: {}", attachment.url));
+    }
+
+    lines.join("\n")
+}
+
+/// Renders the logs the bot posted in `channel_id` as a plain text archive, oldest first.
+/// Returns the archive and how many logs it holds.
+pub async fn export(
+    http: &Http,
+    channel_id: ChannelId,
+    bot_id: UserId,
+) -> Result<(String, usize), serenity::Error> {
+    let mut logs = Vec::new();
+    let mut scanned = 0;
+    let mut before = None;
+
+    while scanned < MAX_SCANNED {
+        let mut request = GetMessages::new().limit(100);
+
+        if let Some(before) = before {
+            request = request.before(before);
+        }
+
+        let page = channel_id.messages(http, request).await?;
+
+        let Some(oldest) = page.last() else {
+            break;
+        };
+
+        before = Some(oldest.id);
+        scanned += page.len();
+
+        let exhausted = page.len() < 100;
+        logs.extend(
+            page.into_iter()
+                .filter(|message| message.author.id == bot_id),
+        );
+
+        if exhausted {
+            break;
+        }
+    }
+
+    // pages come newest first.
+    logs.reverse();
+
+    let archive = logs.iter().map(render).collect::<Vec<_>>().join("\n\n");
+
+    Ok((archive, logs.len()))
+}
@@ -0,0 +1,221 @@
+use serde_json::Value;
+use serenity::{
+    all::{
+        audit_log::Action, AuditLogs, ChannelId, GuildChannel, GuildId, Http, LightMethod, Member,
+        Message, MessageId, Request, Role, RoleId, Route, UserId,
+    },
+    async_trait,
+    builder::{CreateAttachment, CreateMessage},
+    client::Context,
+};
+
+use crate::client::Error;
+
+/// Everything the event formatters need from Discord, beyond the event payload itself.
+#[async_trait]
+pub trait EventContext: Send + Sync {
+    fn cached_message(&self, channel_id: ChannelId, message_id: MessageId) -> Option<Message>;
+
+    /// Every cached message in `channel_id`, oldest first.
+    fn cached_messages(&self, channel_id: ChannelId) -> Vec<Message>;
+
+    fn cached_members(&self, guild_id: GuildId) -> Vec<Member>;
+
+    fn cached_member(&self, guild_id: GuildId, user_id: UserId) -> Option<Member>;
+
+    fn guild_owner(&self, guild_id: GuildId) -> Option<UserId>;
+
+    fn guild_name(&self, guild_id: GuildId) -> Option<String>;
+
+    /// The guild a cached channel belongs to, for events that don't say.
+    fn channel_guild(&self, channel_id: ChannelId) -> Option<GuildId>;
+
+    fn cached_role(&self, guild_id: GuildId, role_id: RoleId) -> Option<Role>;
+
+    fn cached_channel(&self, guild_id: GuildId, channel_id: ChannelId) -> Option<GuildChannel>;
+
+    /// Looks `message_id` up in the cache, falling back to fetching it.
+    async fn message(&self, channel_id: ChannelId, message_id: MessageId)
+        -> Result<Message, Error>;
+
+    async fn audit_logs(
+        &self,
+        guild_id: GuildId,
+        action: Option<Action>,
+        user_id: Option<UserId>,
+        limit: Option<u8>,
+    ) -> Result<AuditLogs, Error>;
+
+    /// Recent audit log entries of `action` as Discord sent them, for changes serenity doesn't model
+    /// and so drops when parsing.
+    async fn raw_audit_logs(
+        &self,
+        guild_id: GuildId,
+        action: Action,
+        limit: Option<u8>,
+    ) -> Result<Vec<Value>, Error>;
+
+    /// The messages pinned in `channel_id`, most recently pinned first.
+    async fn pins(&self, channel_id: ChannelId) -> Result<Vec<Message>, Error>;
+
+    async fn download_attachment(&self, url: &str) -> Result<CreateAttachment, Error>;
+
+    async fn direct_message(&self, user_id: UserId, message: CreateMessage) -> Result<(), Error>;
+}
+
+/// What delivering a log needs from Discord on top of formatting it, so the whole pipeline can run against a mock.
+#[async_trait]
+pub trait Delivery: EventContext {
+    /// For what's sent outside the log channels, like on-call pages.
+    fn http(&self) -> &Http;
+
+    async fn send_message(
+        &self,
+        channel_id: ChannelId,
+        message: CreateMessage,
+    ) -> Result<MessageId, Error>;
+
+    async fn pin_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<(), Error>;
+}
+
+#[async_trait]
+impl EventContext for Context {
+    fn cached_message(&self, channel_id: ChannelId, message_id: MessageId) -> Option<Message> {
+        self.cache
+            .message(channel_id, message_id)
+            .map(|message| message.clone())
+    }
+
+    fn cached_messages(&self, channel_id: ChannelId) -> Vec<Message> {
+        let mut messages = self
+            .cache
+            .channel_messages(channel_id)
+            .map(|messages| messages.values().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        messages.sort_by_key(|message| message.id);
+        messages
+    }
+
+    fn cached_members(&self, guild_id: GuildId) -> Vec<Member> {
+        self.cache
+            .guild(guild_id)
+            .map(|guild| guild.members.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn cached_member(&self, guild_id: GuildId, user_id: UserId) -> Option<Member> {
+        self.cache
+            .guild(guild_id)
+            .and_then(|guild| guild.members.get(&user_id).cloned())
+    }
+
+    fn guild_owner(&self, guild_id: GuildId) -> Option<UserId> {
+        self.cache.guild(guild_id).map(|guild| guild.owner_id)
+    }
+
+    fn guild_name(&self, guild_id: GuildId) -> Option<String> {
+        self.cache.guild(guild_id).map(|guild| guild.name.clone())
+    }
+
+    fn channel_guild(&self, channel_id: ChannelId) -> Option<GuildId> {
+        self.cache.guilds().into_iter().find(|guild_id| {
+            self.cache.guild(*guild_id).is_some_and(|guild| {
+                guild.channels.contains_key(&channel_id)
+                    || guild.threads.iter().any(|thread| thread.id == channel_id)
+            })
+        })
+    }
+
+    fn cached_role(&self, guild_id: GuildId, role_id: RoleId) -> Option<Role> {
+        self.cache
+            .guild(guild_id)
+            .and_then(|guild| guild.roles.get(&role_id).cloned())
+    }
+
+    fn cached_channel(&self, guild_id: GuildId, channel_id: ChannelId) -> Option<GuildChannel> {
+        self.cache
+            .guild(guild_id)
+            .and_then(|guild| guild.channels.get(&channel_id).cloned())
+    }
+
+    async fn message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Result<Message, Error> {
+        if let Some(message) = self.cached_message(channel_id, message_id) {
+            return Ok(message);
+        }
+
+        Ok(channel_id.message(self, message_id).await?)
+    }
+
+    async fn audit_logs(
+        &self,
+        guild_id: GuildId,
+        action: Option<Action>,
+        user_id: Option<UserId>,
+        limit: Option<u8>,
+    ) -> Result<AuditLogs, Error> {
+        Ok(guild_id
+            .audit_logs(self, action, user_id, None, limit)
+            .await?)
+    }
+
+    async fn raw_audit_logs(
+        &self,
+        guild_id: GuildId,
+        action: Action,
+        limit: Option<u8>,
+    ) -> Result<Vec<Value>, Error> {
+        let mut params = vec![("action_type", action.num().to_string())];
+        if let Some(limit) = limit {
+            params.push(("limit", limit.to_string()));
+        }
+
+        let mut logs: Value = self
+            .http
+            .fire(
+                Request::new(Route::GuildAuditLogs { guild_id }, LightMethod::Get)
+                    .params(Some(params)),
+            )
+            .await?;
+
+        Ok(match logs["audit_log_entries"].take() {
+            Value::Array(entries) => entries,
+            _ => Vec::new(),
+        })
+    }
+
+    async fn pins(&self, channel_id: ChannelId) -> Result<Vec<Message>, Error> {
+        Ok(channel_id.pins(self).await?)
+    }
+
+    async fn download_attachment(&self, url: &str) -> Result<CreateAttachment, Error> {
+        Ok(CreateAttachment::url(self, url).await?)
+    }
+
+    async fn direct_message(&self, user_id: UserId, message: CreateMessage) -> Result<(), Error> {
+        user_id.direct_message(self, message).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Delivery for Context {
+    fn http(&self) -> &Http {
+        &self.http
+    }
+
+    async fn send_message(
+        &self,
+        channel_id: ChannelId,
+        message: CreateMessage,
+    ) -> Result<MessageId, Error> {
+        Ok(channel_id.send_message(self, message).await?.id)
+    }
+
+    async fn pin_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<(), Error> {
+        Ok(channel_id.pin(self, message_id).await?)
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serenity::all::{Context, GuildId, UserId};
+
+use super::{formatter::EventFormatter, formatters::LogsCollapsed};
+use crate::{client::Data, commands::LogType};
+
+/// How many logs of one kind a single user may trigger per window before further logs get collapsed.
+pub(super) const THRESHOLD: u32 = 10;
+pub(super) const WINDOW: Duration = Duration::from_secs(60);
+/// How long a user stays damped after their last collapsed log.
+pub(super) const COOLDOWN: Duration = Duration::from_secs(5 * 60);
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+struct Tracker {
+    window_start: Instant,
+    count: u32,
+    damped_until: Option<Instant>,
+    suppressed: u32,
+    title: &'static str,
+    log_type: LogType,
+}
+
+pub struct Summary {
+    pub guild_id: GuildId,
+    pub user_id: UserId,
+    pub title: &'static str,
+    pub log_type: LogType,
+    pub count: u32,
+}
+
+#[derive(Default)]
+pub struct Damper {
+    trackers: Mutex<HashMap<(GuildId, UserId, &'static str), Tracker>>,
+}
+
+impl Damper {
+    /// Records a log about `user_id` and returns whether it should still be delivered.
+    pub fn allow(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        formatter: &dyn EventFormatter,
+        log_type: LogType,
+    ) -> bool {
+        self.allow_at(Instant::now(), guild_id, user_id, formatter, log_type)
+    }
+
+    pub(super) fn allow_at(
+        &self,
+        now: Instant,
+        guild_id: GuildId,
+        user_id: UserId,
+        formatter: &dyn EventFormatter,
+        log_type: LogType,
+    ) -> bool {
+        let mut trackers = self.trackers.lock().unwrap();
+
+        let tracker = trackers
+            .entry((guild_id, user_id, formatter.kind()))
+            .or_insert_with(|| Tracker {
+                window_start: now,
+                count: 0,
+                damped_until: None,
+                suppressed: 0,
+                title: formatter.title(),
+                log_type,
+            });
+
+        if let Some(damped_until) = tracker.damped_until
+            && damped_until > now
+        {
+            tracker.suppressed += 1;
+            tracker.damped_until = Some(now + COOLDOWN);
+            return false;
+        }
+
+        if now.duration_since(tracker.window_start) > WINDOW {
+            tracker.window_start = now;
+            tracker.count = 0;
+            tracker.damped_until = None;
+        }
+
+        tracker.count += 1;
+
+        if tracker.count > THRESHOLD {
+            tracker.suppressed += 1;
+            tracker.damped_until = Some(now + COOLDOWN);
+            return false;
+        }
+
+        true
+    }
+
+    /// Drains the collapsed log counts and forgets users that have calmed down.
+    pub fn take_summaries(&self) -> Vec<Summary> {
+        self.take_summaries_at(Instant::now())
+    }
+
+    pub(super) fn take_summaries_at(&self, now: Instant) -> Vec<Summary> {
+        let mut trackers = self.trackers.lock().unwrap();
+        let mut summaries = Vec::new();
+
+        for ((guild_id, user_id, _), tracker) in trackers.iter_mut() {
+            if tracker.suppressed > 0 {
+                summaries.push(Summary {
+                    guild_id: *guild_id,
+                    user_id: *user_id,
+                    title: tracker.title,
+                    log_type: tracker.log_type,
+                    count: tracker.suppressed,
+                });
+                tracker.suppressed = 0;
+            }
+        }
+
+        trackers.retain(|_, tracker| match tracker.damped_until {
+            Some(damped_until) => damped_until > now,
+            None => now.duration_since(tracker.window_start) <= WINDOW,
+        });
+
+        summaries
+    }
+}
+
+/// Periodically logs "user X triggered N more logs" summaries for damped users.
+pub async fn flush_summaries(ctx: Context, data: Data) {
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        for summary in data.damper.take_summaries() {
+            let entry = LogsCollapsed::entry(&summary);
+
+            if let Err(error) =
+                super::deliver(&ctx, &data, &LogsCollapsed(summary.log_type), entry).await
+            {
+                println!("Failed to log damping summary: {error}");
+            }
+        }
+    }
+}
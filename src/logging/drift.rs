@@ -0,0 +1,150 @@
+use std::{str::FromStr, time::Duration};
+
+use serenity::all::{ChannelId, Context, GuildChannel, GuildId, PermissionOverwrite};
+use sqlx::{Pool, Sqlite};
+
+use super::{formatters::PermissionDrift, now, permissions};
+use crate::{client::Data, settings::keys};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Stores `channel`'s current overwrites as the baseline drift is measured against.
+pub async fn snapshot(
+    pool: &Pool<Sqlite>,
+    channel: &GuildChannel,
+) -> Result<(), crate::client::Error> {
+    let channel_id = channel.id.to_string();
+    let guild_id = channel.guild_id.to_string();
+    let overwrites = serde_json::to_string(&channel.permission_overwrites)?;
+    let now = now() as i64;
+
+    sqlx::query!(
+        "INSERT INTO permission_snapshots (channel_id, guild_id, overwrites, taken_at) VALUES (?, ?, ?, ?)
+        ON CONFLICT (channel_id) DO UPDATE SET overwrites = excluded.overwrites, taken_at = excluded.taken_at",
+        channel_id,
+        guild_id,
+        overwrites,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn unwatch(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let channel_id = channel_id.to_string();
+
+    let result = sqlx::query!(
+        "DELETE FROM permission_snapshots WHERE channel_id = ? AND guild_id = ?",
+        channel_id,
+        guild_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn watched(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+) -> Result<Vec<(ChannelId, i64)>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    Ok(sqlx::query!(
+        "SELECT channel_id, taken_at FROM permission_snapshots WHERE guild_id = ?",
+        guild_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter_map(|row| Some((ChannelId::from_str(&row.channel_id).ok()?, row.taken_at)))
+    .collect())
+}
+
+/// Changes the bot sees happen live move the baseline along with them, so only changes it missed count as drift.
+pub async fn observe_update(
+    pool: &Pool<Sqlite>,
+    channel: &GuildChannel,
+) -> Result<(), crate::client::Error> {
+    let channel_id = channel.id.to_string();
+
+    let watched = sqlx::query!(
+        "SELECT channel_id FROM permission_snapshots WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .is_some();
+
+    if watched {
+        snapshot(pool, channel).await?;
+    }
+
+    Ok(())
+}
+
+/// Daily (and on startup) compares watched channels against their snapshot and alerts on any drift.
+pub async fn check(ctx: Context, data: Data) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let snapshots = match sqlx::query!(
+            "SELECT channel_id, guild_id, overwrites, taken_at FROM permission_snapshots"
+        )
+        .fetch_all(&data.pool)
+        .await
+        {
+            Ok(snapshots) => snapshots,
+            Err(error) => {
+                println!("Failed to fetch permission snapshots: {error}");
+                continue;
+            }
+        };
+
+        for row in snapshots {
+            let (Ok(channel_id), Ok(guild_id), Ok(old)) = (
+                ChannelId::from_str(&row.channel_id),
+                GuildId::from_str(&row.guild_id),
+                serde_json::from_str::<Vec<PermissionOverwrite>>(&row.overwrites),
+            ) else {
+                continue;
+            };
+
+            let Some(channel) = channel_id
+                .to_channel(&ctx)
+                .await
+                .ok()
+                .and_then(|channel| channel.guild())
+            else {
+                continue;
+            };
+
+            let diff = permissions::render_overwrite_diff(&old, &channel.permission_overwrites);
+
+            if let Err(error) = snapshot(&data.pool, &channel).await {
+                println!("Failed to refresh permission snapshot: {error}");
+            }
+
+            if diff.is_empty() {
+                continue;
+            }
+
+            let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+            let entry =
+                PermissionDrift::entry(guild_id, channel_id, row.taken_at, diff, timestamps);
+
+            if let Err(error) = super::deliver(&ctx, &data, &PermissionDrift, entry).await {
+                println!("Failed to log permission drift: {error}");
+            }
+        }
+    }
+}
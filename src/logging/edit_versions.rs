@@ -0,0 +1,181 @@
+use std::time::Duration;
+
+use serenity::{
+    all::{
+        ButtonStyle, ComponentInteraction, Context, FullEvent, GuildId, Interaction, Message,
+        Permissions,
+    },
+    builder::{
+        CreateActionRow, CreateAllowedMentions, CreateAttachment, CreateButton,
+        CreateInteractionResponse, CreateInteractionResponseMessage,
+    },
+};
+use sqlx::{Pool, Sqlite};
+
+use super::now;
+use crate::{client::Data, diff::line_diff};
+
+/// Edits where either version is longer than this get a compact log, with the full versions behind buttons.
+pub const COMPACT_EDIT_CHARS: usize = 1024;
+/// Discord's limit on message content. Longer reveals are sent as a file instead.
+const MAX_MESSAGE_CHARS: usize = 2000;
+/// How long the full versions stay available behind a log's buttons.
+const RETENTION_SECS: i64 = 30 * 24 * 60 * 60;
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Keeps both versions of an edited message, returning the ID its log's buttons refer to.
+pub async fn store(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    old: &Message,
+    new: &Message,
+) -> Result<i64, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let channel_id = new.channel_id.to_string();
+    let message_id = new.id.to_string();
+    let author_id = new.author.id.to_string();
+    let now = now() as i64;
+
+    Ok(sqlx::query!(
+        "INSERT INTO edit_versions (guild_id, channel_id, message_id, author_id, previous, new, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)",
+        guild_id,
+        channel_id,
+        message_id,
+        author_id,
+        old.content,
+        new.content,
+        now
+    )
+    .execute(pool)
+    .await?
+    .last_insert_rowid())
+}
+
+/// Drops every kept version in `guild_id`, for when it stops wanting message content stored.
+pub async fn forget(pool: &Pool<Sqlite>, guild_id: GuildId) -> Result<(), sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    sqlx::query!("DELETE FROM edit_versions WHERE guild_id = ?", guild_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Once an hour, drops versions older than [`RETENTION_SECS`]. Their buttons then say the edit isn't kept anymore.
+pub async fn expire(pool: Pool<Sqlite>) {
+    let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let cutoff = now() as i64 - RETENTION_SECS;
+        if let Err(error) = sqlx::query!("DELETE FROM edit_versions WHERE created_at < ?", cutoff)
+            .execute(&pool)
+            .await
+        {
+            println!("Failed to prune edit versions: {error}");
+        }
+    }
+}
+
+pub fn buttons(id: i64) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("edit:diff:{id}"))
+            .label("View full diff")
+            .style(ButtonStyle::Primary),
+        CreateButton::new(format!("edit:previous:{id}"))
+            .label("View previous version")
+            .style(ButtonStyle::Secondary),
+    ])
+}
+
+/// Shows `shown`, or attaches `raw` as `filename` when `shown` is too long for a message.
+fn reveal(shown: String, raw: String, filename: &str) -> CreateInteractionResponseMessage {
+    let response =
+        CreateInteractionResponseMessage::new().allowed_mentions(CreateAllowedMentions::new());
+
+    if shown.chars().count() <= MAX_MESSAGE_CHARS {
+        return response.content(shown);
+    }
+
+    response
+        .content("Too long to show here, so it's attached instead.")
+        .add_file(CreateAttachment::bytes(raw.into_bytes(), filename))
+}
+
+async fn on_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), crate::client::Error> {
+    let Some((view, id)) = interaction
+        .data
+        .custom_id
+        .strip_prefix("edit:")
+        .and_then(|rest| rest.split_once(':'))
+    else {
+        return Ok(());
+    };
+
+    let Ok(id) = id.parse::<i64>() else {
+        return Ok(());
+    };
+
+    let allowed = interaction
+        .member
+        .as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.contains(Permissions::MANAGE_MESSAGES));
+
+    let guild_id = interaction.guild_id.map(|guild_id| guild_id.to_string());
+
+    let versions = sqlx::query!(
+        "SELECT previous, new FROM edit_versions WHERE id = ? AND guild_id = ?",
+        id,
+        guild_id
+    )
+    .fetch_optional(&data.pool)
+    .await?;
+
+    let response = match (allowed, versions) {
+        (false, _) => CreateInteractionResponseMessage::new()
+            .content("You need the Manage Messages permission to view edited messages."),
+        (true, None) => {
+            CreateInteractionResponseMessage::new().content("This edit isn't kept anymore.")
+        }
+        (true, Some(versions)) if view == "diff" => {
+            let diff = line_diff(&versions.previous, &versions.new);
+            reveal(format!("```diff\n{diff}\n```"), diff, "diff.txt")
+        }
+        (true, Some(versions)) => {
+            reveal(versions.previous.clone(), versions.previous, "previous.txt")
+        }
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(response.ephemeral(true)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Reveals the full versions of a long edited message to a moderator pressing one of its log's buttons.
+pub async fn on_event(
+    ctx: &Context,
+    event: &FullEvent,
+    data: &Data,
+) -> Result<(), crate::client::Error> {
+    if let FullEvent::InteractionCreate {
+        interaction: Interaction::Component(interaction),
+    } = event
+    {
+        on_button(ctx, interaction, data).await?;
+    }
+
+    Ok(())
+}
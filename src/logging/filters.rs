@@ -0,0 +1,87 @@
+use serenity::all::{GuildId, Message, MessageFlags, MessageId, MessageType};
+
+use super::{audit, ignores, now, panic, EventContext};
+use crate::{
+    client::Data,
+    features::{self, Feature},
+    settings::{keys, Key},
+};
+
+/// Deletions this soon after posting are usually typo fixes.
+const QUICK_SELF_DELETE_SECS: i64 = 10;
+
+/// Whether a deletion should be skipped because the author quickly deleted their own message
+/// and the guild opted into suppressing those.
+pub async fn is_quick_self_delete(
+    ctx: &dyn EventContext,
+    data: &Data,
+    guild_id: GuildId,
+    message: &Message,
+) -> bool {
+    let enabled = data
+        .settings
+        .get(guild_id, &keys::SUPPRESS_QUICK_SELF_DELETES)
+        .await;
+
+    if !enabled
+        || panic::active(&data.settings, guild_id).await.is_some()
+        || !features::is_enabled(&data.pool, guild_id, Feature::AuditCorrelation).await
+        || now() as i64 - message.timestamp.unix_timestamp() > QUICK_SELF_DELETE_SECS
+    {
+        return false;
+    }
+
+    audit::find_message_delete(
+        ctx,
+        &data.pool,
+        guild_id,
+        message.author.id,
+        message.channel_id,
+    )
+    .await
+    .is_none()
+}
+
+/// Whether a deleted message is younger than the guild's configured minimum age for deletion logs.
+/// Uses the snowflake timestamp, so it works even if the message wasn't cached.
+pub async fn is_below_min_age(data: &Data, guild_id: GuildId, message_id: MessageId) -> bool {
+    let min_age = data
+        .settings
+        .get(guild_id, &keys::MIN_DELETED_MESSAGE_AGE)
+        .await;
+
+    min_age > 0
+        && now() as i64 - message_id.created_at().unix_timestamp() < min_age
+        && panic::active(&data.settings, guild_id).await.is_none()
+}
+
+/// Whether the guild turned on an opt-in log like reactions. Panic mode logs everything, so it counts as opted in.
+pub async fn is_opted_in(data: &Data, guild_id: GuildId, key: &Key<bool>) -> bool {
+    data.settings.get(guild_id, key).await
+        || panic::active(&data.settings, guild_id).await.is_some()
+}
+
+fn is_user_authored(message: &Message) -> bool {
+    matches!(
+        message.kind,
+        MessageType::Regular | MessageType::InlineReply
+    )
+}
+
+/// Whether a message should be skipped because it's ephemeral, or a system message (boosts, pins, joins, ...)
+/// in a guild that hasn't opted into logging those.
+pub async fn is_ignored_kind(data: &Data, guild_id: GuildId, message: &Message) -> bool {
+    if message
+        .flags
+        .is_some_and(|flags| flags.contains(MessageFlags::EPHEMERAL))
+    {
+        return true;
+    }
+
+    !is_user_authored(message) && !is_opted_in(data, guild_id, &keys::LOG_SYSTEM_MESSAGES).await
+}
+
+/// Whether a message's author, or the application behind it, is on the guild's or the instance's ignore list.
+pub async fn is_ignored_author(data: &Data, guild_id: GuildId, message: &Message) -> bool {
+    ignores::is_ignored(&data.pool, guild_id, message).await
+}
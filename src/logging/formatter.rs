@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+
+use serenity::{
+    all::{FullEvent, GuildId, MessageId, RoleId, UserId},
+    async_trait,
+    builder::{CreateActionRow, CreateEmbed, CreateMessage},
+};
+use sha2::{Digest, Sha256};
+use whatlang::Lang;
+
+use super::{formatters, EventContext};
+use crate::{client::Data, commands::LogType, mod_stats::ModAction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Notice,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Added,
+    Changed,
+    Removed,
+    Moderation,
+}
+
+impl Category {
+    pub fn as_key(&self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Changed => "changed",
+            Self::Removed => "removed",
+            Self::Moderation => "moderation",
+        }
+    }
+}
+
+pub struct LogEntry {
+    pub guild_id: GuildId,
+    pub embed: CreateEmbed,
+    pub followups: Vec<CreateMessage>,
+    /// Overrides the formatter's default severity for this entry only.
+    pub severity: Option<Severity>,
+    /// The user this log is about, used to group related logs into incidents.
+    pub subject: Option<UserId>,
+    /// Someone to ping alongside the log, for alerts that need immediate attention.
+    pub mention: Option<UserId>,
+    /// A role to ping alongside the log, e.g. the staff responsible for the server's settings.
+    pub mention_role: Option<RoleId>,
+    /// Detected language of the logged content, for language routes and ignores.
+    pub language: Option<Lang>,
+    /// The message this log is about, so its logs can be found with /resolve.
+    pub message: Option<MessageId>,
+    /// Buttons to attach to the log, e.g. for triage.
+    pub components: Vec<CreateActionRow>,
+    /// Message content to show as embed fields, added by [`LogEntry::render_content`].
+    pub content: Vec<(&'static str, String)>,
+    /// Followups carrying message content, like re-uploaded attachments or purge transcripts.
+    pub content_followups: Vec<CreateMessage>,
+    /// The moderation case this log records, linked to the log once it's posted so /case reason can edit it.
+    pub case: Option<i64>,
+    /// The moderator action this log records, counted towards /modstats as soon as it's formatted.
+    pub mod_action: Option<ModAction>,
+}
+
+impl LogEntry {
+    pub fn new(guild_id: GuildId, embed: CreateEmbed) -> Self {
+        Self {
+            guild_id,
+            embed,
+            followups: Vec::new(),
+            severity: None,
+            subject: None,
+            mention: None,
+            mention_role: None,
+            language: None,
+            message: None,
+            components: Vec::new(),
+            content: Vec::new(),
+            content_followups: Vec::new(),
+            case: None,
+            mod_action: None,
+        }
+    }
+
+    /// Adds message content as an embed field. Content is added to the embed centrally,
+    /// so guilds in metadata-only mode never have it posted or archived.
+    pub fn content(mut self, name: &'static str, text: String) -> Self {
+        self.content.push((name, text));
+        self
+    }
+
+    pub fn content_followups(mut self, followups: Vec<CreateMessage>) -> Self {
+        self.content_followups = followups;
+        self
+    }
+
+    /// Moves the entry's content into its embed and followups. In metadata-only mode, content fields
+    /// only show the length and a hash of the content, and content followups are left out.
+    pub fn render_content(mut self, metadata_only: bool) -> Self {
+        for (name, text) in std::mem::take(&mut self.content) {
+            let value = if text.is_empty() {
+                "None".to_string()
+            } else if metadata_only {
+                let hash = format!("{:x}", Sha256::digest(text.as_bytes()));
+
+                format!(
+                    "*Hidden* ({} characters, SHA-256 `{}`)",
+                    text.chars().count(),
+                    &hash[..16]
+                )
+            } else {
+                text.chars().take(1024).collect()
+            };
+
+            self.embed = self.embed.field(name, value, false);
+        }
+
+        let content_followups = std::mem::take(&mut self.content_followups);
+
+        if !metadata_only {
+            self.followups.splice(0..0, content_followups);
+        }
+
+        self
+    }
+
+    pub fn followups(mut self, followups: Vec<CreateMessage>) -> Self {
+        self.followups = followups;
+        self
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = Some(severity);
+        self
+    }
+
+    pub fn subject(mut self, subject: UserId) -> Self {
+        self.subject = Some(subject);
+        self
+    }
+
+    pub fn language(mut self, language: Option<Lang>) -> Self {
+        self.language = language;
+        self
+    }
+
+    pub fn message(mut self, message: MessageId) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    pub fn components(mut self, components: Vec<CreateActionRow>) -> Self {
+        self.components = components;
+        self
+    }
+
+    pub fn mention(mut self, mention: UserId) -> Self {
+        self.mention = Some(mention);
+        self
+    }
+
+    pub fn mention_role(mut self, role: RoleId) -> Self {
+        self.mention_role = Some(role);
+        self
+    }
+
+    pub fn case(mut self, case_number: i64) -> Self {
+        self.case = Some(case_number);
+        self
+    }
+
+    pub fn mod_action(mut self, moderator: UserId, action: &'static str, count: i64) -> Self {
+        self.mod_action = Some(ModAction {
+            moderator,
+            action,
+            count,
+        });
+        self
+    }
+}
+
+#[async_trait]
+pub trait EventFormatter: Send + Sync {
+    /// Unique name of the kind of log this formatter produces, e.g. `message_delete`.
+    fn kind(&self) -> &'static str;
+
+    /// The gateway event this formatter handles, as returned by [`FullEvent::snake_case_name`].
+    fn event(&self) -> &'static str;
+
+    /// Human-readable name of the log kind, used as the embed title.
+    fn title(&self) -> &'static str;
+
+    fn category(&self) -> Category;
+
+    fn severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn default_route(&self) -> LogType;
+
+    /// Whether logs about members with a trusted role are archived instead of posted.
+    fn respects_trusted_roles(&self) -> bool {
+        false
+    }
+
+    /// Whether this formatter reads the audit log to attribute actions, which needs the View Audit Log permission.
+    fn uses_audit_log(&self) -> bool {
+        false
+    }
+
+    /// Whether this formatter tags its logs with the content's language, so `/language` rules apply to them.
+    fn detects_language(&self) -> bool {
+        false
+    }
+
+    /// Whether this log means a raid or nuke is likely underway, which is forwarded to the quarantine webhook.
+    fn is_threat_detection(&self) -> bool {
+        false
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry>;
+}
+
+pub struct FormatterRegistry {
+    formatters: HashMap<&'static str, Vec<Box<dyn EventFormatter>>>,
+}
+
+impl FormatterRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            formatters: HashMap::new(),
+        };
+
+        for formatter in formatters::all() {
+            registry.register(formatter);
+        }
+
+        registry
+    }
+
+    pub fn by_kind(&self, kind: &str) -> Option<&dyn EventFormatter> {
+        self.formatters
+            .values()
+            .flatten()
+            .find(|formatter| formatter.kind() == kind)
+            .map(|formatter| formatter.as_ref())
+    }
+
+    /// Every registered formatter, sorted by kind.
+    pub fn all(&self) -> Vec<&dyn EventFormatter> {
+        let mut formatters = self
+            .formatters
+            .values()
+            .flatten()
+            .map(|formatter| formatter.as_ref())
+            .collect::<Vec<_>>();
+
+        formatters.sort_unstable_by_key(|formatter| formatter.kind());
+        formatters
+    }
+
+    pub fn kinds(&self) -> Vec<&'static str> {
+        let mut kinds = self
+            .formatters
+            .values()
+            .flatten()
+            .map(|formatter| formatter.kind())
+            .collect::<Vec<_>>();
+
+        kinds.sort_unstable();
+        kinds.dedup();
+        kinds
+    }
+
+    pub fn register(&mut self, formatter: Box<dyn EventFormatter>) {
+        self.formatters
+            .entry(formatter.event())
+            .or_default()
+            .push(formatter);
+    }
+
+    pub async fn format<'a>(
+        &'a self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Vec<(&'a dyn EventFormatter, LogEntry)> {
+        let Some(formatters) = self.formatters.get(event.snake_case_name()) else {
+            return Vec::new();
+        };
+
+        let mut entries = Vec::new();
+
+        for formatter in formatters {
+            if let Some(entry) = formatter.format(ctx, event, data).await {
+                entries.push((formatter.as_ref(), entry));
+            }
+        }
+
+        entries
+    }
+}
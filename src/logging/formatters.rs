@@ -0,0 +1,237 @@
+use serenity::{
+    all::{Attachment, GuildId, Message, User},
+    builder::{CreateEmbed, CreateEmbedAuthor, CreateMessage},
+};
+
+use super::{formatter::EventFormatter, now, EventContext};
+use crate::{commands::LogType, settings::Settings};
+
+mod automod;
+mod bans;
+mod boosts;
+mod channels;
+mod emojis;
+mod first_message;
+mod guild;
+mod integrations;
+mod invites;
+mod members;
+mod messages;
+mod nuke;
+mod onboarding;
+mod pins;
+mod reactions;
+mod reports;
+mod roles;
+mod soundboard;
+mod stickers;
+mod threads;
+mod verification;
+mod voice;
+mod webhooks;
+
+pub use channels::PermissionDrift;
+pub use guild::UnusualActivity;
+pub use members::{LogsCollapsed, MemberMilestone};
+pub use reports::MemberReport;
+pub use roles::BulkRoleChange;
+pub use soundboard::{SoundCreate, SoundDelete, SoundUpdate};
+
+/// Discord rejects messages with more than 10 files, so that's also the most a route can be set to re-upload.
+pub const MAX_ATTACHMENT_CAP: usize = 10;
+
+pub fn attachment_cap_key(log_type: LogType) -> String {
+    format!("attachment_cap.{}", log_type.as_column_name())
+}
+
+/// How many attachments a single log on `log_type` may re-upload. The rest are only linked.
+pub async fn attachment_cap(settings: &Settings, guild_id: GuildId, log_type: LogType) -> usize {
+    settings
+        .get_raw(guild_id, &attachment_cap_key(log_type))
+        .await
+        .and_then(|cap| cap.parse::<usize>().ok())
+        .map_or(MAX_ATTACHMENT_CAP, |cap| cap.min(MAX_ATTACHMENT_CAP))
+}
+
+/// Builds a followup re-uploading `attachments`, up to `cap` of them. Anything past the cap,
+/// or that fails to download, is linked instead.
+async fn attachment_followup(
+    ctx: &dyn EventContext,
+    intro: Option<String>,
+    attachments: &[Attachment],
+    cap: usize,
+) -> CreateMessage {
+    let mut message = CreateMessage::new();
+    let mut links = Vec::new();
+
+    for (index, attachment) in attachments.iter().enumerate() {
+        if index < cap
+            && let Ok(file) = ctx.download_attachment(&attachment.url).await
+        {
+            message = message.add_file(file);
+        } else {
+            links.push(format!("[{}](<{}>)", attachment.filename, attachment.url));
+        }
+    }
+
+    let mut content = intro.unwrap_or_default();
+
+    if !links.is_empty() {
+        content += &format!(
+            "\n{} not re-uploaded:",
+            pluralize("This attachment was", "These attachments were", links.len())
+        );
+
+        // message content is capped at 2000 characters, and attachment URLs are long.
+        for (index, link) in links.iter().enumerate() {
+            if content.len() + link.len() > 1900 {
+                content += &format!("\n...and {} more", links.len() - index);
+                break;
+            }
+
+            content += "\n";
+            content += link;
+        }
+    }
+
+    if !content.trim().is_empty() {
+        message = message.content(content.trim());
+    }
+
+    message
+}
+
+/// Renders `messages` as a plain text transcript, one line per message plus one per attachment.
+fn transcript(messages: &[Message]) -> String {
+    messages
+        .iter()
+        .map(|message| {
+            let mut line = format!(
+                "[{}] {} ({}): {}",
+                message.timestamp, message.author.name, message.author.id, message.content
+            );
+
+            for attachment in &message.attachments {
+                line += &format!("\n    attachment: {}", attachment.url);
+            }
+
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub(super) fn all() -> Vec<Box<dyn EventFormatter>> {
+    let [channel_deletions, role_deletions] = nuke::MassDeletion::pair();
+
+    vec![
+        Box::new(messages::MessageDelete),
+        Box::new(messages::MessageUpdate),
+        Box::new(messages::MessageBulkDelete),
+        Box::new(members::MemberJoin),
+        Box::new(members::MemberLeave),
+        Box::new(members::MemberKick),
+        Box::new(members::MemberPrune),
+        Box::new(members::MemberRoles),
+        Box::new(members::MemberNickname),
+        Box::new(members::MemberTimeout),
+        Box::new(members::LogsCollapsed(LogType::Member)),
+        Box::new(members::MemberMilestone),
+        Box::new(boosts::BoostStart),
+        Box::new(boosts::BoostStop),
+        Box::new(boosts::PremiumTierChange),
+        Box::new(bans::MemberBan),
+        Box::new(bans::MemberUnban),
+        Box::new(verification::LateVerification),
+        Box::new(verification::MemberVerified),
+        Box::new(first_message::FirstMessage),
+        Box::new(webhooks::WebhookSpoof),
+        Box::new(reports::MemberReport),
+        Box::new(voice::VoiceActivity),
+        Box::new(voice::VoiceChannelStatus),
+        Box::new(voice::VoiceHopSpam),
+        Box::new(channels::ChannelCreate),
+        Box::new(channels::ChannelDelete),
+        Box::new(channels::ChannelUpdate),
+        Box::new(channels::PermissionDrift),
+        Box::new(roles::RoleCreate),
+        Box::new(roles::RoleDelete),
+        Box::new(roles::RoleUpdate),
+        Box::new(roles::BulkRoleChange),
+        Box::new(threads::ThreadCreate),
+        Box::new(threads::ForumPostCreate),
+        Box::new(threads::ThreadDelete),
+        Box::new(threads::ThreadUpdate),
+        Box::new(emojis::EmojiUpdate),
+        Box::new(stickers::StickerUpdate),
+        Box::new(soundboard::SoundCreate),
+        Box::new(soundboard::SoundUpdate),
+        Box::new(soundboard::SoundDelete),
+        Box::new(invites::InviteCreate),
+        Box::new(invites::InviteDelete),
+        Box::new(guild::GuildUpdate),
+        Box::new(guild::VanityUrlChange),
+        Box::new(guild::MfaLevelChange),
+        Box::new(guild::UnusualActivity),
+        Box::new(onboarding::OnboardingUpdate),
+        Box::new(automod::AutoModRuleCreate),
+        Box::new(automod::AutoModRuleUpdate),
+        Box::new(automod::AutoModRuleDelete),
+        Box::new(integrations::IntegrationCreate),
+        Box::new(integrations::IntegrationUpdate),
+        Box::new(integrations::IntegrationDelete),
+        Box::new(integrations::BotAdd),
+        Box::new(reactions::ReactionAdd),
+        Box::new(reactions::ReactionRemove),
+        Box::new(reactions::ReactionClear),
+        Box::new(reactions::ReactionEmojiClear),
+        Box::new(pins::PinsUpdate),
+        Box::new(channel_deletions),
+        Box::new(role_deletions),
+    ]
+}
+
+fn display_name(user: &User) -> String {
+    let nick = user
+        .member
+        .as_ref()
+        .and_then(|member| member.nick.clone())
+        .or_else(|| user.global_name.clone());
+    let name = format!("@{}", user.name.clone());
+
+    if let Some(nick) = nick {
+        format!("{name} ({nick})")
+    } else {
+        name
+    }
+}
+
+pub(super) fn base_embed(user: &User) -> CreateEmbed {
+    CreateEmbed::new().author(
+        CreateEmbedAuthor::new(display_name(user)).icon_url(
+            user.avatar_url()
+                .unwrap_or_else(|| user.default_avatar_url()),
+        ),
+    )
+}
+
+fn pluralize<'a>(singular: &'a str, plural: &'a str, count: usize) -> &'a str {
+    match count {
+        1 => singular,
+        _ => plural,
+    }
+}
+
+/// Joins `lines` into a field value, listing at most 20 so a whole emoji or sticker pack changing at once fits in an embed.
+fn list_field(mut lines: Vec<String>) -> String {
+    const MAX_LISTED: usize = 20;
+
+    let hidden = lines.len().saturating_sub(MAX_LISTED);
+    lines.truncate(MAX_LISTED);
+
+    if hidden > 0 {
+        lines.push(format!("...and {hidden} more"));
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,399 @@
+use serenity::{
+    all::{
+        audit_log::{Action, AutoModAction},
+        automod::{KeywordPresetType, Rule, Trigger, TriggerMetadata},
+        Change, FullEvent, UserId,
+    },
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::now;
+use crate::{
+    client::Data,
+    commands::LogType,
+    diff::asymmetric_diff,
+    logging::{
+        audit,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        EventContext,
+    },
+    settings::keys,
+};
+
+fn describe_trigger(trigger: &Trigger) -> String {
+    match trigger {
+        Trigger::Keyword { .. } => "Keywords".to_string(),
+        Trigger::Spam => "Suspected spam".to_string(),
+        Trigger::KeywordPreset { presets, .. } => format!(
+            "Keyword presets: {}",
+            presets
+                .iter()
+                .map(|preset| match preset {
+                    KeywordPresetType::Profanity => "Profanity".to_string(),
+                    KeywordPresetType::SexualContent => "Sexual content".to_string(),
+                    KeywordPresetType::Slurs => "Slurs".to_string(),
+                    preset => format!("Unknown ({})", u8::from(*preset)),
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Trigger::MentionSpam {
+            mention_total_limit,
+        } => {
+            format!("Mention spam, over {mention_total_limit} mentions")
+        }
+        trigger => format!("Unknown ({})", u8::from(trigger.kind())),
+    }
+}
+
+/// The word lists of a trigger: its keywords, regex patterns and allow list, in that order.
+fn word_lists(trigger: &Trigger) -> [(&'static str, Vec<String>); 3] {
+    let (keywords, patterns, allow_list) = match trigger {
+        Trigger::Keyword {
+            strings,
+            regex_patterns,
+            allow_list,
+        } => (strings.clone(), regex_patterns.clone(), allow_list.clone()),
+        Trigger::KeywordPreset { allow_list, .. } => (Vec::new(), Vec::new(), allow_list.clone()),
+        _ => (Vec::new(), Vec::new(), Vec::new()),
+    };
+
+    [
+        ("Keywords", keywords),
+        ("Regex patterns", patterns),
+        ("Allowed", allow_list),
+    ]
+}
+
+/// Same as [`word_lists`], from the trigger metadata of an audit log change.
+fn metadata_word_lists(metadata: Option<&TriggerMetadata>) -> [(&'static str, Vec<String>); 3] {
+    let list = |list: Option<&Vec<String>>| list.cloned().unwrap_or_default();
+
+    [
+        (
+            "Keywords",
+            list(metadata.and_then(|metadata| metadata.keyword_filter.as_ref())),
+        ),
+        (
+            "Regex patterns",
+            list(metadata.and_then(|metadata| metadata.regex_patterns.as_ref())),
+        ),
+        (
+            "Allowed",
+            list(metadata.and_then(|metadata| metadata.allow_list.as_ref())),
+        ),
+    ]
+}
+
+/// Lists words as code, so wildcards and markdown in them show as typed. Rules can have up to a
+/// thousand keywords, so the list stops short of the 1024 character limit of embed fields.
+fn word_field(words: &[String]) -> String {
+    let mut value = String::new();
+
+    for (index, word) in words.iter().enumerate() {
+        if value.len() + word.len() > 950 {
+            value += &format!("\n...and {} more", words.len() - index);
+            break;
+        }
+
+        value += &format!("\n`{word}`");
+    }
+
+    value.trim().to_string()
+}
+
+fn describe_executor(user_id: Option<UserId>) -> String {
+    user_id.map_or("Unknown".to_string(), |user_id| format!("<@{user_id}>"))
+}
+
+/// The embed shared by created and deleted rules, describing the whole rule.
+async fn rule_embed(
+    data: &Data,
+    rule: &Rule,
+    description: String,
+    verb: &str,
+    executor: Option<UserId>,
+) -> CreateEmbed {
+    let timestamps = data
+        .settings
+        .get(rule.guild_id, &keys::TIMESTAMP_STYLE)
+        .await;
+
+    let mut embed = CreateEmbed::new()
+        .description(description)
+        .field("Trigger", describe_trigger(&rule.trigger), true)
+        .field("Enabled", if rule.enabled { "Yes" } else { "No" }, true)
+        .field(format!("{verb} by"), describe_executor(executor), true);
+
+    for (name, words) in word_lists(&rule.trigger) {
+        if !words.is_empty() {
+            embed = embed.field(name, word_field(&words), false);
+        }
+    }
+
+    embed.field("Timestamp", timestamps.format(now() as i64), true)
+}
+
+pub struct AutoModRuleCreate;
+
+#[async_trait]
+impl EventFormatter for AutoModRuleCreate {
+    fn kind(&self) -> &'static str {
+        "automod_rule_create"
+    }
+
+    fn title(&self) -> &'static str {
+        "AutoMod Rule Created"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "auto_moderation_rule_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::AutoModRuleCreate { rule } = event else {
+            return None;
+        };
+
+        let embed = rule_embed(
+            data,
+            rule,
+            format!("The AutoMod rule **{}** was created.", rule.name),
+            "Created",
+            Some(rule.creator_id),
+        )
+        .await;
+
+        Some(LogEntry::new(rule.guild_id, embed).subject(rule.creator_id))
+    }
+}
+
+/// Edits to AutoMod rules, with the keywords that were added and removed. Quietly removing a keyword or
+/// disabling a rule is a common way to let something through, so those are logged as warnings.
+pub struct AutoModRuleUpdate;
+
+#[async_trait]
+impl EventFormatter for AutoModRuleUpdate {
+    fn kind(&self) -> &'static str {
+        "automod_rule_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "AutoMod Rule Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "auto_moderation_rule_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::AutoModRuleUpdate { rule } = event else {
+            return None;
+        };
+
+        // the event only carries the rule as it is now, so what changed comes from the audit log.
+        let entry = audit::find_target_action(
+            ctx,
+            &data.pool,
+            rule.guild_id,
+            rule.id.get(),
+            Action::AutoMod(AutoModAction::RuleUpdate),
+        )
+        .await;
+
+        let timestamps = data
+            .settings
+            .get(rule.guild_id, &keys::TIMESTAMP_STYLE)
+            .await;
+
+        let mut embed = CreateEmbed::new()
+            .description(format!("The AutoMod rule **{}** was updated.", rule.name))
+            .field("Trigger", describe_trigger(&rule.trigger), true);
+
+        let Some(entry) = entry else {
+            let embed = embed
+                .field("Updated by", "Unknown", true)
+                .field(
+                    "Changes",
+                    "The audit log entry for this edit couldn't be found, so the changes can't be shown.",
+                    false,
+                )
+                .field("Timestamp", timestamps.format(now() as i64), true);
+
+            return Some(LogEntry::new(rule.guild_id, embed));
+        };
+
+        embed = embed.field("Updated by", format!("<@{}>", entry.user_id), true);
+
+        let mut weakened = false;
+
+        for change in entry.changes.iter().flatten() {
+            match change {
+                Change::Name {
+                    old: Some(old),
+                    new: Some(new),
+                } => {
+                    embed = embed.field("Name", format!("{old} → {new}"), false);
+                }
+                Change::Enabled { old, new } => {
+                    weakened |= *new == Some(false);
+
+                    let describe = |enabled: &Option<bool>| match enabled {
+                        Some(true) => "Yes",
+                        Some(false) => "No",
+                        None => "Unknown",
+                    };
+
+                    embed = embed.field(
+                        "Enabled",
+                        format!("{} → {}", describe(old), describe(new)),
+                        true,
+                    );
+                }
+                Change::TriggerMetadata { old, new } => {
+                    let old = metadata_word_lists(old.as_ref());
+                    let new = metadata_word_lists(new.as_ref());
+
+                    for ((name, old), (_, new)) in old.into_iter().zip(new) {
+                        let diff = asymmetric_diff(&old, &new);
+
+                        // a newly allowed word lets messages through just like a removed keyword does.
+                        let loosened = match name {
+                            "Allowed" => &diff.added,
+                            _ => &diff.removed,
+                        };
+                        weakened |= !loosened.is_empty();
+
+                        if !diff.added.is_empty() {
+                            embed = embed.field(
+                                format!("{name} added"),
+                                word_field(&diff.added),
+                                false,
+                            );
+                        }
+
+                        if !diff.removed.is_empty() {
+                            embed = embed.field(
+                                format!("{name} removed"),
+                                word_field(&diff.removed),
+                                false,
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        embed = embed.field("Timestamp", timestamps.format(now() as i64), true);
+
+        let mut log_entry = LogEntry::new(rule.guild_id, embed).subject(entry.user_id);
+
+        if weakened {
+            log_entry = log_entry.severity(Severity::Warning);
+        }
+
+        Some(log_entry)
+    }
+}
+
+pub struct AutoModRuleDelete;
+
+#[async_trait]
+impl EventFormatter for AutoModRuleDelete {
+    fn kind(&self) -> &'static str {
+        "automod_rule_delete"
+    }
+
+    fn title(&self) -> &'static str {
+        "AutoMod Rule Deleted"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn event(&self) -> &'static str {
+        "auto_moderation_rule_delete"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::AutoModRuleDelete { rule } = event else {
+            return None;
+        };
+
+        let executor = audit::find_executor(
+            ctx,
+            &data.pool,
+            rule.guild_id,
+            rule.id.get(),
+            &[Action::AutoMod(AutoModAction::RuleDelete)],
+        )
+        .await;
+
+        let embed = rule_embed(
+            data,
+            rule,
+            format!("The AutoMod rule **{}** was deleted.", rule.name),
+            "Deleted",
+            executor,
+        )
+        .await;
+
+        let mut entry = LogEntry::new(rule.guild_id, embed);
+
+        if let Some(user_id) = executor {
+            entry = entry.subject(user_id);
+        }
+
+        Some(entry)
+    }
+}
@@ -0,0 +1,253 @@
+use serenity::{
+    all::{
+        audit_log::{Action, MemberAction},
+        FullEvent,
+    },
+    async_trait,
+    builder::CreateMessage,
+};
+
+use super::base_embed;
+use crate::{
+    appeals, cases,
+    client::Data,
+    commands::LogType,
+    logging::{
+        audit,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        EventContext,
+    },
+    settings::keys,
+};
+
+pub struct MemberBan;
+
+#[async_trait]
+impl EventFormatter for MemberBan {
+    fn kind(&self) -> &'static str {
+        "member_ban"
+    }
+
+    fn title(&self) -> &'static str {
+        "Member Banned"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_ban_addition"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Moderation
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildBanAddition {
+            guild_id,
+            banned_user: user,
+        } = event
+        else {
+            return None;
+        };
+
+        let entry = audit::wait_for_member_action(
+            ctx,
+            &data.pool,
+            *guild_id,
+            user.id,
+            Action::Member(MemberAction::BanAdd),
+        )
+        .await;
+
+        let moderator = entry.as_ref().map(|entry| entry.user_id);
+        let reason = entry.and_then(|entry| entry.reason);
+
+        // without a moderator there's nobody to attribute the case to.
+        let case_number = match moderator {
+            Some(moderator) => cases::open(
+                &data.pool,
+                *guild_id,
+                "ban",
+                moderator,
+                Some(user.id.to_string()),
+                reason.clone(),
+            )
+            .await
+            .ok(),
+            None => None,
+        };
+
+        let mut embed = base_embed(user)
+            .description(format!("<@{}> ({}) was banned.", user.id, user.name))
+            .field(
+                "Moderator",
+                moderator.map_or("Unknown".to_string(), |moderator| format!("<@{moderator}>")),
+                true,
+            );
+
+        if let Some(case_number) = case_number {
+            embed = embed.field("Case", format!("#{case_number}"), true);
+        }
+
+        embed = embed.field(
+            "Reason",
+            reason.unwrap_or("No reason given".to_string()),
+            false,
+        );
+
+        if let Some(link) = appeals::link(&data.settings, *guild_id, user).await {
+            embed = embed.field("Appeal Link", &link, false);
+
+            if data.settings.get(*guild_id, &keys::APPEAL_DM).await {
+                let server = ctx
+                    .guild_name(*guild_id)
+                    .unwrap_or_else(|| "a server".to_string());
+
+                // usually fails if the user no longer shares a server with the bot, or has DMs closed.
+                let delivered = ctx
+                    .direct_message(
+                        user.id,
+                        CreateMessage::new().content(format!(
+                            "You have been banned from **{server}**. If you'd like to appeal, you can do so here: {link}"
+                        )),
+                    )
+                    .await
+                    .is_ok();
+
+                if let Some(case_number) = case_number {
+                    let _ = cases::record_appeal_dm(&data.pool, *guild_id, case_number, delivered)
+                        .await;
+                }
+
+                embed = embed.field(
+                    "Appeal DM",
+                    if delivered { "Delivered" } else { "Failed" },
+                    true,
+                );
+            }
+        }
+
+        let mut entry = LogEntry::new(*guild_id, embed).subject(user.id);
+
+        if let Some(moderator) = moderator {
+            entry = entry.mod_action(moderator, "ban", 1);
+        }
+
+        if let Some(case_number) = case_number {
+            entry = entry.case(case_number);
+        }
+
+        Some(entry)
+    }
+}
+
+pub struct MemberUnban;
+
+#[async_trait]
+impl EventFormatter for MemberUnban {
+    fn kind(&self) -> &'static str {
+        "member_unban"
+    }
+
+    fn title(&self) -> &'static str {
+        "Member Unbanned"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_ban_removal"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Moderation
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildBanRemoval {
+            guild_id,
+            unbanned_user: user,
+        } = event
+        else {
+            return None;
+        };
+
+        let entry = audit::wait_for_member_action(
+            ctx,
+            &data.pool,
+            *guild_id,
+            user.id,
+            Action::Member(MemberAction::BanRemove),
+        )
+        .await;
+
+        let moderator = entry.as_ref().map(|entry| entry.user_id);
+        let reason = entry.and_then(|entry| entry.reason);
+
+        let case_number = match moderator {
+            Some(moderator) => cases::open(
+                &data.pool,
+                *guild_id,
+                "unban",
+                moderator,
+                Some(user.id.to_string()),
+                reason.clone(),
+            )
+            .await
+            .ok(),
+            None => None,
+        };
+
+        let mut embed = base_embed(user)
+            .description(format!("<@{}> ({}) was unbanned.", user.id, user.name))
+            .field(
+                "Moderator",
+                moderator.map_or("Unknown".to_string(), |moderator| format!("<@{moderator}>")),
+                true,
+            );
+
+        if let Some(case_number) = case_number {
+            embed = embed.field("Case", format!("#{case_number}"), true);
+        }
+
+        // unbans rarely come with a reason, so the field is left out rather than always saying there's none.
+        if let Some(reason) = reason {
+            embed = embed.field("Reason", reason, false);
+        }
+
+        let mut entry = LogEntry::new(*guild_id, embed).subject(user.id);
+
+        if let Some(case_number) = case_number {
+            entry = entry.case(case_number);
+        }
+
+        Some(entry)
+    }
+}
@@ -0,0 +1,258 @@
+use serenity::{
+    all::{FullEvent, GuildId, PremiumTier, User},
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::{base_embed, now};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        boosts,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        timestamps, EventContext,
+    },
+    settings::keys,
+};
+
+/// Boosts older than this when the bot first sees them predate it, so they're recorded without being announced.
+const RECENT_BOOST_SECS: i64 = 10 * 60;
+
+async fn total_field(data: &Data, guild_id: GuildId, user: &User) -> String {
+    match boosts::total(&data.pool, guild_id, user.id).await {
+        Ok(total) => timestamps::describe_duration(total),
+        Err(_) => "Unknown".to_string(),
+    }
+}
+
+pub struct BoostStart;
+
+#[async_trait]
+impl EventFormatter for BoostStart {
+    fn kind(&self) -> &'static str {
+        "boost_start"
+    }
+
+    fn title(&self) -> &'static str {
+        "Server Boosted"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberUpdate { event, .. } = event else {
+            return None;
+        };
+
+        let started_at = event.premium_since?.unix_timestamp();
+        let (guild_id, user) = (event.guild_id, &event.user);
+
+        // the archive, not the cached member, decides whether this is a transition, so restarts don't re-announce boosts.
+        let started = boosts::start(&data.pool, guild_id, user.id, started_at)
+            .await
+            .ok()?;
+
+        let now = now() as i64;
+
+        if !started || now - started_at > RECENT_BOOST_SECS {
+            return None;
+        }
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = base_embed(user)
+            .description(format!(
+                "<@{}> ({}) boosted the server.",
+                user.id, user.name
+            ))
+            .field(
+                "Total boosted",
+                total_field(data, guild_id, user).await,
+                true,
+            )
+            .field("Timestamp", timestamps.format(started_at), true);
+
+        Some(LogEntry::new(guild_id, embed).subject(user.id))
+    }
+}
+
+pub struct BoostStop;
+
+#[async_trait]
+impl EventFormatter for BoostStop {
+    fn kind(&self) -> &'static str {
+        "boost_stop"
+    }
+
+    fn title(&self) -> &'static str {
+        "Boost Ended"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberUpdate { event, .. } = event else {
+            return None;
+        };
+
+        if event.premium_since.is_some() {
+            return None;
+        }
+
+        let (guild_id, user) = (event.guild_id, &event.user);
+
+        let stopped = boosts::stop(&data.pool, guild_id, user.id).await.ok()?;
+
+        if !stopped {
+            return None;
+        }
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = base_embed(user)
+            .description(format!(
+                "<@{}> ({}) stopped boosting the server.",
+                user.id, user.name
+            ))
+            .field(
+                "Total boosted",
+                total_field(data, guild_id, user).await,
+                true,
+            )
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(guild_id, embed).subject(user.id))
+    }
+}
+
+/// The perks staff most often notice gaining or losing at `tier`.
+fn perks(tier: PremiumTier) -> &'static str {
+    match tier {
+        PremiumTier::Tier0 => "50 emoji slots, 8 sticker slots, 10 MB uploads",
+        PremiumTier::Tier1 => {
+            "100 emoji slots, 15 sticker slots, 10 MB uploads, animated server icon"
+        }
+        PremiumTier::Tier2 => "150 emoji slots, 30 sticker slots, 50 MB uploads, server banner",
+        PremiumTier::Tier3 => "250 emoji slots, 60 sticker slots, 100 MB uploads, vanity URL",
+        _ => "Unknown",
+    }
+}
+
+fn describe_tier(tier: PremiumTier) -> String {
+    match tier {
+        PremiumTier::Tier0 => "No level".to_string(),
+        tier => format!("Level {}", u8::from(tier)),
+    }
+}
+
+pub struct PremiumTierChange;
+
+#[async_trait]
+impl EventFormatter for PremiumTierChange {
+    fn kind(&self) -> &'static str {
+        "premium_tier_change"
+    }
+
+    fn title(&self) -> &'static str {
+        "Boost Level Changed"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildUpdate {
+            old_data_if_available: Some(old),
+            new_data: new,
+        } = event
+        else {
+            return None;
+        };
+
+        if old.premium_tier == new.premium_tier {
+            return None;
+        }
+
+        let gained = u8::from(new.premium_tier) > u8::from(old.premium_tier);
+        let timestamps = data.settings.get(new.id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "**{}** {} boost level {}.",
+                new.name,
+                if gained { "reached" } else { "dropped to" },
+                u8::from(new.premium_tier)
+            ))
+            .field(
+                "Level",
+                format!(
+                    "{} → {}",
+                    describe_tier(old.premium_tier),
+                    describe_tier(new.premium_tier)
+                ),
+                true,
+            )
+            .field(
+                "Boosts",
+                new.premium_subscription_count
+                    .map_or("Unknown".to_string(), |count| count.to_string()),
+                true,
+            )
+            .field("Perks now", perks(new.premium_tier), false)
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        let mut entry = LogEntry::new(new.id, embed);
+
+        // losing perks can break things, like uploads over the new limit or emojis past the slot count.
+        if !gained {
+            entry = entry.severity(Severity::Warning);
+        }
+
+        Some(entry)
+    }
+}
@@ -0,0 +1,389 @@
+use serenity::{
+    all::{
+        audit_log::{Action, ChannelAction, ChannelOverwriteAction},
+        ChannelId, FullEvent, GuildChannel, GuildId, UserId,
+    },
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::now;
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        audit,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        permissions,
+        timestamps::TimestampStyle,
+        EventContext,
+    },
+    settings::keys,
+};
+
+fn describe_category(parent_id: Option<ChannelId>) -> String {
+    parent_id.map_or("None".to_string(), |id| format!("<#{id}>"))
+}
+
+fn describe_slowmode(seconds: Option<u16>) -> String {
+    match seconds.unwrap_or(0) {
+        0 => "Off".to_string(),
+        seconds => format!("{seconds}s"),
+    }
+}
+
+fn describe_topic(topic: &Option<String>) -> String {
+    match topic.as_deref() {
+        None | Some("") => "None".to_string(),
+        Some(topic) => topic.chars().take(1000).collect(),
+    }
+}
+
+fn describe_executor(user_id: Option<UserId>) -> String {
+    user_id.map_or("Unknown".to_string(), |user_id| format!("<@{user_id}>"))
+}
+
+/// Builds the entry for a created or deleted channel, attributed to whoever took `action` on it.
+async fn channel_entry(
+    ctx: &dyn EventContext,
+    data: &Data,
+    channel: &GuildChannel,
+    description: String,
+    action: ChannelAction,
+    verb: &str,
+) -> LogEntry {
+    let timestamps = data
+        .settings
+        .get(channel.guild_id, &keys::TIMESTAMP_STYLE)
+        .await;
+
+    let executor = audit::find_executor(
+        ctx,
+        &data.pool,
+        channel.guild_id,
+        channel.id.get(),
+        &[Action::Channel(action)],
+    )
+    .await;
+
+    let embed = CreateEmbed::new()
+        .description(description)
+        .field("Type", channel.kind.name(), true)
+        .field("Category", describe_category(channel.parent_id), true)
+        .field(format!("{verb} by"), describe_executor(executor), true)
+        .field("Timestamp", timestamps.format(now() as i64), true);
+
+    let mut entry = LogEntry::new(channel.guild_id, embed);
+
+    if let Some(user_id) = executor {
+        entry = entry.subject(user_id);
+    }
+
+    entry
+}
+
+pub struct ChannelCreate;
+
+#[async_trait]
+impl EventFormatter for ChannelCreate {
+    fn kind(&self) -> &'static str {
+        "channel_create"
+    }
+
+    fn title(&self) -> &'static str {
+        "Channel Created"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "channel_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ChannelCreate { channel } = event else {
+            return None;
+        };
+
+        Some(
+            channel_entry(
+                ctx,
+                data,
+                channel,
+                format!("<#{}> (**{}**) was created.", channel.id, channel.name),
+                ChannelAction::Create,
+                "Created",
+            )
+            .await,
+        )
+    }
+}
+
+pub struct ChannelDelete;
+
+#[async_trait]
+impl EventFormatter for ChannelDelete {
+    fn kind(&self) -> &'static str {
+        "channel_delete"
+    }
+
+    fn title(&self) -> &'static str {
+        "Channel Deleted"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn event(&self) -> &'static str {
+        "channel_delete"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ChannelDelete { channel, .. } = event else {
+            return None;
+        };
+
+        // the channel is gone, so only its name is useful - a mention would render as #deleted-channel.
+        Some(
+            channel_entry(
+                ctx,
+                data,
+                channel,
+                format!("**#{}** was deleted.", channel.name),
+                ChannelAction::Delete,
+                "Deleted",
+            )
+            .await,
+        )
+    }
+}
+
+pub struct ChannelUpdate;
+
+#[async_trait]
+impl EventFormatter for ChannelUpdate {
+    fn kind(&self) -> &'static str {
+        "channel_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Channel Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "channel_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ChannelUpdate {
+            old: Some(old),
+            new,
+        } = event
+        else {
+            return None;
+        };
+
+        let mut changes = Vec::new();
+
+        if old.name != new.name {
+            changes.push(("Name", format!("{} → {}", old.name, new.name)));
+        }
+
+        if old.topic != new.topic {
+            changes.push((
+                "Topic",
+                format!(
+                    "**Before:** {}\n**After:** {}",
+                    describe_topic(&old.topic),
+                    describe_topic(&new.topic)
+                ),
+            ));
+        }
+
+        if old.rate_limit_per_user.unwrap_or(0) != new.rate_limit_per_user.unwrap_or(0) {
+            changes.push((
+                "Slowmode",
+                format!(
+                    "{} → {}",
+                    describe_slowmode(old.rate_limit_per_user),
+                    describe_slowmode(new.rate_limit_per_user)
+                ),
+            ));
+        }
+
+        if old.parent_id != new.parent_id {
+            changes.push((
+                "Category",
+                format!(
+                    "{} → {}",
+                    describe_category(old.parent_id),
+                    describe_category(new.parent_id)
+                ),
+            ));
+        }
+
+        if old.nsfw != new.nsfw {
+            changes.push(("NSFW", format!("{} → {}", old.nsfw, new.nsfw)));
+        }
+
+        let overwrites = permissions::render_overwrite_diff(
+            &old.permission_overwrites,
+            &new.permission_overwrites,
+        );
+
+        // position changes (e.g. from dragging another channel around) are the bulk of updates and not worth a log.
+        if changes.is_empty() && overwrites.is_empty() {
+            return None;
+        }
+
+        // overwrite edits get audit entries of their own, separate from the channel's.
+        let mut actions = Vec::new();
+        if !changes.is_empty() {
+            actions.push(Action::Channel(ChannelAction::Update));
+        }
+        if !overwrites.is_empty() {
+            actions.extend([
+                Action::ChannelOverwrite(ChannelOverwriteAction::Update),
+                Action::ChannelOverwrite(ChannelOverwriteAction::Create),
+                Action::ChannelOverwrite(ChannelOverwriteAction::Delete),
+            ]);
+        }
+
+        let executor =
+            audit::find_executor(ctx, &data.pool, new.guild_id, new.id.get(), &actions).await;
+
+        let timestamps = data
+            .settings
+            .get(new.guild_id, &keys::TIMESTAMP_STYLE)
+            .await;
+
+        let mut embed = CreateEmbed::new()
+            .description(format!("<#{}> (**{}**) was updated.", new.id, new.name));
+
+        for (name, value) in changes {
+            embed = embed.field(name, value, false);
+        }
+
+        for (target, diff) in overwrites.into_iter().take(5) {
+            embed = embed.field("Permissions", format!("{target}\n{diff}"), false);
+        }
+
+        embed = embed
+            .field("Updated by", describe_executor(executor), true)
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        let mut entry = LogEntry::new(new.guild_id, embed);
+
+        if let Some(user_id) = executor {
+            entry = entry.subject(user_id);
+        }
+
+        Some(entry)
+    }
+}
+
+/// Drift is found by comparing a watched channel against its snapshot on a schedule rather than from an event,
+/// so this formatter never matches an event and is delivered through [`PermissionDrift::entry`] instead.
+pub struct PermissionDrift;
+
+impl PermissionDrift {
+    /// `diff` is the rendered overwrite diff between the snapshot taken at `taken_at` and the channel now.
+    pub fn entry(
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        taken_at: i64,
+        diff: Vec<(String, String)>,
+        timestamps: TimestampStyle,
+    ) -> LogEntry {
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "Permissions in <#{channel_id}> changed since {} without the bot seeing it happen, e.g. while it was offline.",
+                timestamps.format(taken_at)
+            ))
+            .fields(diff.into_iter().take(25).map(|(target, block)| (target, block, false)));
+
+        LogEntry::new(guild_id, embed)
+    }
+}
+
+#[async_trait]
+impl EventFormatter for PermissionDrift {
+    fn kind(&self) -> &'static str {
+        "permission_drift"
+    }
+
+    fn title(&self) -> &'static str {
+        "Permission Drift"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn event(&self) -> &'static str {
+        "permission_drift"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        _event: &FullEvent,
+        _data: &Data,
+    ) -> Option<LogEntry> {
+        None
+    }
+}
@@ -0,0 +1,144 @@
+use serenity::{
+    all::{Emoji, FullEvent},
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::{list_field, now, pluralize};
+use crate::{
+    client::Data,
+    commands::LogType,
+    diff::{asymmetric_diff_by, changed_by, AsymmetricDiff},
+    logging::{
+        formatter::{Category, EventFormatter, LogEntry},
+        EventContext,
+    },
+    settings::keys,
+};
+
+pub struct EmojiUpdate;
+
+#[async_trait]
+impl EventFormatter for EmojiUpdate {
+    fn kind(&self) -> &'static str {
+        "emoji_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Emojis Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_emojis_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildEmojisUpdate {
+            guild_id,
+            current_state,
+        } = event
+        else {
+            return None;
+        };
+
+        // without the previous set there's nothing to diff against; it's known from here on.
+        let previous = data.emojis.replace(*guild_id, current_state)?;
+
+        let previous = previous.into_values().collect::<Vec<_>>();
+        let current = current_state.values().cloned().collect::<Vec<_>>();
+
+        let AsymmetricDiff {
+            mut added,
+            mut removed,
+        } = asymmetric_diff_by(&previous, &current, |emoji| emoji.id);
+
+        // emojis can't be compared as a whole, so renames are found among their names alone.
+        let names = |emojis: &[Emoji]| {
+            emojis
+                .iter()
+                .map(|emoji| (emoji.id, emoji.name.clone()))
+                .collect::<Vec<_>>()
+        };
+        let mut renamed = changed_by(&names(&previous), &names(&current), |(id, _)| *id)
+            .into_iter()
+            .filter_map(|((_, old_name), (id, _))| Some((old_name, current_state.get(&id)?)))
+            .collect::<Vec<_>>();
+
+        if added.is_empty() && removed.is_empty() && renamed.is_empty() {
+            return None;
+        }
+
+        added.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        removed.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        renamed.sort_unstable_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+
+        let timestamps = data.settings.get(*guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let changed = added.len() + removed.len() + renamed.len();
+        let mut embed = CreateEmbed::new().description(format!(
+            "{changed} {} changed.",
+            pluralize("emoji was", "emojis were", changed)
+        ));
+
+        if let Some(preview) = added.first().or(renamed.first().map(|(_, emoji)| *emoji)) {
+            embed = embed.thumbnail(preview.url());
+        }
+
+        if !added.is_empty() {
+            embed = embed.field(
+                "Added",
+                list_field(
+                    added
+                        .iter()
+                        .map(|emoji| format!("{emoji} `:{}:`", emoji.name))
+                        .collect(),
+                ),
+                false,
+            );
+        }
+
+        if !removed.is_empty() {
+            // removed emojis can't be rendered anymore, but the CDN keeps their images around for a while.
+            embed = embed.field(
+                "Removed",
+                list_field(
+                    removed
+                        .iter()
+                        .map(|emoji| format!("[`:{}:`]({})", emoji.name, emoji.url()))
+                        .collect(),
+                ),
+                false,
+            );
+        }
+
+        if !renamed.is_empty() {
+            embed = embed.field(
+                "Renamed",
+                list_field(
+                    renamed
+                        .iter()
+                        .map(|(old_name, new)| format!("{new} `:{old_name}:` → `:{}:`", new.name))
+                        .collect(),
+                ),
+                false,
+            );
+        }
+
+        embed = embed.field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(*guild_id, embed))
+    }
+}
@@ -0,0 +1,91 @@
+use serenity::{all::FullEvent, async_trait};
+
+use super::base_embed;
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        filters,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        timestamps, verification, EventContext,
+    },
+    settings::keys,
+};
+
+/// Only members this new get their first message highlighted.
+const NEW_MEMBER_SECS: i64 = 7 * 24 * 60 * 60;
+
+pub struct FirstMessage;
+
+#[async_trait]
+impl EventFormatter for FirstMessage {
+    fn kind(&self) -> &'static str {
+        "first_message"
+    }
+
+    fn title(&self) -> &'static str {
+        "First Message"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "message"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::Message { new_message } = event else {
+            return None;
+        };
+
+        let guild_id = new_message.guild_id?;
+
+        if new_message.author.bot
+            || new_message.webhook_id.is_some()
+            || !filters::is_opted_in(data, guild_id, &keys::LOG_FIRST_MESSAGES).await
+        {
+            return None;
+        }
+
+        let since_join =
+            verification::record_first_message(&data.pool, guild_id, new_message.author.id)
+                .await
+                .ok()??;
+
+        if since_join > NEW_MEMBER_SECS {
+            return None;
+        }
+
+        let embed = base_embed(&new_message.author)
+            .description(format!(
+                "<@{}> ({}) sent their first message in <#{}>, {} after joining.\n[Jump to message]({})",
+                new_message.author.id,
+                new_message.author.name,
+                new_message.channel_id,
+                timestamps::describe_duration(since_join),
+                new_message.link()
+            ));
+
+        Some(
+            LogEntry::new(guild_id, embed)
+                .content("Content", new_message.content.clone())
+                .subject(new_message.author.id)
+                .message(new_message.id),
+        )
+    }
+}
@@ -0,0 +1,439 @@
+use serenity::{
+    all::{audit_log::Action, Change, ChannelId, FullEvent, GuildId, MfaLevel, VerificationLevel},
+    async_trait,
+    builder::{CreateEmbed, CreateMessage},
+};
+
+use super::now;
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        anomalies::Anomaly,
+        audit,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        timestamps::TimestampStyle,
+        EventContext,
+    },
+    settings::keys,
+};
+
+fn describe_channel(channel_id: Option<ChannelId>) -> String {
+    channel_id.map_or("None".to_string(), |id| format!("<#{id}>"))
+}
+
+fn describe_verification(level: VerificationLevel) -> String {
+    match level {
+        VerificationLevel::None => "None".to_string(),
+        VerificationLevel::Low => "Low".to_string(),
+        VerificationLevel::Medium => "Medium".to_string(),
+        VerificationLevel::High => "High".to_string(),
+        VerificationLevel::Higher => "Highest".to_string(),
+        level => format!("Unknown ({})", u8::from(level)),
+    }
+}
+
+fn describe_mfa(level: Option<MfaLevel>) -> String {
+    match level {
+        Some(MfaLevel::None) => "Off".to_string(),
+        Some(MfaLevel::Elevated) => "On".to_string(),
+        Some(level) => format!("Unknown ({})", u8::from(level)),
+        None => "Unknown".to_string(),
+    }
+}
+
+fn describe_vanity(code: &Option<String>) -> String {
+    code.as_ref()
+        .map_or("None".to_string(), |code| format!("discord.gg/{code}"))
+}
+
+/// Re-uploads the previous and new version of a server image, e.g. its icon, linking any that can't be downloaded.
+async fn image_followup(
+    ctx: &dyn EventContext,
+    name: &str,
+    old: Option<String>,
+    new: Option<String>,
+) -> CreateMessage {
+    let mut message = CreateMessage::new();
+    let mut lines = vec![format!("{name} changed:")];
+
+    for (label, url) in [("before", old), ("after", new)] {
+        let Some(url) = url else {
+            lines.push(format!("**{label}:** None"));
+            continue;
+        };
+
+        match ctx.download_attachment(&url).await {
+            Ok(mut file) => {
+                let extension = file
+                    .filename
+                    .rsplit('.')
+                    .next()
+                    .unwrap_or("png")
+                    .to_string();
+                file.filename = format!("{}_{label}.{extension}", name.to_lowercase());
+
+                lines.push(format!("**{label}:** `{}`", file.filename));
+                message = message.add_file(file);
+            }
+            Err(_) => lines.push(format!("**{label}:** [link](<{url}>)")),
+        }
+    }
+
+    message.content(lines.join("\n"))
+}
+
+pub struct GuildUpdate;
+
+#[async_trait]
+impl EventFormatter for GuildUpdate {
+    fn kind(&self) -> &'static str {
+        "guild_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Server Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildUpdate {
+            old_data_if_available: Some(old),
+            new_data: new,
+        } = event
+        else {
+            return None;
+        };
+
+        let mut changes = Vec::new();
+        let mut followups = Vec::new();
+
+        if old.name != new.name {
+            changes.push(("Name", format!("{} → {}", old.name, new.name)));
+        }
+
+        if old.icon != new.icon {
+            changes.push(("Icon", "Changed, see below.".to_string()));
+            followups.push(image_followup(ctx, "Icon", old.icon_url(), new.icon_url()).await);
+        }
+
+        if old.banner != new.banner {
+            changes.push(("Banner", "Changed, see below.".to_string()));
+            followups.push(image_followup(ctx, "Banner", old.banner_url(), new.banner_url()).await);
+        }
+
+        if old.verification_level != new.verification_level {
+            changes.push((
+                "Verification Level",
+                format!(
+                    "{} → {}",
+                    describe_verification(old.verification_level),
+                    describe_verification(new.verification_level)
+                ),
+            ));
+        }
+
+        if old.system_channel_id != new.system_channel_id {
+            changes.push((
+                "System Channel",
+                format!(
+                    "{} → {}",
+                    describe_channel(old.system_channel_id),
+                    describe_channel(new.system_channel_id)
+                ),
+            ));
+        }
+
+        let old_afk = old.afk_metadata.as_ref();
+        let new_afk = new.afk_metadata.as_ref();
+
+        if old_afk.map(|afk| afk.afk_channel_id) != new_afk.map(|afk| afk.afk_channel_id) {
+            changes.push((
+                "AFK Channel",
+                format!(
+                    "{} → {}",
+                    describe_channel(old_afk.map(|afk| afk.afk_channel_id)),
+                    describe_channel(new_afk.map(|afk| afk.afk_channel_id))
+                ),
+            ));
+        }
+
+        // the timeout is only sent alongside an AFK channel, so it can't be compared without one on both sides.
+        if let (Some(old_afk), Some(new_afk)) = (old_afk, new_afk)
+            && old_afk.afk_timeout != new_afk.afk_timeout
+        {
+            changes.push((
+                "AFK Timeout",
+                format!(
+                    "{}m → {}m",
+                    u16::from(old_afk.afk_timeout) / 60,
+                    u16::from(new_afk.afk_timeout) / 60
+                ),
+            ));
+        }
+
+        // most updates are to things we don't log, like boost counts or feature flags.
+        // vanity URL changes are left to their own alert.
+        if changes.is_empty() {
+            return None;
+        }
+
+        let timestamps = data.settings.get(new.id, &keys::TIMESTAMP_STYLE).await;
+
+        let mut embed = CreateEmbed::new().description(format!("**{}** was updated.", new.name));
+
+        for (name, value) in changes {
+            embed = embed.field(name, value, false);
+        }
+
+        embed = embed.field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(new.id, embed).followups(followups))
+    }
+}
+
+/// Vanity URLs are a common target when an admin account is compromised, e.g. to point the server's link elsewhere,
+/// so changes to them are alerted on rather than folded into [`GuildUpdate`].
+pub struct VanityUrlChange;
+
+#[async_trait]
+impl EventFormatter for VanityUrlChange {
+    fn kind(&self) -> &'static str {
+        "vanity_url_change"
+    }
+
+    fn title(&self) -> &'static str {
+        "Vanity URL Changed"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Critical
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildUpdate {
+            old_data_if_available: Some(old),
+            new_data: new,
+        } = event
+        else {
+            return None;
+        };
+
+        if old.vanity_url_code == new.vanity_url_code {
+            return None;
+        }
+
+        let changed_by =
+            audit::find_target_action(ctx, &data.pool, new.id, new.id.get(), Action::GuildUpdate)
+                .await
+                .filter(|entry| {
+                    entry
+                        .changes
+                        .iter()
+                        .flatten()
+                        .any(|change| matches!(change, Change::VanityUrlCode { .. }))
+                })
+                .map(|entry| entry.user_id);
+
+        let timestamps = data.settings.get(new.id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "The vanity URL of **{}** was changed. If this wasn't planned, check who has the Manage Server permission.",
+                new.name
+            ))
+            .field("Previous", describe_vanity(&old.vanity_url_code), true)
+            .field("New", describe_vanity(&new.vanity_url_code), true)
+            .field(
+                "Changed by",
+                changed_by.map_or("Unknown".to_string(), |user_id| format!("<@{user_id}>")),
+                false,
+            )
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        let mut entry = LogEntry::new(new.id, embed);
+
+        if let Some(user_id) = changed_by {
+            entry = entry.subject(user_id);
+        }
+
+        if let Some(role) = data.settings.get(new.id, &keys::VANITY_PING_ROLE).await {
+            entry = entry.mention_role(role);
+        }
+
+        Some(entry)
+    }
+}
+
+/// Whether moderators need 2FA for moderation actions. Turning it off makes a stolen moderator password
+/// enough to act, so that's alerted on. Changes are picked up from audit log entries as they're created.
+pub struct MfaLevelChange;
+
+#[async_trait]
+impl EventFormatter for MfaLevelChange {
+    fn kind(&self) -> &'static str {
+        "mfa_level_change"
+    }
+
+    fn title(&self) -> &'static str {
+        "2FA Requirement Changed"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_audit_log_entry_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildAuditLogEntryCreate { entry, guild_id } = event else {
+            return None;
+        };
+
+        let (old, new) = entry
+            .changes
+            .iter()
+            .flatten()
+            .find_map(|change| match change {
+                Change::MfaLevel { old, new } => Some((*old, *new)),
+                _ => None,
+            })?;
+
+        let timestamps = data.settings.get(*guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = CreateEmbed::new()
+            .description("The 2FA requirement for moderation actions was changed.")
+            .field("Previous", describe_mfa(old), true)
+            .field("New", describe_mfa(new), true)
+            .field("Changed by", format!("<@{}>", entry.user_id), false)
+            .field(
+                "Timestamp",
+                timestamps.format(entry.id.created_at().unix_timestamp()),
+                true,
+            );
+
+        let mut log_entry = LogEntry::new(*guild_id, embed).subject(entry.user_id);
+
+        if new == Some(MfaLevel::None) {
+            log_entry = log_entry.severity(Severity::Warning);
+        }
+
+        Some(log_entry)
+    }
+}
+
+/// Spikes are found by comparing hourly log counts on a schedule rather than from an event,
+/// so this formatter never matches an event and is delivered through [`UnusualActivity::entry`] instead.
+pub struct UnusualActivity;
+
+impl UnusualActivity {
+    /// `start` is when the checked hour began.
+    pub fn entry(
+        guild_id: GuildId,
+        start: i64,
+        anomalies: Vec<Anomaly>,
+        timestamps: TimestampStyle,
+    ) -> LogEntry {
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "Some kinds of log spiked in the hour starting {}, compared to the past week.",
+                timestamps.format(start)
+            ))
+            .fields(anomalies.into_iter().take(25).map(|anomaly| {
+                (
+                    anomaly.kind,
+                    format!(
+                        "{} in the hour, usually {:.1}",
+                        anomaly.count, anomaly.baseline
+                    ),
+                    true,
+                )
+            }));
+
+        LogEntry::new(guild_id, embed)
+    }
+}
+
+#[async_trait]
+impl EventFormatter for UnusualActivity {
+    fn kind(&self) -> &'static str {
+        "unusual_activity"
+    }
+
+    fn title(&self) -> &'static str {
+        "Unusual Activity"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn event(&self) -> &'static str {
+        "unusual_activity"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        _event: &FullEvent,
+        _data: &Data,
+    ) -> Option<LogEntry> {
+        None
+    }
+}
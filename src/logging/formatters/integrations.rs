@@ -0,0 +1,375 @@
+use serenity::{
+    all::{
+        audit_log::{Action, IntegrationAction, MemberAction},
+        FullEvent, GuildId, Integration, Role, Scope,
+    },
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::{base_embed, now};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        audit,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        permissions, EventContext,
+    },
+    settings::keys,
+};
+
+fn describe_scopes(scopes: &[Scope]) -> String {
+    if scopes.is_empty() {
+        return "None".to_string();
+    }
+
+    scopes
+        .iter()
+        .map(|scope| match serde_json::to_value(scope) {
+            Ok(serde_json::Value::String(name)) => format!("`{name}`"),
+            _ => format!("`{scope:?}`"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The permissions granted through `roles`, e.g. a bot's managed role.
+fn describe_permissions(roles: &[Role]) -> Option<String> {
+    let permissions = roles
+        .iter()
+        .fold(Default::default(), |acc, role| acc | role.permissions);
+
+    permissions::render_permission_diff(Default::default(), permissions)
+        .filter(|permissions| permissions.len() <= 1024)
+}
+
+/// Bots with moderation permissions can do everything a moderator can, so they're flagged like role grants.
+fn grant_severity(roles: &[Role], default: Severity) -> Severity {
+    permissions::grant_context(roles).map_or(default, |grant| grant.severity)
+}
+
+async fn integration_embed(
+    ctx: &dyn EventContext,
+    data: &Data,
+    guild_id: GuildId,
+    integration: &Integration,
+    description: String,
+) -> (CreateEmbed, Vec<Role>) {
+    let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+    let mut embed = match &integration.user {
+        Some(user) => base_embed(user),
+        None => CreateEmbed::new(),
+    }
+    .description(description)
+    .field("Type", &integration.kind, true);
+
+    if let Some(user) = &integration.user {
+        embed = embed.field("Authorized by", format!("<@{}>", user.id), true);
+    }
+
+    if let Some(bot) = integration
+        .application
+        .as_ref()
+        .and_then(|application| application.bot.as_ref())
+    {
+        embed = embed.field("Bot", format!("<@{}> ({})", bot.id, bot.name), true);
+    }
+
+    if let Some(scopes) = &integration.scopes {
+        embed = embed.field("Scopes", describe_scopes(scopes), false);
+    }
+
+    let roles = integration
+        .role_id
+        .and_then(|role_id| ctx.cached_role(guild_id, role_id))
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    if let Some(permissions) = describe_permissions(&roles) {
+        embed = embed.field("Permissions", permissions, false);
+    }
+
+    let embed = embed.field("Timestamp", timestamps.format(now() as i64), true);
+
+    (embed, roles)
+}
+
+pub struct IntegrationCreate;
+
+#[async_trait]
+impl EventFormatter for IntegrationCreate {
+    fn kind(&self) -> &'static str {
+        "integration_create"
+    }
+
+    fn title(&self) -> &'static str {
+        "Integration Added"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "integration_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::IntegrationCreate { integration } = event else {
+            return None;
+        };
+
+        let guild_id = integration.guild_id?;
+
+        let (embed, roles) = integration_embed(
+            ctx,
+            data,
+            guild_id,
+            integration,
+            format!("The integration **{}** was added.", integration.name),
+        )
+        .await;
+
+        Some(LogEntry::new(guild_id, embed).severity(grant_severity(&roles, Severity::Notice)))
+    }
+}
+
+pub struct IntegrationUpdate;
+
+#[async_trait]
+impl EventFormatter for IntegrationUpdate {
+    fn kind(&self) -> &'static str {
+        "integration_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Integration Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "integration_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::IntegrationUpdate { integration } = event else {
+            return None;
+        };
+
+        let guild_id = integration.guild_id?;
+
+        // Discord doesn't send the previous state, so this lists the integration as it is now.
+        let (embed, roles) = integration_embed(
+            ctx,
+            data,
+            guild_id,
+            integration,
+            format!(
+                "The integration **{}** was updated. It is now {}.",
+                integration.name,
+                if integration.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            ),
+        )
+        .await;
+
+        Some(LogEntry::new(guild_id, embed).severity(grant_severity(&roles, Severity::Info)))
+    }
+}
+
+pub struct IntegrationDelete;
+
+#[async_trait]
+impl EventFormatter for IntegrationDelete {
+    fn kind(&self) -> &'static str {
+        "integration_delete"
+    }
+
+    fn title(&self) -> &'static str {
+        "Integration Removed"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn event(&self) -> &'static str {
+        "integration_delete"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::IntegrationDelete {
+            integration_id,
+            guild_id,
+            application_id,
+        } = event
+        else {
+            return None;
+        };
+
+        let timestamps = data.settings.get(*guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let entry = audit::find_target_action(
+            ctx,
+            &data.pool,
+            *guild_id,
+            integration_id.get(),
+            Action::Integration(IntegrationAction::Delete),
+        )
+        .await;
+
+        let mut embed = CreateEmbed::new()
+            .description(format!("The integration `{integration_id}` was removed."))
+            .field(
+                "Removed by",
+                entry.map_or("Unknown".to_string(), |entry| {
+                    format!("<@{}>", entry.user_id)
+                }),
+                true,
+            );
+
+        if let Some(application_id) = application_id {
+            embed = embed.field("Application", format!("`{application_id}`"), true);
+        }
+
+        let embed = embed.field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(*guild_id, embed))
+    }
+}
+
+/// A bot joining the server, logged apart from regular joins since it means an admin authorized an application.
+pub struct BotAdd;
+
+#[async_trait]
+impl EventFormatter for BotAdd {
+    fn kind(&self) -> &'static str {
+        "bot_add"
+    }
+
+    fn title(&self) -> &'static str {
+        "Bot Added"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_addition"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberAddition { new_member: member } = event else {
+            return None;
+        };
+
+        if !member.user.bot {
+            return None;
+        }
+
+        let guild_id = member.guild_id;
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let entry = audit::find_member_action(
+            ctx,
+            &data.pool,
+            guild_id,
+            member.user.id,
+            Action::Member(MemberAction::BotAdd),
+        )
+        .await;
+
+        let mut embed = base_embed(&member.user)
+            .description(format!(
+                "The bot <@{}> ({}) was added.",
+                member.user.id, member.user.name
+            ))
+            .field(
+                "Authorized by",
+                entry.map_or("Unknown".to_string(), |entry| {
+                    format!("<@{}>", entry.user_id)
+                }),
+                true,
+            );
+
+        // the bot's managed role carries the permissions it was invited with.
+        let roles = member
+            .roles
+            .iter()
+            .filter_map(|role_id| ctx.cached_role(guild_id, *role_id))
+            .collect::<Vec<_>>();
+
+        if let Some(permissions) = describe_permissions(&roles) {
+            embed = embed.field("Permissions", permissions, false);
+        }
+
+        let embed = embed.field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(
+            LogEntry::new(guild_id, embed)
+                .subject(member.user.id)
+                .severity(grant_severity(&roles, Severity::Notice)),
+        )
+    }
+}
@@ -0,0 +1,153 @@
+use serenity::{all::FullEvent, async_trait, builder::CreateEmbed};
+
+use super::{base_embed, now};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        formatter::{Category, EventFormatter, LogEntry},
+        timestamps, EventContext,
+    },
+    settings::keys,
+};
+
+pub struct InviteCreate;
+
+#[async_trait]
+impl EventFormatter for InviteCreate {
+    fn kind(&self) -> &'static str {
+        "invite_create"
+    }
+
+    fn title(&self) -> &'static str {
+        "Invite Created"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "invite_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::InviteCreate { data: invite } = event else {
+            return None;
+        };
+
+        let guild_id = invite.guild_id?;
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = invite
+            .inviter
+            .as_ref()
+            .map_or_else(CreateEmbed::new, base_embed)
+            .description(format!(
+                "Invite `{}` was created for <#{}>.",
+                invite.code, invite.channel_id
+            ))
+            .field(
+                "Creator",
+                invite
+                    .inviter
+                    .as_ref()
+                    .map_or("Unknown".to_string(), |inviter| {
+                        format!("<@{}>", inviter.id)
+                    }),
+                true,
+            )
+            .field(
+                "Max Uses",
+                match invite.max_uses {
+                    0 => "Unlimited".to_string(),
+                    uses => uses.to_string(),
+                },
+                true,
+            )
+            .field(
+                "Expires",
+                match invite.max_age {
+                    0 => "Never".to_string(),
+                    max_age => format!(
+                        "{}, after {}",
+                        timestamps.format(invite.created_at.unix_timestamp() + i64::from(max_age)),
+                        timestamps::describe_duration(i64::from(max_age))
+                    ),
+                },
+                true,
+            )
+            .field(
+                "Temporary Membership",
+                if invite.temporary { "Yes" } else { "No" },
+                true,
+            )
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        let mut entry = LogEntry::new(guild_id, embed);
+
+        if let Some(inviter) = &invite.inviter {
+            entry = entry.subject(inviter.id);
+        }
+
+        Some(entry)
+    }
+}
+
+pub struct InviteDelete;
+
+#[async_trait]
+impl EventFormatter for InviteDelete {
+    fn kind(&self) -> &'static str {
+        "invite_delete"
+    }
+
+    fn title(&self) -> &'static str {
+        "Invite Deleted"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn event(&self) -> &'static str {
+        "invite_delete"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::InviteDelete { data: invite } = event else {
+            return None;
+        };
+
+        let guild_id = invite.guild_id?;
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        // Discord also sends this when an invite expires or runs out of uses, and doesn't say which it was.
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "Invite `{}` for <#{}> was deleted or expired.",
+                invite.code, invite.channel_id
+            ))
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(guild_id, embed))
+    }
+}
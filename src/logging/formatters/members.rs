@@ -0,0 +1,842 @@
+use serenity::{
+    all::{
+        audit_log::{Action, MemberAction},
+        AuditLogEntry, FullEvent, GuildId, Member, RoleId, Timestamp, UserId,
+    },
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::{base_embed, now, pluralize};
+use crate::{
+    cases,
+    client::Data,
+    commands::LogType,
+    diff::asymmetric_diff,
+    logging::{
+        audit,
+        damping::Summary,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        incidents, permissions, timestamps, EventContext,
+    },
+    settings::keys,
+};
+
+/// How far back moderation history is considered relevant to a member leaving.
+const LEAVE_CONTEXT_SECS: i64 = 30 * 60;
+
+/// Recent moderation involving a leaving member: an active timeout, a recent case or a recent alert about them.
+async fn leave_context(
+    ctx: &dyn EventContext,
+    data: &Data,
+    guild_id: GuildId,
+    user_id: UserId,
+    member: &Member,
+) -> Vec<String> {
+    let now = now() as i64;
+    let since = now - LEAVE_CONTEXT_SECS;
+    let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+    let mut context = Vec::new();
+
+    if let Some(until) = member.communication_disabled_until
+        && until.unix_timestamp() > now
+    {
+        context.push(
+            match audit::find_timeout(ctx, &data.pool, guild_id, user_id).await {
+                Some(entry) => format!(
+                    "Left {} after being timed out by <@{}>, until {}.",
+                    timestamps::describe_duration(now - entry.id.created_at().unix_timestamp()),
+                    entry.user_id,
+                    timestamps.format(until.unix_timestamp())
+                ),
+                None => format!(
+                    "Left while timed out, until {}.",
+                    timestamps.format(until.unix_timestamp())
+                ),
+            },
+        );
+    }
+
+    if let Ok(Some(case)) =
+        cases::latest_for_target(&data.pool, guild_id, &user_id.to_string(), since).await
+    {
+        context.push(format!(
+            "Left {} after case #{} ({}) by <@{}>.",
+            timestamps::describe_duration(now - case.created_at),
+            case.case_number,
+            case.action,
+            case.moderator
+        ));
+    }
+
+    if let Ok(Some(incident)) =
+        incidents::latest_for_subject(&data.pool, guild_id, user_id, since).await
+    {
+        let link = incident
+            .first_message
+            .map(|(channel_id, message_id)| {
+                format!(
+                    " ([alert]({}))",
+                    message_id.link(channel_id, Some(guild_id))
+                )
+            })
+            .unwrap_or_default();
+
+        context.push(format!(
+            "Left {} after incident #{} was opened{link}.",
+            timestamps::describe_duration(now - incident.opened_at),
+            incident.id
+        ));
+    }
+
+    context
+}
+
+pub struct MemberJoin;
+
+#[async_trait]
+impl EventFormatter for MemberJoin {
+    fn kind(&self) -> &'static str {
+        "member_join"
+    }
+
+    fn title(&self) -> &'static str {
+        "Member Joined"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_addition"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberAddition { new_member: member } = event else {
+            return None;
+        };
+
+        // bots joining are logged as bot additions instead.
+        if member.user.bot {
+            return None;
+        }
+
+        let timestamps = data
+            .settings
+            .get(member.guild_id, &keys::TIMESTAMP_STYLE)
+            .await;
+
+        let mut embed = base_embed(&member.user)
+            .description(format!(
+                "<@{}> ({}) joined.",
+                member.user.id, member.user.name
+            ))
+            .field(
+                "Joined At",
+                timestamps.format(member.joined_at?.timestamp()),
+                true,
+            )
+            .field(
+                "Created At",
+                timestamps.format(member.user.created_at().timestamp()),
+                true,
+            );
+
+        // passing screening is logged separately, as member_verified.
+        if member.pending {
+            embed = embed.field("Screening", "Pending", true);
+        }
+
+        Some(LogEntry::new(member.guild_id, embed).subject(member.user.id))
+    }
+}
+
+async fn find_kick(
+    ctx: &dyn EventContext,
+    data: &Data,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Option<AuditLogEntry> {
+    audit::find_member_action(
+        ctx,
+        &data.pool,
+        guild_id,
+        user_id,
+        Action::Member(MemberAction::Kick),
+    )
+    .await
+}
+
+pub struct MemberLeave;
+
+#[async_trait]
+impl EventFormatter for MemberLeave {
+    fn kind(&self) -> &'static str {
+        "member_leave"
+    }
+
+    fn title(&self) -> &'static str {
+        "Member Left"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_removal"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberRemoval {
+            guild_id,
+            user,
+            member_data_if_available,
+        } = event
+        else {
+            return None;
+        };
+
+        // TODO: shit's fucked. Members are not gonna be cached. We may be able to fetch guilds on startup?
+        let member = member_data_if_available.as_ref()?;
+
+        // kicks are logged as such instead.
+        if find_kick(ctx, data, *guild_id, user.id).await.is_some() {
+            return None;
+        }
+
+        let timestamps = data.settings.get(*guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let context = leave_context(ctx, data, *guild_id, user.id, member).await;
+
+        let mut embed = base_embed(user)
+            .description(format!("<@{}> ({}) left.", user.id, user.name))
+            .field(
+                "Joined At",
+                timestamps.format(member.joined_at?.timestamp()),
+                true,
+            )
+            .field(
+                "Created At",
+                timestamps.format(user.created_at().timestamp()),
+                true,
+            )
+            .field("Left At", timestamps.format(now() as i64), true);
+
+        if !context.is_empty() {
+            embed = embed.field("Recent Context", context.join("\n"), false);
+        }
+
+        Some(LogEntry::new(*guild_id, embed).subject(user.id))
+    }
+}
+
+pub struct MemberKick;
+
+#[async_trait]
+impl EventFormatter for MemberKick {
+    fn kind(&self) -> &'static str {
+        "member_kick"
+    }
+
+    fn title(&self) -> &'static str {
+        "Member Kicked"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_removal"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Moderation
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberRemoval { guild_id, user, .. } = event else {
+            return None;
+        };
+
+        let entry = find_kick(ctx, data, *guild_id, user.id).await?;
+        let moderator = entry.user_id;
+
+        let case_number = cases::open(
+            &data.pool,
+            *guild_id,
+            "kick",
+            moderator,
+            Some(user.id.to_string()),
+            entry.reason.clone(),
+        )
+        .await
+        .ok();
+
+        let timestamps = data.settings.get(*guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let mut embed = base_embed(user)
+            .description(format!(
+                "<@{}> ({}) was kicked by <@{moderator}>.",
+                user.id, user.name
+            ))
+            .field("Moderator", format!("<@{moderator}>"), true);
+
+        if let Some(case_number) = case_number {
+            embed = embed.field("Case", format!("#{case_number}"), true);
+        }
+
+        embed = embed
+            .field("Kicked At", timestamps.format(now() as i64), true)
+            .field(
+                "Reason",
+                entry.reason.unwrap_or("No reason given".to_string()),
+                false,
+            );
+
+        let mut entry = LogEntry::new(*guild_id, embed)
+            .subject(user.id)
+            .mod_action(moderator, "kick", 1);
+
+        if let Some(case_number) = case_number {
+            entry = entry.case(case_number);
+        }
+
+        Some(entry)
+    }
+}
+
+/// A prune removing inactive members. There's no gateway event for these beyond the individual removals,
+/// so they're picked up from audit log entries as they're created.
+pub struct MemberPrune;
+
+#[async_trait]
+impl EventFormatter for MemberPrune {
+    fn kind(&self) -> &'static str {
+        "member_prune"
+    }
+
+    fn title(&self) -> &'static str {
+        "Members Pruned"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_audit_log_entry_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Moderation
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildAuditLogEntryCreate { entry, guild_id } = event else {
+            return None;
+        };
+
+        if !matches!(entry.action, Action::Member(MemberAction::Prune)) {
+            return None;
+        }
+
+        let options = entry.options.as_ref();
+        let removed = options.and_then(|options| options.members_removed);
+        let days = options.and_then(|options| options.delete_member_days);
+
+        let timestamps = data.settings.get(*guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let removed = removed.map_or("members".to_string(), |removed| {
+            format!(
+                "{removed} {}",
+                pluralize("member", "members", removed as usize)
+            )
+        });
+        let inactivity = days.map_or(String::new(), |days| {
+            format!(
+                " inactive for {days} {}",
+                pluralize("day", "days", days as usize)
+            )
+        });
+
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "<@{}> pruned {removed}{inactivity}.",
+                entry.user_id
+            ))
+            .field("Moderator", format!("<@{}>", entry.user_id), true)
+            .field(
+                "Pruned At",
+                timestamps.format(entry.id.created_at().unix_timestamp()),
+                true,
+            )
+            .field(
+                "Reason",
+                entry
+                    .reason
+                    .clone()
+                    .unwrap_or("No reason given".to_string()),
+                false,
+            );
+
+        Some(LogEntry::new(*guild_id, embed).subject(entry.user_id))
+    }
+}
+
+pub struct MemberRoles;
+
+#[async_trait]
+impl EventFormatter for MemberRoles {
+    fn kind(&self) -> &'static str {
+        "member_roles"
+    }
+
+    fn title(&self) -> &'static str {
+        "Member Roles Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberUpdate {
+            old_if_available: Some(old),
+            new: Some(member),
+            ..
+        } = event
+        else {
+            return None;
+        };
+
+        let guild_id = member.guild_id;
+        let mut difference = asymmetric_diff(&old.roles, &member.roles);
+
+        // roles handed out or taken away en masse are left to the bulk role change summary.
+        difference
+            .added
+            .retain(|role_id| !data.bulk_roles.is_bulk(guild_id, *role_id, true));
+        difference
+            .removed
+            .retain(|role_id| !data.bulk_roles.is_bulk(guild_id, *role_id, false));
+
+        if difference.added.is_empty() && difference.removed.is_empty() {
+            return None;
+        }
+
+        let mention_all = |roles: &[RoleId]| {
+            roles
+                .iter()
+                .map(|role_id| format!("<@&{role_id}>"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let mut embed = base_embed(&member.user).description(format!(
+            "<@{}> ({}) had their roles updated.",
+            member.user.id, member.user.name
+        ));
+
+        if !difference.added.is_empty() {
+            embed = embed.field(
+                pluralize("Role Added", "Roles Added", difference.added.len()),
+                mention_all(&difference.added),
+                false,
+            );
+        }
+
+        if !difference.removed.is_empty() {
+            embed = embed.field(
+                pluralize("Role Removed", "Roles Removed", difference.removed.len()),
+                mention_all(&difference.removed),
+                false,
+            );
+        }
+
+        let added_roles = difference
+            .added
+            .iter()
+            .filter_map(|role_id| ctx.cached_role(guild_id, *role_id))
+            .collect::<Vec<_>>();
+
+        let grant = permissions::grant_context(&added_roles);
+
+        if let Some(grant) = &grant {
+            embed = embed.field("Permissions", grant.describe(), false);
+        }
+
+        let entry = LogEntry::new(guild_id, embed).subject(member.user.id);
+
+        Some(match grant {
+            Some(grant) => entry.severity(grant.severity),
+            None => entry,
+        })
+    }
+}
+
+pub struct MemberNickname;
+
+#[async_trait]
+impl EventFormatter for MemberNickname {
+    fn kind(&self) -> &'static str {
+        "member_nickname"
+    }
+
+    fn title(&self) -> &'static str {
+        "Nickname Changed"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        _data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberUpdate {
+            old_if_available: Some(old),
+            new: Some(member),
+            ..
+        } = event
+        else {
+            return None;
+        };
+
+        if old.nick == member.nick {
+            return None;
+        }
+
+        let description = match (&old.nick, &member.nick) {
+            (_, None) => format!(
+                "<@{}> ({}) no longer has a nickname.",
+                member.user.id, member.user.name
+            ),
+            (None, Some(_)) => format!(
+                "<@{}> ({}) was given a nickname.",
+                member.user.id, member.user.name
+            ),
+            (Some(_), Some(_)) => format!(
+                "<@{}> ({}) had their nickname changed.",
+                member.user.id, member.user.name
+            ),
+        };
+
+        let embed = base_embed(&member.user)
+            .description(description)
+            .field("Previous", old.nick.as_deref().unwrap_or("None"), true)
+            .field("New", member.nick.as_deref().unwrap_or("None"), true);
+
+        Some(LogEntry::new(member.guild_id, embed).subject(member.user.id))
+    }
+}
+
+pub struct MemberTimeout;
+
+#[async_trait]
+impl EventFormatter for MemberTimeout {
+    fn kind(&self) -> &'static str {
+        "member_timeout"
+    }
+
+    fn title(&self) -> &'static str {
+        "Member Timeout"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Moderation
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberUpdate {
+            old_if_available: Some(old),
+            new: Some(member),
+            ..
+        } = event
+        else {
+            return None;
+        };
+
+        let now = now() as i64;
+        // Discord doesn't clear expired timeouts, so anything in the past counts as no timeout.
+        let active = |until: Option<Timestamp>| {
+            until
+                .map(|until| until.unix_timestamp())
+                .filter(|until| *until > now)
+        };
+
+        let (old_until, new_until) = (
+            active(old.communication_disabled_until),
+            active(member.communication_disabled_until),
+        );
+
+        if old_until == new_until {
+            return None;
+        }
+
+        let guild_id = member.guild_id;
+        let user = &member.user;
+
+        let (description, entry) = match new_until {
+            Some(until) => (
+                format!(
+                    "<@{}> ({}) was timed out until <t:{until}:f>, <t:{until}:R>.",
+                    user.id, user.name
+                ),
+                audit::find_timeout(ctx, &data.pool, guild_id, user.id).await,
+            ),
+            None => (
+                format!(
+                    "<@{}> ({}) had their timeout removed early.",
+                    user.id, user.name
+                ),
+                audit::find_timeout_removal(ctx, &data.pool, guild_id, user.id).await,
+            ),
+        };
+
+        // removing a timeout early isn't an action of its own, so only timeouts being applied count or open a case.
+        let moderator = entry
+            .as_ref()
+            .filter(|_| new_until.is_some())
+            .map(|entry| entry.user_id);
+
+        let case_number = match (&entry, new_until) {
+            (Some(entry), Some(_)) => cases::open(
+                &data.pool,
+                guild_id,
+                "timeout",
+                entry.user_id,
+                Some(user.id.to_string()),
+                entry.reason.clone(),
+            )
+            .await
+            .ok(),
+            _ => None,
+        };
+
+        let mut embed = base_embed(user).description(description).field(
+            "Moderator",
+            entry.as_ref().map_or("Unknown".to_string(), |entry| {
+                format!("<@{}>", entry.user_id)
+            }),
+            true,
+        );
+
+        if let Some(case_number) = case_number {
+            embed = embed.field("Case", format!("#{case_number}"), true);
+        }
+
+        if new_until.is_some() {
+            embed = embed.field(
+                "Reason",
+                entry
+                    .and_then(|entry| entry.reason)
+                    .unwrap_or("No reason given".to_string()),
+                false,
+            );
+        }
+
+        let mut entry = LogEntry::new(guild_id, embed).subject(user.id);
+
+        if let Some(moderator) = moderator {
+            entry = entry.mod_action(moderator, "timeout", 1);
+        }
+
+        if let Some(case_number) = case_number {
+            entry = entry.case(case_number);
+        }
+
+        Some(entry)
+    }
+}
+
+/// Summarises the logs damping held back from a noisy user. It's posted on the route the collapsed logs
+/// would have gone to, so unlike other formatters its route isn't fixed, and it never matches an event.
+pub struct LogsCollapsed(pub LogType);
+
+impl LogsCollapsed {
+    pub fn entry(summary: &Summary) -> LogEntry {
+        let embed = CreateEmbed::new().description(format!(
+            "<@{}> triggered {} more **{}** logs. Further logs from them are being collapsed until things calm down.",
+            summary.user_id, summary.count, summary.title
+        ));
+
+        LogEntry::new(summary.guild_id, embed).subject(summary.user_id)
+    }
+}
+
+#[async_trait]
+impl EventFormatter for LogsCollapsed {
+    fn kind(&self) -> &'static str {
+        "logs_collapsed"
+    }
+
+    fn title(&self) -> &'static str {
+        "Logs Collapsed"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn event(&self) -> &'static str {
+        "logs_collapsed"
+    }
+
+    fn default_route(&self) -> LogType {
+        self.0
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        _event: &FullEvent,
+        _data: &Data,
+    ) -> Option<LogEntry> {
+        None
+    }
+}
+
+/// Milestones are counted from joins and leaves rather than read off a single event,
+/// so this formatter never matches an event and is delivered through [`MemberMilestone::entry`] instead.
+pub struct MemberMilestone;
+
+impl MemberMilestone {
+    pub fn entry(guild_id: GuildId, milestone: i64) -> LogEntry {
+        LogEntry::new(
+            guild_id,
+            CreateEmbed::new().description(format!("The server reached {milestone} members.")),
+        )
+    }
+}
+
+#[async_trait]
+impl EventFormatter for MemberMilestone {
+    fn kind(&self) -> &'static str {
+        "member_milestone"
+    }
+
+    fn title(&self) -> &'static str {
+        "Member Milestone"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "member_milestone"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        _event: &FullEvent,
+        _data: &Data,
+    ) -> Option<LogEntry> {
+        None
+    }
+}
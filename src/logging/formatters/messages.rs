@@ -0,0 +1,538 @@
+use serenity::{
+    all::{FullEvent, Message, MessageReferenceKind, MessageSnapshot},
+    async_trait,
+    builder::{CreateAttachment, CreateEmbed, CreateMessage},
+};
+
+use super::{attachment_cap, attachment_followup, base_embed, now, pluralize, transcript};
+use crate::{
+    client::Data,
+    commands::LogType,
+    diff::asymmetric_diff_by,
+    logging::{
+        audit, edit_versions, filters,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        language, EventContext,
+    },
+    settings::keys,
+};
+
+/// How much of each version a compact edit log shows.
+const PREVIEW_CHARS: usize = 200;
+
+fn preview(content: &str) -> String {
+    match content.char_indices().nth(PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &content[..end]),
+        None => content.to_string(),
+    }
+}
+
+/// Where a forwarded message came from, and its snapshot of the original if Discord included one.
+///
+/// Forwards have no content of their own. The snapshot is used rather than the original itself, since
+/// forwards from other servers usually can't be fetched, and the original may have changed since.
+fn forwarded(message: &Message) -> Option<(String, Option<&MessageSnapshot>)> {
+    let reference = message.message_reference.as_ref()?;
+
+    if reference.kind != MessageReferenceKind::Forward {
+        return None;
+    }
+
+    let message_id = reference.message_id?;
+
+    let origin = match reference.guild_id {
+        Some(guild_id) if Some(guild_id) != message.guild_id => format!(
+            "[A message in another server]({})",
+            message_id.link(reference.channel_id, Some(guild_id))
+        ),
+        guild_id => format!(
+            "[A message in <#{}>]({})",
+            reference.channel_id,
+            message_id.link(reference.channel_id, guild_id)
+        ),
+    };
+
+    Some((origin, message.message_snapshots.first()))
+}
+
+pub struct MessageDelete;
+
+#[async_trait]
+impl EventFormatter for MessageDelete {
+    fn kind(&self) -> &'static str {
+        "message_delete"
+    }
+
+    fn title(&self) -> &'static str {
+        "Message Deleted"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "message_delete"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Chat
+    }
+
+    fn respects_trusted_roles(&self) -> bool {
+        true
+    }
+
+    fn detects_language(&self) -> bool {
+        true
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::MessageDelete {
+            channel_id,
+            deleted_message_id,
+            guild_id,
+        } = event
+        else {
+            return None;
+        };
+
+        let guild_id = *(guild_id.as_ref()?);
+
+        if data.housekeeping.take(*deleted_message_id)
+            || filters::is_below_min_age(data, guild_id, *deleted_message_id).await
+        {
+            return None;
+        }
+
+        let message = ctx
+            .cached_message(*channel_id, *deleted_message_id)
+            .or_else(|| data.cache_boost.message(*channel_id, *deleted_message_id))?;
+
+        if message.author.bot
+            || filters::is_ignored_kind(data, guild_id, &message).await
+            || filters::is_ignored_author(data, guild_id, &message).await
+            || filters::is_quick_self_delete(ctx, data, guild_id, &message).await
+        {
+            return None;
+        }
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let language = language::detect(&data.settings, guild_id, &message.content).await;
+
+        let mut followups = Vec::new();
+
+        let mut log_embed = base_embed(&message.author)
+            .description(format!(
+                "A message by <@{}> (**{}**) was deleted in <#{}>.",
+                message.author.id, message.author.name, message.channel_id
+            ))
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        let mut deleted_by = None;
+
+        // without an audit entry, the author most likely deleted it themselves, which needs no field.
+        if let Some(entry) = audit::find_message_delete(
+            ctx,
+            &data.pool,
+            guild_id,
+            message.author.id,
+            message.channel_id,
+        )
+        .await
+        {
+            log_embed = log_embed.field("Deleted by", format!("<@{}>", entry.user_id), true);
+            deleted_by = Some(entry.user_id);
+        }
+
+        if let Some(language) = language {
+            log_embed = log_embed.field("Language", language::describe(language), true);
+        }
+
+        if !message.attachments.is_empty() {
+            log_embed = log_embed.field(
+                "No. Attachments",
+                format!("{}", message.attachments.len()),
+                true,
+            );
+
+            let cap = attachment_cap(&data.settings, guild_id, self.default_route()).await;
+            followups.push(attachment_followup(ctx, None, &message.attachments, cap).await);
+        }
+
+        let content = match forwarded(&message) {
+            Some((origin, Some(original))) => {
+                log_embed = log_embed.field("Forwarded from", origin, false);
+
+                if !original.attachments.is_empty() {
+                    let cap = attachment_cap(&data.settings, guild_id, self.default_route()).await;
+                    let intro = "Forwarded attachments:".to_string();
+                    followups.push(
+                        attachment_followup(ctx, Some(intro), &original.attachments, cap).await,
+                    );
+                }
+
+                ("Forwarded content", original.content.clone())
+            }
+            Some((origin, None)) => {
+                log_embed = log_embed.field("Forwarded from", origin, false).field(
+                    "Forwarded content",
+                    "Unavailable, Discord didn't include the original message.",
+                    false,
+                );
+
+                ("Content", message.content)
+            }
+            None => ("Content", message.content),
+        };
+
+        let mut entry = LogEntry::new(guild_id, log_embed)
+            .content(content.0, content.1)
+            .content_followups(followups)
+            .subject(message.author.id)
+            .message(message.id)
+            .language(language);
+
+        if let Some(deleted_by) = deleted_by {
+            entry = entry.mod_action(deleted_by, "delete", 1);
+        }
+
+        Some(entry)
+    }
+}
+
+pub struct MessageUpdate;
+
+#[async_trait]
+impl EventFormatter for MessageUpdate {
+    fn kind(&self) -> &'static str {
+        "message_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Message Edited"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "message_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Chat
+    }
+
+    fn respects_trusted_roles(&self) -> bool {
+        true
+    }
+
+    fn detects_language(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::MessageUpdate {
+            old_if_available,
+            new,
+            event: _,
+        } = event
+        else {
+            return None;
+        };
+
+        let old = old_if_available.as_ref()?.clone();
+
+        if old.author.bot {
+            return None;
+        }
+
+        let guild_id = old.guild_id?;
+        let new = new.as_ref()?.clone();
+
+        if filters::is_ignored_kind(data, guild_id, &new).await
+            || filters::is_ignored_author(data, guild_id, &new).await
+        {
+            return None;
+        }
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let mut followups = Vec::new();
+
+        let mut description = format!(
+            "<@{}> (**{}**) updated their message in <#{}>.\n [Jump to message]({})",
+            new.author.id,
+            new.author.name,
+            new.channel_id,
+            new.link()
+        );
+
+        let mut log_embed = base_embed(&old.author);
+
+        let content_changed = old.content != new.content;
+        let language = if content_changed {
+            language::detect(&data.settings, guild_id, &new.content).await
+        } else {
+            None
+        };
+
+        if !content_changed {
+            description += "\n\n Message content hasn't changed. Check followup message(s) for attachment changes."
+        }
+
+        log_embed = log_embed.field("Timestamp", timestamps.format(now() as i64), true);
+
+        if let Some(language) = language {
+            log_embed = log_embed.field("Language", language::describe(language), true);
+        }
+
+        let attachments_could_have_changed =
+            !old.attachments.is_empty() || !new.attachments.is_empty();
+
+        if attachments_could_have_changed {
+            let mut difference = asymmetric_diff_by(&old.attachments, &new.attachments, |a| a.id);
+            let moved = difference.extract_moved(|a| a.filename.clone());
+
+            let mut summary = format!(
+                "**Removed**: {} | **Added**: {}",
+                difference.removed.len(),
+                difference.added.len()
+            );
+
+            if !moved.is_empty() {
+                summary += &format!(
+                    " | **Replaced**: {}",
+                    moved
+                        .iter()
+                        .map(|(_, new)| format!("`{}`", new.filename))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+
+            log_embed = log_embed.field("Attachments", summary, true);
+
+            let cap = attachment_cap(&data.settings, guild_id, self.default_route()).await;
+
+            if !difference.added.is_empty() {
+                let intro = format!(
+                    "Added {}:",
+                    pluralize("attachment", "attachments", difference.added.len())
+                );
+
+                followups.push(attachment_followup(ctx, Some(intro), &difference.added, cap).await);
+            }
+
+            if !difference.removed.is_empty() {
+                let intro = format!(
+                    "Removed {}:",
+                    pluralize("attachment", "attachments", difference.removed.len())
+                );
+
+                followups
+                    .push(attachment_followup(ctx, Some(intro), &difference.removed, cap).await);
+            }
+
+            // the replacements are still on the message, but the originals would be gone for good.
+            if !moved.is_empty() {
+                let originals = moved.into_iter().map(|(old, _)| old).collect::<Vec<_>>();
+                let intro = format!(
+                    "Replaced {}:",
+                    pluralize("attachment", "attachments", originals.len())
+                );
+
+                followups.push(attachment_followup(ctx, Some(intro), &originals, cap).await);
+            }
+        }
+
+        // slightly hacky workaround - we don't want to log embed deletions (yet).
+        if content_changed || attachments_could_have_changed {
+            let mut forwarded_content = None;
+
+            if let Some((origin, original)) = forwarded(&new) {
+                log_embed = log_embed.field("Forwarded from", origin, false);
+                forwarded_content = original.map(|original| original.content.clone());
+            }
+
+            let mut entry = LogEntry::new(guild_id, log_embed.description(description))
+                .content_followups(followups)
+                .subject(new.author.id)
+                .message(new.id)
+                .language(language);
+
+            let long = [&old.content, &new.content]
+                .iter()
+                .any(|content| content.chars().count() > edit_versions::COMPACT_EDIT_CHARS);
+
+            // long edits only get a preview, with the full versions one click away.
+            if content_changed
+                && long
+                && !data.settings.get(guild_id, &keys::METADATA_ONLY).await
+                && let Ok(id) = edit_versions::store(&data.pool, guild_id, &old, &new).await
+            {
+                entry = entry
+                    .content("New", preview(&new.content))
+                    .content("Previous", preview(&old.content))
+                    .components(vec![edit_versions::buttons(id)]);
+            } else if content_changed {
+                entry = entry
+                    .content("New", new.content)
+                    .content("Previous", old.content);
+            }
+
+            if let Some(content) = forwarded_content {
+                entry = entry.content("Forwarded content", content);
+            }
+
+            Some(entry)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct MessageBulkDelete;
+
+#[async_trait]
+impl EventFormatter for MessageBulkDelete {
+    fn kind(&self) -> &'static str {
+        "message_bulk_delete"
+    }
+
+    fn title(&self) -> &'static str {
+        "Messages Purged"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "message_delete_bulk"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Chat
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::MessageDeleteBulk {
+            channel_id,
+            multiple_deleted_messages_ids,
+            guild_id,
+        } = event
+        else {
+            return None;
+        };
+
+        let guild_id = (*guild_id)?;
+
+        // retention sweeps bulk delete too; those are already summarised by their own housekeeping log.
+        let deleted = multiple_deleted_messages_ids
+            .iter()
+            .filter(|message_id| !data.housekeeping.take(**message_id))
+            .copied()
+            .collect::<Vec<_>>();
+
+        if deleted.is_empty() {
+            return None;
+        }
+
+        let mut messages = deleted
+            .iter()
+            .filter_map(|message_id| {
+                ctx.cached_message(*channel_id, *message_id)
+                    .or_else(|| data.cache_boost.message(*channel_id, *message_id))
+            })
+            .collect::<Vec<_>>();
+        messages.sort_by_key(|message| message.id);
+
+        let entry = audit::find_bulk_delete(ctx, &data.pool, guild_id, *channel_id).await;
+
+        let purger = entry.as_ref().map(|entry| entry.user_id);
+        let reason = entry.and_then(|entry| entry.reason);
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let mut log_embed = CreateEmbed::new()
+            .description(format!(
+                "{} {} purged in <#{channel_id}>.",
+                deleted.len(),
+                pluralize("message was", "messages were", deleted.len())
+            ))
+            .field(
+                "Purged By",
+                purger.map_or("Unknown".to_string(), |purger| format!("<@{purger}>")),
+                true,
+            )
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        // purges by hand rarely come with a reason, but bots purging on a moderator's behalf usually say who asked.
+        if let Some(reason) = reason {
+            log_embed = log_embed.field("Reason", reason, false);
+        }
+
+        if messages.len() < deleted.len() {
+            log_embed = log_embed.field(
+                "Not Cached",
+                format!(
+                    "{} of the purged messages weren't cached and are missing from the transcript.",
+                    deleted.len() - messages.len()
+                ),
+                false,
+            );
+        }
+
+        let mut followups = Vec::new();
+
+        if !messages.is_empty() {
+            followups.push(CreateMessage::new().add_file(CreateAttachment::bytes(
+                transcript(&messages).into_bytes(),
+                format!("purge-{channel_id}.txt"),
+            )));
+        }
+
+        let mut entry = LogEntry::new(guild_id, log_embed).content_followups(followups);
+
+        if let Some(purger) = purger {
+            entry = entry.mod_action(purger, "delete", deleted.len() as i64);
+        }
+
+        Some(entry)
+    }
+}
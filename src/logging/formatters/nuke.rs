@@ -0,0 +1,199 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serenity::{
+    all::{
+        audit_log::{Action, ChannelAction, RoleAction},
+        FullEvent, GuildId,
+    },
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::now;
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        audit,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        EventContext,
+    },
+    settings::keys,
+};
+
+/// How many channels and roles have to be deleted within `WINDOW` to raise the alarm.
+const THRESHOLD: usize = 3;
+const WINDOW: Duration = Duration::from_secs(60);
+
+struct Deletions {
+    window_start: Instant,
+    /// `window_start` as a unix timestamp, to match against audit log entries.
+    started_at: i64,
+    deleted: Vec<String>,
+    alerted: bool,
+}
+
+#[derive(Clone, Copy)]
+pub enum Deleted {
+    Channel,
+    Role,
+}
+
+/// Watches channel and role deletions together, so a nuke that alternates between both still trips it.
+pub struct MassDeletion {
+    deleted: Deleted,
+    deletions: Arc<Mutex<HashMap<GuildId, Deletions>>>,
+}
+
+impl MassDeletion {
+    /// One formatter per deletion event, sharing a single tracker.
+    pub fn pair() -> [Self; 2] {
+        let deletions = Arc::new(Mutex::new(HashMap::new()));
+
+        [
+            Self {
+                deleted: Deleted::Channel,
+                deletions: deletions.clone(),
+            },
+            Self {
+                deleted: Deleted::Role,
+                deletions,
+            },
+        ]
+    }
+
+    /// Records a deletion and returns everything deleted in the current burst the first time it crosses the threshold.
+    fn record(&self, guild_id: GuildId, name: String) -> Option<(i64, Vec<String>)> {
+        let now = Instant::now();
+        let mut deletions = self.deletions.lock().unwrap();
+
+        deletions.retain(|_, deletions| now.duration_since(deletions.window_start) <= WINDOW);
+
+        let deletions = deletions.entry(guild_id).or_insert_with(|| Deletions {
+            window_start: now,
+            started_at: super::now() as i64,
+            deleted: Vec::new(),
+            alerted: false,
+        });
+
+        deletions.deleted.push(name);
+
+        if deletions.deleted.len() >= THRESHOLD && !deletions.alerted {
+            deletions.alerted = true;
+            Some((deletions.started_at, deletions.deleted.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl EventFormatter for MassDeletion {
+    fn kind(&self) -> &'static str {
+        "mass_deletion"
+    }
+
+    fn title(&self) -> &'static str {
+        "Mass Deletion"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Critical
+    }
+
+    fn event(&self) -> &'static str {
+        match self.deleted {
+            Deleted::Channel => "channel_delete",
+            Deleted::Role => "guild_role_delete",
+        }
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    fn is_threat_detection(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let (guild_id, name) = match event {
+            FullEvent::ChannelDelete { channel, .. } => {
+                (channel.guild_id, format!("#{}", channel.name))
+            }
+            FullEvent::GuildRoleDelete {
+                guild_id,
+                removed_role_id,
+                removed_role_data_if_available,
+            } => (
+                *guild_id,
+                removed_role_data_if_available.as_ref().map_or_else(
+                    || format!("role {removed_role_id}"),
+                    |role| format!("@{}", role.name),
+                ),
+            ),
+            _ => return None,
+        };
+
+        let (started_at, deleted) = self.record(guild_id, name)?;
+
+        let executor = audit::find_frequent_executor(
+            ctx,
+            &data.pool,
+            guild_id,
+            &[
+                Action::Channel(ChannelAction::Delete),
+                Action::Role(RoleAction::Delete),
+            ],
+            started_at,
+        )
+        .await;
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "{} channels and roles were deleted within {} seconds. This could be a compromised account or a nuke in progress.",
+                deleted.len(),
+                WINDOW.as_secs()
+            ))
+            .field(
+                "Executor",
+                executor.map_or("Unknown".to_string(), |executor| format!("<@{executor}>")),
+                true,
+            )
+            .field("Timestamp", timestamps.format(now() as i64), true)
+            .field("Deleted", deleted.join(", "), false);
+
+        let mut entry = LogEntry::new(guild_id, embed);
+
+        if let Some(executor) = executor {
+            entry = entry.subject(executor);
+        }
+
+        if data.settings.get(guild_id, &keys::NUKE_PING_OWNER).await
+            && let Some(owner) = ctx.guild_owner(guild_id)
+        {
+            entry = entry.mention(owner);
+        }
+
+        Some(entry)
+    }
+}
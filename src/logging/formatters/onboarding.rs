@@ -0,0 +1,239 @@
+use serde_json::Value;
+use serenity::{
+    all::{audit_log::Action, FullEvent},
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::now;
+use crate::{
+    client::Data,
+    commands::LogType,
+    diff::{asymmetric_diff_by, changed_by},
+    logging::{
+        audit,
+        formatter::{Category, EventFormatter, LogEntry},
+        EventContext,
+    },
+    settings::keys,
+};
+
+// serenity doesn't know the onboarding audit log actions yet.
+const PROMPT_CREATE: u8 = 163;
+const PROMPT_UPDATE: u8 = 164;
+const PROMPT_DELETE: u8 = 165;
+const ONBOARDING_CREATE: u8 = 166;
+const ONBOARDING_UPDATE: u8 = 167;
+
+/// The old and new value of the change to `key`.
+fn raw_change<'a>(changes: &'a [Value], key: &str) -> (Option<&'a Value>, Option<&'a Value>) {
+    changes
+        .iter()
+        .find(|change| change["key"] == key)
+        .map_or((None, None), |change| {
+            (change.get("old_value"), change.get("new_value"))
+        })
+}
+
+fn items(value: Option<&Value>) -> Vec<Value> {
+    value.and_then(Value::as_array).cloned().unwrap_or_default()
+}
+
+fn title(item: &Value) -> String {
+    item["title"].as_str().unwrap_or("Untitled").to_string()
+}
+
+/// Lists the prompts or options added, removed and edited between two versions, matched up by ID.
+fn diff_fields(noun: &str, old: Option<&Value>, new: Option<&Value>) -> Vec<(String, String)> {
+    let (old, new) = (items(old), items(new));
+    let id = |item: &Value| item["id"].to_string();
+
+    let diff = asymmetric_diff_by(&old, &new, id);
+    let edited = changed_by(&old, &new, id);
+
+    let mut fields = Vec::new();
+
+    for (label, titles) in [
+        ("added", diff.added.iter().map(title).collect::<Vec<_>>()),
+        ("removed", diff.removed.iter().map(title).collect()),
+        (
+            "edited",
+            edited
+                .iter()
+                .map(|(old, new)| match (title(old), title(new)) {
+                    (old, new) if old == new => new,
+                    (old, new) => format!("{old} → {new}"),
+                })
+                .collect(),
+        ),
+    ] {
+        if titles.is_empty() {
+            continue;
+        }
+
+        // embed fields are capped at 1024 characters, and a prompt can have dozens of options.
+        let mut value = String::new();
+        for (index, title) in titles.iter().enumerate() {
+            if value.len() + title.len() > 950 {
+                value += &format!("\n...and {} more", titles.len() - index);
+                break;
+            }
+
+            value += &format!("\n- {title}");
+        }
+
+        fields.push((format!("{noun} {label}"), value.trim().to_string()));
+    }
+
+    fields
+}
+
+/// Titles are only known if the entry could be fetched again.
+fn prompt_name(title: &str) -> String {
+    if title.is_empty() {
+        "An onboarding prompt".to_string()
+    } else {
+        format!("The onboarding prompt **{title}**")
+    }
+}
+
+fn channel_list(ids: &[Value]) -> String {
+    ids.iter()
+        .filter_map(Value::as_str)
+        .map(|id| format!("<#{id}>"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Changes to the onboarding flow new members go through. There's no gateway event for these,
+/// so they're picked up from audit log entries as they're created.
+pub struct OnboardingUpdate;
+
+#[async_trait]
+impl EventFormatter for OnboardingUpdate {
+    fn kind(&self) -> &'static str {
+        "onboarding_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Onboarding Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_audit_log_entry_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildAuditLogEntryCreate { entry, guild_id } = event else {
+            return None;
+        };
+
+        let Action::Unknown(action) = entry.action else {
+            return None;
+        };
+
+        if !(PROMPT_CREATE..=ONBOARDING_UPDATE).contains(&action) {
+            return None;
+        }
+
+        // serenity drops the changes to prompts, so the entry is fetched again as Discord sent it.
+        let changes = audit::raw_changes(ctx, &data.pool, *guild_id, entry)
+            .await
+            .unwrap_or_default();
+
+        let (old_title, new_title) = raw_change(&changes, "title");
+        let old_title = old_title.and_then(Value::as_str).unwrap_or_default();
+        let new_title = new_title.and_then(Value::as_str).unwrap_or_default();
+
+        let (description, fields) = match action {
+            PROMPT_CREATE => {
+                let (_, options) = raw_change(&changes, "options");
+                (
+                    format!("{} was added.", prompt_name(new_title)),
+                    diff_fields("Options", None, options),
+                )
+            }
+            PROMPT_DELETE => (
+                format!("{} was removed.", prompt_name(old_title)),
+                Vec::new(),
+            ),
+            PROMPT_UPDATE => {
+                let (old_options, new_options) = raw_change(&changes, "options");
+                let mut fields = diff_fields("Options", old_options, new_options);
+
+                if !old_title.is_empty() && old_title != new_title {
+                    fields.insert(
+                        0,
+                        ("Title".to_string(), format!("{old_title} → {new_title}")),
+                    );
+                }
+
+                (format!("{} was edited.", prompt_name(new_title)), fields)
+            }
+            ONBOARDING_CREATE | ONBOARDING_UPDATE => {
+                let (old_prompts, new_prompts) = raw_change(&changes, "prompts");
+                let mut fields = diff_fields("Prompts", old_prompts, new_prompts);
+
+                let (old_channels, new_channels) = raw_change(&changes, "default_channel_ids");
+                if old_channels.is_some() || new_channels.is_some() {
+                    let id = |id: &Value| id.to_string();
+                    let diff = asymmetric_diff_by(&items(old_channels), &items(new_channels), id);
+
+                    for (label, ids) in [
+                        ("Default channels added", diff.added),
+                        ("Default channels removed", diff.removed),
+                    ] {
+                        if !ids.is_empty() {
+                            fields.push((label.to_string(), channel_list(&ids)));
+                        }
+                    }
+                }
+
+                let (old_enabled, new_enabled) = raw_change(&changes, "enabled");
+                if old_enabled != new_enabled {
+                    let describe = |enabled: Option<&Value>| match enabled.and_then(Value::as_bool)
+                    {
+                        Some(true) => "On",
+                        Some(false) => "Off",
+                        None => "Unknown",
+                    };
+
+                    fields.push((
+                        "Enabled".to_string(),
+                        format!("{} → {}", describe(old_enabled), describe(new_enabled)),
+                    ));
+                }
+
+                ("The onboarding flow was edited.".to_string(), fields)
+            }
+            _ => return None,
+        };
+
+        let timestamps = data.settings.get(*guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let mut embed = CreateEmbed::new().description(description);
+
+        for (name, value) in fields {
+            embed = embed.field(name, value, false);
+        }
+
+        let embed = embed
+            .field("Edited by", format!("<@{}>", entry.user_id), true)
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(*guild_id, embed).subject(entry.user_id))
+    }
+}
@@ -0,0 +1,143 @@
+use serenity::{
+    all::{ChannelId, FullEvent, GuildId, MessageId, UserId},
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::now;
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        audit,
+        formatter::{Category, EventFormatter, LogEntry},
+        pins, EventContext,
+    },
+    settings::keys,
+};
+
+async fn describe_change(
+    ctx: &dyn EventContext,
+    data: &Data,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    author: Option<UserId>,
+    pinned: bool,
+) -> String {
+    let pinner = audit::find_pin(ctx, &data.pool, guild_id, channel_id, message_id, pinned)
+        .await
+        .map_or("Unknown".to_string(), |entry| {
+            format!("<@{}>", entry.user_id)
+        });
+
+    format!(
+        "{} [a message]({}){} - by {pinner}",
+        if pinned { "Pinned" } else { "Unpinned" },
+        message_id.link(channel_id, Some(guild_id)),
+        author.map_or(String::new(), |author| format!(" from <@{author}>")),
+    )
+}
+
+pub struct PinsUpdate;
+
+#[async_trait]
+impl EventFormatter for PinsUpdate {
+    fn kind(&self) -> &'static str {
+        "pins_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Pins Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "channel_pins_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Chat
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ChannelPinsUpdate { pin } = event else {
+            return None;
+        };
+
+        let channel_id = pin.channel_id;
+        let guild_id = pin.guild_id.or_else(|| ctx.channel_guild(channel_id))?;
+
+        // critical alerts get pinned in log channels, which shouldn't be logged in turn.
+        for log_type in LogType::ALL {
+            if log_type.fetch_channel(&data.pool, guild_id).await == Some(channel_id) {
+                return None;
+            }
+        }
+
+        let current = ctx.pins(channel_id).await.ok()?;
+        let current_ids = current.iter().map(|message| message.id).collect::<Vec<_>>();
+
+        // without the previous pins there's nothing to diff against; they're known from here on.
+        let previous = pins::replace(&data.pool, guild_id, channel_id, &current_ids)
+            .await
+            .ok()??;
+
+        let mut changes = Vec::new();
+
+        for message in current
+            .iter()
+            .filter(|message| !previous.contains(&message.id))
+        {
+            changes.push(
+                describe_change(
+                    ctx,
+                    data,
+                    guild_id,
+                    channel_id,
+                    message.id,
+                    Some(message.author.id),
+                    true,
+                )
+                .await,
+            );
+        }
+
+        for message_id in previous.iter().filter(|id| !current_ids.contains(id)) {
+            let author = ctx
+                .cached_message(channel_id, *message_id)
+                .map(|message| message.author.id);
+
+            changes.push(
+                describe_change(ctx, data, guild_id, channel_id, *message_id, author, false).await,
+            );
+        }
+
+        if changes.is_empty() {
+            return None;
+        }
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "Pins changed in <#{channel_id}>.\n{}",
+                changes.join("\n")
+            ))
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(guild_id, embed))
+    }
+}
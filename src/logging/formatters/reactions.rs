@@ -0,0 +1,281 @@
+use serenity::{
+    all::{ChannelId, FullEvent, GuildId, MessageId, Reaction},
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::{base_embed, now};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        filters,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        EventContext,
+    },
+    settings::keys,
+};
+
+fn jump_link(guild_id: GuildId, channel_id: ChannelId, message_id: MessageId) -> String {
+    format!(
+        "[Jump to message]({})",
+        message_id.link(channel_id, Some(guild_id))
+    )
+}
+
+/// Logs a single reaction being added or removed, if the guild opted into reaction logs or is in panic mode.
+async fn reaction_entry(data: &Data, reaction: &Reaction, action: &str) -> Option<LogEntry> {
+    let guild_id = reaction.guild_id?;
+    let user_id = reaction.user_id?;
+
+    if reaction
+        .member
+        .as_ref()
+        .is_some_and(|member| member.user.bot)
+        || !filters::is_opted_in(data, guild_id, &keys::LOG_REACTIONS).await
+    {
+        return None;
+    }
+
+    // moderators reacting to logs, e.g. to triage them, shouldn't be logged themselves.
+    for log_type in LogType::ALL {
+        if log_type.fetch_channel(&data.pool, guild_id).await == Some(reaction.channel_id) {
+            return None;
+        }
+    }
+
+    let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+    // only additions carry the member, so removals are logged without an avatar.
+    let embed = match &reaction.member {
+        Some(member) => base_embed(&member.user),
+        None => CreateEmbed::new(),
+    };
+
+    let embed = embed
+        .description(format!(
+            "<@{user_id}> {action} {} in <#{}>.\n{}",
+            reaction.emoji,
+            reaction.channel_id,
+            jump_link(guild_id, reaction.channel_id, reaction.message_id)
+        ))
+        .field("Timestamp", timestamps.format(now() as i64), true);
+
+    Some(
+        LogEntry::new(guild_id, embed)
+            .subject(user_id)
+            .message(reaction.message_id),
+    )
+}
+
+pub struct ReactionAdd;
+
+#[async_trait]
+impl EventFormatter for ReactionAdd {
+    fn kind(&self) -> &'static str {
+        "reaction_add"
+    }
+
+    fn title(&self) -> &'static str {
+        "Reaction Added"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "reaction_add"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Chat
+    }
+
+    fn respects_trusted_roles(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ReactionAdd { add_reaction } = event else {
+            return None;
+        };
+
+        reaction_entry(data, add_reaction, "reacted with").await
+    }
+}
+
+pub struct ReactionRemove;
+
+#[async_trait]
+impl EventFormatter for ReactionRemove {
+    fn kind(&self) -> &'static str {
+        "reaction_remove"
+    }
+
+    fn title(&self) -> &'static str {
+        "Reaction Removed"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn event(&self) -> &'static str {
+        "reaction_remove"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Chat
+    }
+
+    fn respects_trusted_roles(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ReactionRemove { removed_reaction } = event else {
+            return None;
+        };
+
+        reaction_entry(data, removed_reaction, "removed their").await
+    }
+}
+
+/// Every reaction on a message being cleared at once. Always logged, since only moderators can do it.
+pub struct ReactionClear;
+
+#[async_trait]
+impl EventFormatter for ReactionClear {
+    fn kind(&self) -> &'static str {
+        "reaction_clear"
+    }
+
+    fn title(&self) -> &'static str {
+        "Reactions Cleared"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "reaction_remove_all"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Chat
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ReactionRemoveAll {
+            channel_id,
+            removed_from_message_id,
+        } = event
+        else {
+            return None;
+        };
+
+        let message = ctx.cached_message(*channel_id, *removed_from_message_id);
+        let guild_id = message
+            .as_ref()
+            .and_then(|message| message.guild_id)
+            .or_else(|| ctx.channel_guild(*channel_id))?;
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let mut embed = CreateEmbed::new()
+            .description(format!(
+                "All reactions were removed from a message in <#{channel_id}>.\n{}",
+                jump_link(guild_id, *channel_id, *removed_from_message_id)
+            ))
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        if let Some(message) = &message {
+            embed = embed.field("Author", format!("<@{}>", message.author.id), true);
+        }
+
+        Some(LogEntry::new(guild_id, embed).message(*removed_from_message_id))
+    }
+}
+
+/// One emoji's reactions being cleared from a message. Always logged, since only moderators can do it.
+pub struct ReactionEmojiClear;
+
+#[async_trait]
+impl EventFormatter for ReactionEmojiClear {
+    fn kind(&self) -> &'static str {
+        "reaction_emoji_clear"
+    }
+
+    fn title(&self) -> &'static str {
+        "Reaction Cleared"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Notice
+    }
+
+    fn event(&self) -> &'static str {
+        "reaction_remove_emoji"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Chat
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ReactionRemoveEmoji { removed_reactions } = event else {
+            return None;
+        };
+
+        let guild_id = removed_reactions
+            .guild_id
+            .or_else(|| ctx.channel_guild(removed_reactions.channel_id))?;
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "All {} reactions were removed from a message in <#{}>.\n{}",
+                removed_reactions.emoji,
+                removed_reactions.channel_id,
+                jump_link(
+                    guild_id,
+                    removed_reactions.channel_id,
+                    removed_reactions.message_id
+                )
+            ))
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(guild_id, embed).message(removed_reactions.message_id))
+    }
+}
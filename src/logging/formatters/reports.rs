@@ -0,0 +1,116 @@
+use serenity::{
+    all::{FullEvent, GuildId, Message},
+    async_trait,
+};
+
+use super::{base_embed, pluralize};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        timestamps::TimestampStyle,
+        triage, EventContext,
+    },
+    user_reports::ReporterStats,
+};
+
+/// Reports come from the "Report to mods" context menu rather than a gateway event,
+/// so this formatter never matches an event and is delivered through [`MemberReport::entry`] instead.
+pub struct MemberReport;
+
+impl MemberReport {
+    /// `reporter` is the reporter's track record, which is shown without revealing who they are.
+    pub fn entry(
+        guild_id: GuildId,
+        report_id: i64,
+        message: &Message,
+        reporter: &ReporterStats,
+        timestamps: TimestampStyle,
+    ) -> LogEntry {
+        let mut embed = base_embed(&message.author)
+            .description(format!(
+                "A member reported a message by <@{}> ({}) in <#{}>.\n[Jump to message]({})",
+                message.author.id,
+                message.author.name,
+                message.channel_id,
+                message.link()
+            ))
+            .field(
+                "Sent At",
+                timestamps.format(message.timestamp.unix_timestamp()),
+                true,
+            )
+            .field("Report", format!("#{report_id}"), true);
+
+        if reporter.filed > 0 {
+            embed = embed.field(
+                "Reporter History",
+                format!(
+                    "{} earlier {}: {} handled, {} false positive.",
+                    reporter.filed,
+                    pluralize("report", "reports", reporter.filed as usize),
+                    reporter.handled,
+                    reporter.false_positives
+                ),
+                false,
+            );
+        }
+
+        let mut entry = LogEntry::new(guild_id, embed)
+            .content("Content", message.content.clone())
+            .subject(message.author.id)
+            .message(message.id)
+            .components(vec![triage::buttons()]);
+
+        if !message.attachments.is_empty() {
+            entry = entry.content(
+                "Attachments",
+                message
+                    .attachments
+                    .iter()
+                    .map(|attachment| format!("[{}]({})", attachment.filename, attachment.url))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl EventFormatter for MemberReport {
+    fn kind(&self) -> &'static str {
+        "member_report"
+    }
+
+    fn title(&self) -> &'static str {
+        "Message Reported"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn event(&self) -> &'static str {
+        "report"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Reports
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        _event: &FullEvent,
+        _data: &Data,
+    ) -> Option<LogEntry> {
+        None
+    }
+}
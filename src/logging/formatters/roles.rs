@@ -0,0 +1,396 @@
+use serenity::{
+    all::{
+        audit_log::{Action, RoleAction},
+        FullEvent, GuildId, Role, RoleId, UserId,
+    },
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::now;
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        audit,
+        bulk_roles::BulkChange,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        permissions,
+        timestamps::TimestampStyle,
+        EventContext,
+    },
+    settings::keys,
+};
+
+fn describe_colour(role: &Role) -> String {
+    match role.colour.0 {
+        0 => "Default".to_string(),
+        _ => format!("#{}", role.colour.hex()),
+    }
+}
+
+/// Embed field values are capped at 1024 characters, which a role going from nothing to everything can exceed.
+fn permissions_field(diff: String) -> String {
+    if diff.len() <= 1024 {
+        diff
+    } else {
+        "Too many permissions changed to list.".to_string()
+    }
+}
+
+async fn role_embed(data: &Data, guild_id: GuildId, description: String) -> CreateEmbed {
+    let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+    CreateEmbed::new().description(description).field(
+        "Timestamp",
+        timestamps.format(now() as i64),
+        true,
+    )
+}
+
+/// Who took `action` on the role, going by the audit log.
+async fn executor(
+    ctx: &dyn EventContext,
+    data: &Data,
+    guild_id: GuildId,
+    role_id: RoleId,
+    action: RoleAction,
+) -> Option<UserId> {
+    audit::find_executor(
+        ctx,
+        &data.pool,
+        guild_id,
+        role_id.get(),
+        &[Action::Role(action)],
+    )
+    .await
+}
+
+fn describe_executor(user_id: Option<UserId>) -> String {
+    user_id.map_or("Unknown".to_string(), |user_id| format!("<@{user_id}>"))
+}
+
+fn attribute(entry: LogEntry, user_id: Option<UserId>) -> LogEntry {
+    match user_id {
+        Some(user_id) => entry.subject(user_id),
+        None => entry,
+    }
+}
+
+pub struct RoleCreate;
+
+#[async_trait]
+impl EventFormatter for RoleCreate {
+    fn kind(&self) -> &'static str {
+        "role_create"
+    }
+
+    fn title(&self) -> &'static str {
+        "Role Created"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_role_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildRoleCreate { new } = event else {
+            return None;
+        };
+
+        let mut embed = role_embed(
+            data,
+            new.guild_id,
+            format!("<@&{}> (**{}**) was created.", new.id, new.name),
+        )
+        .await
+        .field("Colour", describe_colour(new), true);
+
+        let created_by = executor(ctx, data, new.guild_id, new.id, RoleAction::Create).await;
+        embed = embed.field("Created by", describe_executor(created_by), true);
+
+        if let Some(permissions) =
+            permissions::render_permission_diff(Default::default(), new.permissions)
+        {
+            embed = embed.field("Permissions", permissions_field(permissions), false);
+        }
+
+        let mut entry = attribute(LogEntry::new(new.guild_id, embed), created_by);
+
+        // roles are usually created empty, so one that starts out with moderation permissions is worth a closer look.
+        if let Some(grant) = permissions::grant_context(std::slice::from_ref(new)) {
+            entry = entry.severity(grant.severity);
+        }
+
+        Some(entry)
+    }
+}
+
+pub struct RoleDelete;
+
+#[async_trait]
+impl EventFormatter for RoleDelete {
+    fn kind(&self) -> &'static str {
+        "role_delete"
+    }
+
+    fn title(&self) -> &'static str {
+        "Role Deleted"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_role_delete"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildRoleDelete {
+            guild_id,
+            removed_role_id,
+            removed_role_data_if_available: role,
+        } = event
+        else {
+            return None;
+        };
+
+        let description = match role {
+            Some(role) => format!("**@{}** was deleted.", role.name),
+            None => format!("A role that wasn't cached ({removed_role_id}) was deleted."),
+        };
+
+        let deleted_by = executor(ctx, data, *guild_id, *removed_role_id, RoleAction::Delete).await;
+
+        let mut embed = role_embed(data, *guild_id, description).await.field(
+            "Deleted by",
+            describe_executor(deleted_by),
+            true,
+        );
+
+        if let Some(role) = role {
+            embed = embed.field("Colour", describe_colour(role), true);
+
+            if let Some(permissions) =
+                permissions::render_permission_diff(role.permissions, Default::default())
+            {
+                embed = embed.field("Permissions", permissions_field(permissions), false);
+            }
+        }
+
+        Some(attribute(LogEntry::new(*guild_id, embed), deleted_by))
+    }
+}
+
+pub struct RoleUpdate;
+
+#[async_trait]
+impl EventFormatter for RoleUpdate {
+    fn kind(&self) -> &'static str {
+        "role_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Role Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_role_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildRoleUpdate {
+            old_data_if_available: Some(old),
+            new,
+        } = event
+        else {
+            return None;
+        };
+
+        let mut changes = Vec::new();
+
+        if old.name != new.name {
+            changes.push(("Name", format!("{} → {}", old.name, new.name)));
+        }
+
+        if old.colour != new.colour {
+            changes.push((
+                "Colour",
+                format!("{} → {}", describe_colour(old), describe_colour(new)),
+            ));
+        }
+
+        if old.hoist != new.hoist {
+            changes.push(("Hoisted", format!("{} → {}", old.hoist, new.hoist)));
+        }
+
+        if old.mentionable != new.mentionable {
+            changes.push((
+                "Mentionable",
+                format!("{} → {}", old.mentionable, new.mentionable),
+            ));
+        }
+
+        let permission_diff = permissions::render_permission_diff(old.permissions, new.permissions);
+
+        // reordering roles updates every role below the moved one, which would otherwise flood the log.
+        if changes.is_empty() && permission_diff.is_none() {
+            return None;
+        }
+
+        let mut embed = role_embed(
+            data,
+            new.guild_id,
+            format!("<@&{}> (**{}**) was updated.", new.id, new.name),
+        )
+        .await;
+
+        let updated_by = executor(ctx, data, new.guild_id, new.id, RoleAction::Update).await;
+        embed = embed.field("Updated by", describe_executor(updated_by), true);
+
+        for (name, value) in changes {
+            embed = embed.field(name, value, true);
+        }
+
+        if let Some(permission_diff) = permission_diff {
+            embed = embed.field("Permissions", permissions_field(permission_diff), false);
+        }
+
+        let mut entry = attribute(LogEntry::new(new.guild_id, embed), updated_by);
+
+        let mut gained = new.clone();
+        gained.permissions = new.permissions - old.permissions;
+
+        if let Some(grant) = permissions::grant_context(&[gained]) {
+            entry = entry.severity(grant.severity);
+        }
+
+        Some(entry)
+    }
+}
+
+/// Bulk role changes are noticed across many member updates rather than in a single event,
+/// so this formatter never matches an event and is delivered through [`BulkRoleChange::entry`] instead.
+pub struct BulkRoleChange;
+
+impl BulkRoleChange {
+    pub fn entry(
+        change: &BulkChange,
+        executor: Option<UserId>,
+        severity: Severity,
+        timestamps: TimestampStyle,
+    ) -> LogEntry {
+        let mut members = change
+            .members
+            .iter()
+            .take(40)
+            .map(|user_id| format!("<@{user_id}>"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if change.members.len() > 40 {
+            members += &format!(" and {} more", change.members.len() - 40);
+        }
+
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "{} members {} <@&{}>.",
+                change.members.len(),
+                if change.added { "were given" } else { "lost" },
+                change.role_id
+            ))
+            .field("Started", timestamps.format(change.started_at), true)
+            .field("Executor", describe_executor(executor), true)
+            .field("Members", members, false);
+
+        attribute(LogEntry::new(change.guild_id, embed), executor).severity(severity)
+    }
+}
+
+#[async_trait]
+impl EventFormatter for BulkRoleChange {
+    fn kind(&self) -> &'static str {
+        "bulk_role_change"
+    }
+
+    fn title(&self) -> &'static str {
+        "Bulk Role Change"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn event(&self) -> &'static str {
+        "bulk_role_change"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    fn is_threat_detection(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        _event: &FullEvent,
+        _data: &Data,
+    ) -> Option<LogEntry> {
+        None
+    }
+}
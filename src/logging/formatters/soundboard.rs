@@ -0,0 +1,291 @@
+use serenity::{
+    all::{audit_log::Action, FullEvent, GuildId, UserId},
+    async_trait,
+    builder::{CreateEmbed, CreateMessage},
+};
+
+use super::{attachment_cap, now};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        audit,
+        formatter::{Category, EventFormatter, LogEntry},
+        soundboard::SoundboardSound,
+        EventContext,
+    },
+    settings::keys,
+};
+
+// serenity doesn't know the soundboard audit log actions yet.
+const SOUND_UPDATE_ACTION: u8 = 131;
+const SOUND_DELETE_ACTION: u8 = 132;
+
+fn describe(sound: &SoundboardSound) -> String {
+    format!(
+        "**{}**{} at {:.0}% volume",
+        sound.name,
+        sound
+            .emoji
+            .as_ref()
+            .map_or(String::new(), |emoji| format!(" {emoji}")),
+        sound.volume * 100.0
+    )
+}
+
+/// Who took `action` on the sound, going by the audit log.
+async fn actor(
+    ctx: &dyn EventContext,
+    data: &Data,
+    guild_id: GuildId,
+    sound_id: u64,
+    action: u8,
+) -> Option<UserId> {
+    audit::find_target_action(ctx, &data.pool, guild_id, sound_id, Action::Unknown(action))
+        .await
+        .map(|entry| entry.user_id)
+}
+
+fn mention(user_id: Option<UserId>) -> String {
+    user_id.map_or("Unknown".to_string(), |user_id| format!("<@{user_id}>"))
+}
+
+/// Re-uploads the sound, so it can still be listened to once it's gone from the server. Links it if that fails.
+async fn audio_followup(
+    ctx: &dyn EventContext,
+    data: &Data,
+    guild_id: GuildId,
+    sound: &SoundboardSound,
+) -> Option<CreateMessage> {
+    if attachment_cap(&data.settings, guild_id, LogType::Server).await == 0 {
+        return None;
+    }
+
+    let intro = format!("Sound: **{}**", sound.name);
+
+    Some(match ctx.download_attachment(&sound.url()).await {
+        Ok(file) => CreateMessage::new().content(intro).add_file(file),
+        Err(_) => CreateMessage::new().content(format!("{intro}\n{}", sound.url())),
+    })
+}
+
+/// Soundboard events don't reach the event handler, so this and the other soundboard formatters never match an event.
+/// [`crate::logging::soundboard`] delivers their entries instead.
+pub struct SoundCreate;
+
+impl SoundCreate {
+    pub async fn entry(
+        ctx: &dyn EventContext,
+        data: &Data,
+        guild_id: GuildId,
+        sound: &SoundboardSound,
+    ) -> LogEntry {
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = CreateEmbed::new()
+            .description(format!(
+                "A soundboard sound was added: {}.",
+                describe(sound)
+            ))
+            .field("Added by", mention(sound.user), true)
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        let mut entry = LogEntry::new(guild_id, embed);
+
+        if let Some(followup) = audio_followup(ctx, data, guild_id, sound).await {
+            entry = entry.followups(vec![followup]);
+        }
+
+        if let Some(user_id) = sound.user {
+            entry = entry.subject(user_id);
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl EventFormatter for SoundCreate {
+    fn kind(&self) -> &'static str {
+        "soundboard_sound_create"
+    }
+
+    fn title(&self) -> &'static str {
+        "Soundboard Sound Added"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_soundboard_sound_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        _event: &FullEvent,
+        _data: &Data,
+    ) -> Option<LogEntry> {
+        None
+    }
+}
+
+pub struct SoundUpdate;
+
+impl SoundUpdate {
+    /// `previous` is the sound as last seen, if it was seen before.
+    pub async fn entry(
+        ctx: &dyn EventContext,
+        data: &Data,
+        guild_id: GuildId,
+        previous: Option<&SoundboardSound>,
+        sound: &SoundboardSound,
+    ) -> LogEntry {
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+        let updated_by = actor(ctx, data, guild_id, sound.id, SOUND_UPDATE_ACTION).await;
+
+        let mut embed = CreateEmbed::new()
+            .description(format!(
+                "A soundboard sound was edited: {}.",
+                describe(sound)
+            ))
+            .field("Edited by", mention(updated_by), true);
+
+        if let Some(previous) = previous {
+            embed = embed.field("Previously", describe(previous), false);
+        }
+
+        let embed = embed.field("Timestamp", timestamps.format(now() as i64), true);
+
+        let mut entry = LogEntry::new(guild_id, embed);
+
+        if let Some(followup) = audio_followup(ctx, data, guild_id, sound).await {
+            entry = entry.followups(vec![followup]);
+        }
+
+        if let Some(user_id) = updated_by {
+            entry = entry.subject(user_id);
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl EventFormatter for SoundUpdate {
+    fn kind(&self) -> &'static str {
+        "soundboard_sound_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Soundboard Sound Edited"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_soundboard_sound_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        _event: &FullEvent,
+        _data: &Data,
+    ) -> Option<LogEntry> {
+        None
+    }
+}
+
+pub struct SoundDelete;
+
+impl SoundDelete {
+    /// `previous` is the sound as last seen, if it was seen before. Otherwise only its ID is known.
+    pub async fn entry(
+        ctx: &dyn EventContext,
+        data: &Data,
+        guild_id: GuildId,
+        sound_id: u64,
+        previous: Option<&SoundboardSound>,
+    ) -> LogEntry {
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+        let removed_by = actor(ctx, data, guild_id, sound_id, SOUND_DELETE_ACTION).await;
+
+        let description = match previous {
+            Some(previous) => format!("A soundboard sound was removed: {}.", describe(previous)),
+            None => format!("The soundboard sound `{sound_id}` was removed."),
+        };
+
+        let embed = CreateEmbed::new()
+            .description(description)
+            .field("Removed by", mention(removed_by), true)
+            .field("Timestamp", timestamps.format(now() as i64), true);
+
+        let mut entry = LogEntry::new(guild_id, embed);
+
+        // deleted sounds linger on the CDN for a while, like stickers do.
+        if let Some(previous) = previous
+            && let Some(followup) = audio_followup(ctx, data, guild_id, previous).await
+        {
+            entry = entry.followups(vec![followup]);
+        }
+
+        if let Some(user_id) = removed_by {
+            entry = entry.subject(user_id);
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl EventFormatter for SoundDelete {
+    fn kind(&self) -> &'static str {
+        "soundboard_sound_delete"
+    }
+
+    fn title(&self) -> &'static str {
+        "Soundboard Sound Removed"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_soundboard_sound_delete"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        _event: &FullEvent,
+        _data: &Data,
+    ) -> Option<LogEntry> {
+        None
+    }
+}
@@ -0,0 +1,165 @@
+use serenity::{
+    all::{FullEvent, Sticker},
+    async_trait,
+    builder::{CreateEmbed, CreateMessage},
+};
+
+use super::{list_field, now, pluralize};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        formatter::{Category, EventFormatter, LogEntry},
+        EventContext,
+    },
+    settings::keys,
+};
+
+/// A message can carry at most 10 embeds, so that's how many sticker previews are posted.
+const MAX_PREVIEWS: usize = 10;
+
+fn preview(title: String, sticker: &Sticker) -> Option<CreateEmbed> {
+    // lottie stickers have no image to show.
+    let url = sticker.image_url()?;
+
+    Some(CreateEmbed::new().title(title).image(url))
+}
+
+pub struct StickerUpdate;
+
+#[async_trait]
+impl EventFormatter for StickerUpdate {
+    fn kind(&self) -> &'static str {
+        "sticker_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Stickers Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_stickers_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildStickersUpdate {
+            guild_id,
+            current_state,
+        } = event
+        else {
+            return None;
+        };
+
+        // without the previous set there's nothing to diff against; it's known from here on.
+        let previous = data.stickers.replace(*guild_id, current_state)?;
+
+        let mut added = current_state
+            .values()
+            .filter(|sticker| !previous.contains_key(&sticker.id))
+            .collect::<Vec<_>>();
+        let mut removed = previous
+            .values()
+            .filter(|sticker| !current_state.contains_key(&sticker.id))
+            .collect::<Vec<_>>();
+        let mut renamed = current_state
+            .values()
+            .filter_map(|sticker| {
+                let old = previous.get(&sticker.id)?;
+                (old.name != sticker.name).then_some((old, sticker))
+            })
+            .collect::<Vec<_>>();
+
+        if added.is_empty() && removed.is_empty() && renamed.is_empty() {
+            return None;
+        }
+
+        added.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        removed.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        renamed.sort_unstable_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+
+        let timestamps = data.settings.get(*guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let changed = added.len() + removed.len() + renamed.len();
+        let mut embed = CreateEmbed::new().description(format!(
+            "{changed} {} changed.",
+            pluralize("sticker was", "stickers were", changed)
+        ));
+
+        if !added.is_empty() {
+            embed = embed.field(
+                "Added",
+                list_field(
+                    added
+                        .iter()
+                        .map(|sticker| format!("**{}**", sticker.name))
+                        .collect(),
+                ),
+                false,
+            );
+        }
+
+        if !removed.is_empty() {
+            embed = embed.field(
+                "Removed",
+                list_field(
+                    removed
+                        .iter()
+                        .map(|sticker| format!("**{}**", sticker.name))
+                        .collect(),
+                ),
+                false,
+            );
+        }
+
+        if !renamed.is_empty() {
+            embed = embed.field(
+                "Renamed",
+                list_field(
+                    renamed
+                        .iter()
+                        .map(|(old, new)| format!("**{}** → **{}**", old.name, new.name))
+                        .collect(),
+                ),
+                false,
+            );
+        }
+
+        embed = embed.field("Timestamp", timestamps.format(now() as i64), true);
+
+        // removed stickers stay on the CDN for a while, so they can still be previewed.
+        let previews = added
+            .iter()
+            .filter_map(|sticker| preview(format!("Added: {}", sticker.name), sticker))
+            .chain(renamed.iter().filter_map(|(old, new)| {
+                preview(format!("Renamed: {} → {}", old.name, new.name), new)
+            }))
+            .chain(
+                removed
+                    .iter()
+                    .filter_map(|sticker| preview(format!("Removed: {}", sticker.name), sticker)),
+            )
+            .take(MAX_PREVIEWS)
+            .collect::<Vec<_>>();
+
+        let mut entry = LogEntry::new(*guild_id, embed);
+
+        if !previews.is_empty() {
+            entry = entry.followups(vec![CreateMessage::new().embeds(previews)]);
+        }
+
+        Some(entry)
+    }
+}
@@ -0,0 +1,362 @@
+use serenity::{
+    all::{AutoArchiveDuration, ChannelType, FullEvent, GuildChannel, GuildId},
+    async_trait,
+    builder::{CreateAttachment, CreateEmbed, CreateMessage},
+};
+
+use super::{now, pluralize, transcript};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        formatter::{Category, EventFormatter, LogEntry},
+        timestamps, EventContext,
+    },
+    settings::keys,
+};
+
+fn describe_auto_archive(duration: AutoArchiveDuration) -> String {
+    match u16::from(duration) {
+        0 => "Never".to_string(),
+        minutes => timestamps::describe_duration(i64::from(minutes) * 60),
+    }
+}
+
+/// The forum `thread` was posted in, if it's a forum post rather than a regular thread.
+fn forum(ctx: &dyn EventContext, thread: &GuildChannel) -> Option<GuildChannel> {
+    ctx.cached_channel(thread.guild_id, thread.parent_id?)
+        .filter(|parent| parent.kind == ChannelType::Forum)
+}
+
+async fn thread_embed(
+    data: &Data,
+    guild_id: GuildId,
+    thread: Option<&GuildChannel>,
+    description: String,
+) -> CreateEmbed {
+    let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+    let mut embed = CreateEmbed::new().description(description);
+
+    if let Some(thread) = thread {
+        if let Some(parent_id) = thread.parent_id {
+            embed = embed.field("Parent", format!("<#{parent_id}>"), true);
+        }
+
+        embed = embed.field(
+            "Owner",
+            thread
+                .owner_id
+                .map_or("Unknown".to_string(), |owner| format!("<@{owner}>")),
+            true,
+        );
+    }
+
+    embed.field("Timestamp", timestamps.format(now() as i64), true)
+}
+
+pub struct ThreadCreate;
+
+#[async_trait]
+impl EventFormatter for ThreadCreate {
+    fn kind(&self) -> &'static str {
+        "thread_create"
+    }
+
+    fn title(&self) -> &'static str {
+        "Thread Created"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "thread_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ThreadCreate { thread } = event else {
+            return None;
+        };
+
+        // forum posts are logged as such instead.
+        if forum(ctx, thread).is_some() {
+            return None;
+        }
+
+        let embed = thread_embed(
+            data,
+            thread.guild_id,
+            Some(thread),
+            format!("<#{}> (**{}**) was created.", thread.id, thread.name),
+        )
+        .await;
+
+        let mut entry = LogEntry::new(thread.guild_id, embed);
+
+        if let Some(owner) = thread.owner_id {
+            entry = entry.subject(owner);
+        }
+
+        Some(entry)
+    }
+}
+
+/// A thread created in a forum channel, logged with its tags and opening message.
+pub struct ForumPostCreate;
+
+#[async_trait]
+impl EventFormatter for ForumPostCreate {
+    fn kind(&self) -> &'static str {
+        "forum_post_create"
+    }
+
+    fn title(&self) -> &'static str {
+        "New Forum Post"
+    }
+
+    fn category(&self) -> Category {
+        Category::Added
+    }
+
+    fn event(&self) -> &'static str {
+        "thread_create"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Chat
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ThreadCreate { thread } = event else {
+            return None;
+        };
+
+        let forum = forum(ctx, thread)?;
+
+        let tags = forum
+            .available_tags
+            .iter()
+            .filter(|tag| thread.applied_tags.contains(&tag.id))
+            .map(|tag| format!("`{}`", tag.name))
+            .collect::<Vec<_>>();
+
+        let embed = thread_embed(
+            data,
+            thread.guild_id,
+            Some(thread),
+            format!("<#{}> was posted in <#{}>.", thread.id, forum.id),
+        )
+        .await
+        .field("Title", &thread.name, false)
+        .field(
+            "Tags",
+            match tags.is_empty() {
+                true => "None".to_string(),
+                false => tags.join(", "),
+            },
+            false,
+        );
+
+        let mut entry = LogEntry::new(thread.guild_id, embed);
+
+        if let Some(owner) = thread.owner_id {
+            entry = entry.subject(owner);
+        }
+
+        // a forum post's opening message shares the thread's ID.
+        if let Ok(starter) = ctx.message(thread.id, thread.id.get().into()).await {
+            entry = entry.content("Content", starter.content);
+        }
+
+        Some(entry)
+    }
+}
+
+pub struct ThreadDelete;
+
+#[async_trait]
+impl EventFormatter for ThreadDelete {
+    fn kind(&self) -> &'static str {
+        "thread_delete"
+    }
+
+    fn title(&self) -> &'static str {
+        "Thread Deleted"
+    }
+
+    fn category(&self) -> Category {
+        Category::Removed
+    }
+
+    fn event(&self) -> &'static str {
+        "thread_delete"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ThreadDelete {
+            thread,
+            full_thread_data,
+        } = event
+        else {
+            return None;
+        };
+
+        let description = match full_thread_data {
+            Some(full) => format!("**{}** was deleted.", full.name),
+            None => format!("A thread that wasn't cached ({}) was deleted.", thread.id),
+        };
+
+        let mut embed = thread_embed(
+            data,
+            thread.guild_id,
+            full_thread_data.as_ref(),
+            description,
+        )
+        .await;
+
+        // the full thread data carries the parent too; only fall back to the partial one without it.
+        if full_thread_data.is_none() {
+            embed = embed.field("Parent", format!("<#{}>", thread.parent_id), true);
+        }
+
+        // deleting a thread takes its whole conversation with it, so keep whatever the bot still has cached.
+        let messages = ctx.cached_messages(thread.id);
+        let mut followups = Vec::new();
+
+        if !messages.is_empty() {
+            embed = embed.field(
+                "Recovered",
+                format!(
+                    "{} cached {}, attached below.",
+                    messages.len(),
+                    pluralize("message", "messages", messages.len())
+                ),
+                true,
+            );
+
+            followups.push(CreateMessage::new().add_file(CreateAttachment::bytes(
+                transcript(&messages).into_bytes(),
+                format!("thread-{}.txt", thread.id),
+            )));
+        }
+
+        Some(LogEntry::new(thread.guild_id, embed).content_followups(followups))
+    }
+}
+
+pub struct ThreadUpdate;
+
+#[async_trait]
+impl EventFormatter for ThreadUpdate {
+    fn kind(&self) -> &'static str {
+        "thread_update"
+    }
+
+    fn title(&self) -> &'static str {
+        "Thread Updated"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "thread_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Server
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::ThreadUpdate {
+            old: Some(old),
+            new,
+        } = event
+        else {
+            return None;
+        };
+
+        let mut changes = Vec::new();
+
+        if old.name != new.name {
+            changes.push(("Name", format!("{} → {}", old.name, new.name)));
+        }
+
+        if let (Some(old_meta), Some(new_meta)) = (&old.thread_metadata, &new.thread_metadata) {
+            if old_meta.archived != new_meta.archived {
+                changes.push((
+                    "Archived",
+                    format!("{} → {}", old_meta.archived, new_meta.archived),
+                ));
+            }
+
+            if old_meta.locked != new_meta.locked {
+                changes.push((
+                    "Locked",
+                    format!("{} → {}", old_meta.locked, new_meta.locked),
+                ));
+            }
+
+            if old_meta.auto_archive_duration != new_meta.auto_archive_duration {
+                changes.push((
+                    "Auto Archive",
+                    format!(
+                        "{} → {}",
+                        describe_auto_archive(old_meta.auto_archive_duration),
+                        describe_auto_archive(new_meta.auto_archive_duration)
+                    ),
+                ));
+            }
+        }
+
+        // message counts and the like change on every message, so anything else is ignored.
+        if changes.is_empty() {
+            return None;
+        }
+
+        let mut embed = thread_embed(
+            data,
+            new.guild_id,
+            Some(new),
+            format!("<#{}> (**{}**) was updated.", new.id, new.name),
+        )
+        .await;
+
+        for (name, value) in changes {
+            embed = embed.field(name, value, true);
+        }
+
+        Some(LogEntry::new(new.guild_id, embed))
+    }
+}
@@ -0,0 +1,162 @@
+use serenity::{all::FullEvent, async_trait};
+
+use super::{base_embed, now};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        timestamps, verification, EventContext,
+    },
+    settings::keys,
+};
+
+pub struct LateVerification;
+
+#[async_trait]
+impl EventFormatter for LateVerification {
+    fn kind(&self) -> &'static str {
+        "late_verification"
+    }
+
+    fn title(&self) -> &'static str {
+        "Late Verification"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberUpdate {
+            new: Some(member), ..
+        } = event
+        else {
+            return None;
+        };
+
+        let waited = verification::record_update(&data.pool, member)
+            .await
+            .ok()??;
+        let threshold = data
+            .settings
+            .get(member.guild_id, &keys::VERIFICATION_LURK_HOURS)
+            .await;
+
+        if threshold <= 0 || waited < threshold * 60 * 60 {
+            return None;
+        }
+
+        let timestamps = data
+            .settings
+            .get(member.guild_id, &keys::TIMESTAMP_STYLE)
+            .await;
+
+        let embed = base_embed(&member.user)
+            .description(format!(
+                "<@{}> ({}) verified {} after joining. Accounts that lurk unverified and then suddenly activate are a common raid pattern.",
+                member.user.id,
+                member.user.name,
+                timestamps::describe_duration(waited)
+            ))
+            .field(
+                "Joined At",
+                timestamps.format(now() as i64 - waited),
+                true,
+            )
+            .field("Verified At", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(member.guild_id, embed).subject(member.user.id))
+    }
+}
+
+/// Passing membership screening, i.e. accepting the server's rules. Unlike [`LateVerification`], logged for everyone.
+pub struct MemberVerified;
+
+#[async_trait]
+impl EventFormatter for MemberVerified {
+    fn kind(&self) -> &'static str {
+        "member_verified"
+    }
+
+    fn title(&self) -> &'static str {
+        "Member Verified"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "guild_member_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::GuildMemberUpdate {
+            old_if_available: Some(old),
+            new: Some(member),
+            ..
+        } = event
+        else {
+            return None;
+        };
+
+        if !old.pending || member.pending {
+            return None;
+        }
+
+        let timestamps = data
+            .settings
+            .get(member.guild_id, &keys::TIMESTAMP_STYLE)
+            .await;
+
+        let mut embed = base_embed(&member.user).description(format!(
+            "<@{}> ({}) passed membership screening.",
+            member.user.id, member.user.name
+        ));
+
+        if let Some(joined_at) = member.joined_at {
+            embed = embed
+                .field(
+                    "Joined At",
+                    timestamps.format(joined_at.unix_timestamp()),
+                    true,
+                )
+                .field(
+                    "Took",
+                    timestamps::describe_duration(now() as i64 - joined_at.unix_timestamp()),
+                    true,
+                );
+        }
+
+        let embed = embed.field("Verified At", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(member.guild_id, embed).subject(member.user.id))
+    }
+}
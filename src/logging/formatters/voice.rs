@@ -0,0 +1,285 @@
+use std::time::Duration;
+
+use serenity::{
+    all::{
+        audit_log::{Action, VoiceChannelStatusAction},
+        FullEvent,
+    },
+    async_trait,
+    builder::CreateEmbed,
+};
+
+use super::{base_embed, now};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        audit,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        voice_hops::MAX_HOP_WINDOW,
+        EventContext,
+    },
+    settings::keys,
+};
+
+pub struct VoiceHopSpam;
+
+#[async_trait]
+impl EventFormatter for VoiceHopSpam {
+    fn kind(&self) -> &'static str {
+        "voice_hop_spam"
+    }
+
+    fn title(&self) -> &'static str {
+        "Voice Channel Hopping"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn event(&self) -> &'static str {
+        "voice_state_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Member
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::VoiceStateUpdate { old, new } = event else {
+            return None;
+        };
+
+        // mutes, deafens and streams also come through here; only channel changes count as hops.
+        if old.as_ref().and_then(|old| old.channel_id) == new.channel_id {
+            return None;
+        }
+
+        let guild_id = new.guild_id?;
+        let threshold = data
+            .settings
+            .get(guild_id, &keys::VOICE_HOP_THRESHOLD)
+            .await;
+
+        if threshold <= 0 {
+            return None;
+        }
+
+        let window = data.settings.get(guild_id, &keys::VOICE_HOP_WINDOW).await;
+        let window = Duration::from_secs(window.max(1) as u64).min(MAX_HOP_WINDOW);
+
+        let count = data
+            .voice_hops
+            .record(guild_id, new.user_id, threshold, window)?;
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = match &new.member {
+            Some(member) => base_embed(&member.user),
+            None => CreateEmbed::new(),
+        }
+        .description(format!(
+            "<@{}> joined or left voice channels {count} times within {} seconds. Further hops won't be reported until they slow down.",
+            new.user_id,
+            window.as_secs()
+        ))
+        .field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(guild_id, embed).subject(new.user_id))
+    }
+}
+
+pub struct VoiceActivity;
+
+#[async_trait]
+impl EventFormatter for VoiceActivity {
+    fn kind(&self) -> &'static str {
+        "voice_activity"
+    }
+
+    fn title(&self) -> &'static str {
+        "Voice Activity"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "voice_state_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Voice
+    }
+
+    async fn format(
+        &self,
+        _ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::VoiceStateUpdate { old, new } = event else {
+            return None;
+        };
+
+        let guild_id = new.guild_id?;
+        let user_id = new.user_id;
+        let old_channel = old.as_ref().and_then(|old| old.channel_id);
+
+        let mut changes = Vec::new();
+
+        // once flagged for hopping, the alert speaks for their joins and leaves until they slow down.
+        let hopping = data.voice_hops.is_flagged(guild_id, user_id);
+
+        match (old_channel, new.channel_id) {
+            _ if hopping => {}
+            (None, Some(channel)) => changes.push(format!("<@{user_id}> joined <#{channel}>.")),
+            (Some(channel), None) => changes.push(format!("<@{user_id}> left <#{channel}>.")),
+            (Some(from), Some(to)) if from != to => {
+                changes.push(format!("<@{user_id}> moved from <#{from}> to <#{to}>."))
+            }
+            _ => {}
+        }
+
+        // self mutes and deafens are too noisy to log; only server-side ones are moderation actions.
+        if let Some(old) = old {
+            if old.mute != new.mute {
+                changes.push(format!(
+                    "<@{user_id}> was server {}.",
+                    if new.mute { "muted" } else { "unmuted" }
+                ));
+            }
+
+            if old.deaf != new.deaf {
+                changes.push(format!(
+                    "<@{user_id}> was server {}.",
+                    if new.deaf { "deafened" } else { "undeafened" }
+                ));
+            }
+        }
+
+        if changes.is_empty() {
+            return None;
+        }
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let embed = match &new.member {
+            Some(member) => base_embed(&member.user),
+            None => CreateEmbed::new(),
+        }
+        .description(changes.join("\n"))
+        .field("Timestamp", timestamps.format(now() as i64), true);
+
+        Some(LogEntry::new(guild_id, embed).subject(user_id))
+    }
+}
+
+pub struct VoiceChannelStatus;
+
+#[async_trait]
+impl EventFormatter for VoiceChannelStatus {
+    fn kind(&self) -> &'static str {
+        "voice_channel_status"
+    }
+
+    fn title(&self) -> &'static str {
+        "Voice Channel Status"
+    }
+
+    fn category(&self) -> Category {
+        Category::Changed
+    }
+
+    fn event(&self) -> &'static str {
+        "voice_channel_status_update"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Voice
+    }
+
+    fn uses_audit_log(&self) -> bool {
+        true
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::VoiceChannelStatusUpdate {
+            old,
+            status,
+            id,
+            guild_id,
+        } = event
+        else {
+            return None;
+        };
+
+        // empty statuses come through as both missing and blank.
+        let old = old.as_deref().filter(|old| !old.is_empty());
+        let status = status.as_deref().filter(|status| !status.is_empty());
+
+        if old == status {
+            return None;
+        }
+
+        let action = match status {
+            Some(_) => VoiceChannelStatusAction::StatusUpdate,
+            None => VoiceChannelStatusAction::StatusDelete,
+        };
+
+        let setter = audit::find_target_action(
+            ctx,
+            &data.pool,
+            *guild_id,
+            id.get(),
+            Action::VoiceChannelStatus(action),
+        )
+        .await
+        .map(|entry| entry.user_id);
+
+        let timestamps = data.settings.get(*guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let mut entry = LogEntry::new(
+            *guild_id,
+            CreateEmbed::new()
+                .description(format!(
+                    "The status of <#{id}> was {}.",
+                    if status.is_some() { "set" } else { "cleared" }
+                ))
+                .field(
+                    if status.is_some() {
+                        "Set by"
+                    } else {
+                        "Cleared by"
+                    },
+                    setter.map_or("Unknown".to_string(), |user_id| format!("<@{user_id}>")),
+                    true,
+                )
+                .field("Timestamp", timestamps.format(now() as i64), true),
+        )
+        .content("Previous", old.unwrap_or_default().to_string())
+        .content("New", status.unwrap_or_default().to_string());
+
+        if let Some(setter) = setter {
+            entry = entry.subject(setter);
+        }
+
+        Some(entry)
+    }
+}
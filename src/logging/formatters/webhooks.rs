@@ -0,0 +1,114 @@
+use serenity::{all::FullEvent, async_trait};
+
+use super::{base_embed, now};
+use crate::{
+    client::Data,
+    commands::LogType,
+    logging::{
+        filters,
+        formatter::{Category, EventFormatter, LogEntry, Severity},
+        EventContext,
+    },
+    settings::keys,
+};
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+pub struct WebhookSpoof;
+
+#[async_trait]
+impl EventFormatter for WebhookSpoof {
+    fn kind(&self) -> &'static str {
+        "webhook_spoof"
+    }
+
+    fn title(&self) -> &'static str {
+        "Possible Webhook Impersonation"
+    }
+
+    fn category(&self) -> Category {
+        Category::Moderation
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn event(&self) -> &'static str {
+        "message"
+    }
+
+    fn default_route(&self) -> LogType {
+        LogType::Chat
+    }
+
+    async fn format(
+        &self,
+        ctx: &dyn EventContext,
+        event: &FullEvent,
+        data: &Data,
+    ) -> Option<LogEntry> {
+        let FullEvent::Message { new_message } = event else {
+            return None;
+        };
+
+        // interaction responses are sent through the application's webhook, too.
+        if new_message.webhook_id.is_none() || new_message.application_id.is_some() {
+            return None;
+        }
+
+        let guild_id = new_message.guild_id?;
+
+        if !data
+            .settings
+            .get(guild_id, &keys::DETECT_WEBHOOK_SPOOFS)
+            .await
+            || filters::is_ignored_author(data, guild_id, new_message).await
+        {
+            return None;
+        }
+
+        let webhook_name = normalize(&new_message.author.name);
+
+        let member = ctx.cached_members(guild_id).into_iter().find(|member| {
+            [
+                member.nick.as_deref(),
+                member.user.global_name.as_deref(),
+                Some(member.user.name.as_str()),
+            ]
+            .into_iter()
+            .flatten()
+            .any(|name| normalize(name) == webhook_name)
+        })?;
+
+        let avatar_matches =
+            new_message.author.avatar.is_some() && new_message.author.avatar == member.user.avatar;
+
+        let timestamps = data.settings.get(guild_id, &keys::TIMESTAMP_STYLE).await;
+
+        let mut entry = LogEntry::new(
+            guild_id,
+            base_embed(&new_message.author)
+                .description(format!(
+                    "A webhook posted as **{}** in <#{}>, which matches the name of <@{}>{}.\n[Jump to message]({})",
+                    new_message.author.name,
+                    new_message.channel_id,
+                    member.user.id,
+                    if avatar_matches { " and their avatar" } else { "" },
+                    new_message.link()
+                ))
+                .field("Timestamp", timestamps.format(now() as i64), true),
+        )
+        .content("Content", new_message.content.clone())
+        .subject(member.user.id)
+        .message(new_message.id);
+
+        if avatar_matches {
+            entry = entry.severity(Severity::Critical);
+        }
+
+        Some(entry)
+    }
+}
@@ -0,0 +1,31 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serenity::all::MessageId;
+
+/// How long to wait for Discord to confirm a housekeeping deletion before forgetting about it.
+const EXPIRY: Duration = Duration::from_secs(5 * 60);
+
+/// Messages the bot is deleting itself (e.g. for channel retention), so their deletion isn't logged as a user's.
+#[derive(Default)]
+pub struct Housekeeping {
+    pending: Mutex<HashMap<MessageId, Instant>>,
+}
+
+impl Housekeeping {
+    pub fn mark(&self, message_ids: impl IntoIterator<Item = MessageId>) {
+        let now = Instant::now();
+        let mut pending = self.pending.lock().unwrap();
+
+        pending.retain(|_, marked_at| now.duration_since(*marked_at) <= EXPIRY);
+        pending.extend(message_ids.into_iter().map(|id| (id, now)));
+    }
+
+    /// Whether `message_id` was deleted by housekeeping. Each mark is only consumed once.
+    pub fn take(&self, message_id: MessageId) -> bool {
+        self.pending.lock().unwrap().remove(&message_id).is_some()
+    }
+}
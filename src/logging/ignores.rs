@@ -0,0 +1,126 @@
+use serenity::all::{GuildId, Message, UserId};
+use sqlx::{Pool, Sqlite};
+
+use super::now;
+use crate::features::GLOBAL_SCOPE;
+
+#[derive(Debug, poise::ChoiceParameter, Clone, Copy, PartialEq, Eq)]
+pub enum IgnoreKind {
+    #[name = "User or bot"]
+    User,
+    #[name = "Webhook application"]
+    Application,
+}
+
+impl IgnoreKind {
+    pub const ALL: [Self; 2] = [Self::User, Self::Application];
+
+    pub fn as_key(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Application => "application",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_key() == key)
+    }
+}
+
+pub struct Ignore {
+    pub kind: IgnoreKind,
+    pub target_id: String,
+    pub added_by: String,
+    pub added_at: i64,
+}
+
+/// Ignores `target_id` in `scope`. Returns `false` if it was already ignored there.
+pub async fn add(
+    pool: &Pool<Sqlite>,
+    scope: &str,
+    kind: IgnoreKind,
+    target_id: &str,
+    added_by: UserId,
+) -> Result<bool, sqlx::Error> {
+    let kind = kind.as_key();
+    let added_by = added_by.to_string();
+    let now = now() as i64;
+
+    let result = sqlx::query!(
+        "INSERT INTO ignores (scope, kind, target_id, added_by, added_at) VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT (scope, kind, target_id) DO NOTHING",
+        scope,
+        kind,
+        target_id,
+        added_by,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Stops ignoring `target_id` in `scope`. Returns `false` if it wasn't ignored there.
+pub async fn remove(
+    pool: &Pool<Sqlite>,
+    scope: &str,
+    kind: IgnoreKind,
+    target_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let kind = kind.as_key();
+
+    let result = sqlx::query!(
+        "DELETE FROM ignores WHERE scope = ? AND kind = ? AND target_id = ?",
+        scope,
+        kind,
+        target_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn list(pool: &Pool<Sqlite>, scope: &str) -> Result<Vec<Ignore>, sqlx::Error> {
+    let rows = sqlx::query!(
+        "SELECT kind, target_id, added_by, added_at FROM ignores WHERE scope = ? ORDER BY added_at",
+        scope
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(Ignore {
+                kind: IgnoreKind::from_key(&row.kind)?,
+                target_id: row.target_id,
+                added_by: row.added_by,
+                added_at: row.added_at,
+            })
+        })
+        .collect())
+}
+
+/// Whether `message`'s author, or the application that sent it, is ignored in `guild_id` or instance-wide.
+pub async fn is_ignored(pool: &Pool<Sqlite>, guild_id: GuildId, message: &Message) -> bool {
+    let guild_id = guild_id.to_string();
+    let author = message.author.id.to_string();
+    let application = message.application_id.map(|id| id.to_string());
+
+    sqlx::query!(
+        "SELECT target_id FROM ignores WHERE scope IN (?, ?)
+        AND ((kind = 'user' AND target_id = ?) OR (kind = 'application' AND target_id = ?))
+        LIMIT 1",
+        guild_id,
+        GLOBAL_SCOPE,
+        author,
+        application
+    )
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()
+    .is_some()
+}
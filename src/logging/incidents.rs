@@ -0,0 +1,283 @@
+use std::str::FromStr;
+
+use serenity::all::{ChannelId, GuildId, MessageId, UserId};
+use sqlx::{Pool, Sqlite};
+
+use super::now;
+
+/// How long an incident stays open for new logs after its last activity.
+const INCIDENT_WINDOW_SECS: i64 = 5 * 60;
+
+pub struct Incident {
+    pub id: i64,
+    /// The most recent log message already sent for this incident, if any.
+    pub previous: Option<(ChannelId, MessageId)>,
+}
+
+/// Finds the open incident for `subject`, or opens a new one if `opens` is set.
+/// Logs that don't concern a specific user are never grouped.
+pub async fn assign(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    subject: Option<UserId>,
+    opens: bool,
+) -> Result<Option<Incident>, sqlx::Error> {
+    let Some(subject) = subject else {
+        return Ok(None);
+    };
+
+    let guild_id = guild_id.to_string();
+    let subject = subject.to_string();
+    let now = now() as i64;
+    let cutoff = now - INCIDENT_WINDOW_SECS;
+
+    let open = sqlx::query!(
+        "SELECT id FROM incidents WHERE guild_id = ? AND subject_id = ? AND last_activity >= ? AND resolved_at IS NULL ORDER BY id DESC LIMIT 1",
+        guild_id,
+        subject,
+        cutoff
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let id = match open {
+        Some(row) => {
+            sqlx::query!(
+                "UPDATE incidents SET last_activity = ? WHERE id = ?",
+                now,
+                row.id
+            )
+            .execute(pool)
+            .await?;
+
+            row.id
+        }
+        None if opens => sqlx::query!(
+            "INSERT INTO incidents (guild_id, subject_id, opened_at, last_activity) VALUES (?, ?, ?, ?)",
+            guild_id,
+            subject,
+            now,
+            now
+        )
+        .execute(pool)
+        .await?
+        .last_insert_rowid(),
+        None => return Ok(None),
+    };
+
+    let previous = sqlx::query!(
+        "SELECT channel_id, message_id FROM incident_messages WHERE incident_id = ? ORDER BY created_at DESC LIMIT 1",
+        id
+    )
+    .fetch_optional(pool)
+    .await?
+    .and_then(|row| {
+        Some((
+            ChannelId::from_str(&row.channel_id).ok()?,
+            MessageId::from_str(&row.message_id).ok()?,
+        ))
+    });
+
+    Ok(Some(Incident { id, previous }))
+}
+
+pub async fn record_message(
+    pool: &Pool<Sqlite>,
+    incident_id: i64,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> Result<(), sqlx::Error> {
+    let channel_id = channel_id.to_string();
+    let message_id = message_id.to_string();
+    let now = now() as i64;
+
+    sqlx::query!(
+        "INSERT INTO incident_messages (incident_id, channel_id, message_id, created_at) VALUES (?, ?, ?, ?)",
+        incident_id,
+        channel_id,
+        message_id,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Marks `message_id` as the incident's pinned alert, unless one is pinned already. Returns whether it was claimed.
+pub async fn claim_pin(
+    pool: &Pool<Sqlite>,
+    incident_id: i64,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> Result<bool, sqlx::Error> {
+    let channel_id = channel_id.to_string();
+    let message_id = message_id.to_string();
+
+    let result = sqlx::query!(
+        "UPDATE incidents SET pinned_channel_id = ?, pinned_message_id = ? WHERE id = ? AND pinned_message_id IS NULL",
+        channel_id,
+        message_id,
+        incident_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub enum Resolution {
+    NotFound,
+    AlreadyResolved,
+    Resolved {
+        pinned: Option<(ChannelId, MessageId)>,
+    },
+}
+
+/// Closes an incident so no further logs join it, returning its pinned alert (if any) so it can be unpinned.
+pub async fn resolve(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    incident_id: i64,
+) -> Result<Resolution, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    let Some(row) = sqlx::query!(
+        "SELECT resolved_at, pinned_channel_id, pinned_message_id FROM incidents WHERE id = ? AND guild_id = ?",
+        incident_id,
+        guild_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(Resolution::NotFound);
+    };
+
+    if row.resolved_at.is_some() {
+        return Ok(Resolution::AlreadyResolved);
+    }
+
+    let now = now() as i64;
+    sqlx::query!(
+        "UPDATE incidents SET resolved_at = ? WHERE id = ?",
+        now,
+        incident_id
+    )
+    .execute(pool)
+    .await?;
+
+    let pinned =
+        row.pinned_channel_id
+            .zip(row.pinned_message_id)
+            .and_then(|(channel_id, message_id)| {
+                Some((
+                    ChannelId::from_str(&channel_id).ok()?,
+                    MessageId::from_str(&message_id).ok()?,
+                ))
+            });
+
+    Ok(Resolution::Resolved { pinned })
+}
+
+pub struct RecentIncident {
+    pub id: i64,
+    pub opened_at: i64,
+    /// The first log message sent for the incident, if any.
+    pub first_message: Option<(ChannelId, MessageId)>,
+}
+
+/// The latest incident about `subject` with activity since `since`, resolved or not.
+pub async fn latest_for_subject(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    subject: UserId,
+    since: i64,
+) -> Result<Option<RecentIncident>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let subject = subject.to_string();
+
+    let Some(incident) = sqlx::query!(
+        "SELECT id, opened_at FROM incidents WHERE guild_id = ? AND subject_id = ? AND last_activity >= ? ORDER BY id DESC LIMIT 1",
+        guild_id,
+        subject,
+        since
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let first_message = sqlx::query!(
+        "SELECT channel_id, message_id FROM incident_messages WHERE incident_id = ? ORDER BY created_at ASC LIMIT 1",
+        incident.id
+    )
+    .fetch_optional(pool)
+    .await?
+    .and_then(|row| {
+        Some((
+            ChannelId::from_str(&row.channel_id).ok()?,
+            MessageId::from_str(&row.message_id).ok()?,
+        ))
+    });
+
+    Ok(Some(RecentIncident {
+        id: incident.id,
+        opened_at: incident.opened_at,
+        first_message,
+    }))
+}
+
+pub struct IncidentLogs {
+    pub subject: UserId,
+    pub opened_at: i64,
+    pub resolved_at: Option<i64>,
+    /// Every log message sent for the incident, oldest first.
+    pub messages: Vec<(ChannelId, MessageId)>,
+}
+
+/// An incident in `guild_id` along with all of its logs, so they can be found from one place.
+pub async fn logs(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    incident_id: i64,
+) -> Result<Option<IncidentLogs>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    let Some(incident) = sqlx::query!(
+        "SELECT subject_id, opened_at, resolved_at FROM incidents WHERE id = ? AND guild_id = ?",
+        incident_id,
+        guild_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let Ok(subject) = UserId::from_str(&incident.subject_id) else {
+        return Ok(None);
+    };
+
+    let messages = sqlx::query!(
+        "SELECT channel_id, message_id FROM incident_messages WHERE incident_id = ? ORDER BY created_at ASC",
+        incident_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter_map(|row| {
+        Some((
+            ChannelId::from_str(&row.channel_id).ok()?,
+            MessageId::from_str(&row.message_id).ok()?,
+        ))
+    })
+    .collect();
+
+    Ok(Some(IncidentLogs {
+        subject,
+        opened_at: incident.opened_at,
+        resolved_at: incident.resolved_at,
+        messages,
+    }))
+}
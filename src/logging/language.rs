@@ -0,0 +1,58 @@
+use serenity::all::{ChannelId, GuildId};
+use whatlang::Lang;
+
+use crate::settings::{keys, Settings};
+
+/// Short messages ("ok", "lol") are too ambiguous to tag.
+const MIN_CHARS: usize = 12;
+
+pub fn channel_key(code: &str) -> String {
+    format!("language.{code}.channel")
+}
+
+pub fn ignored_key(code: &str) -> String {
+    format!("language.{code}.ignored")
+}
+
+/// Detects the language of `content` if the guild opted into language tags and the detection is reliable.
+pub async fn detect(settings: &Settings, guild_id: GuildId, content: &str) -> Option<Lang> {
+    if content.chars().count() < MIN_CHARS || !settings.get(guild_id, &keys::LANGUAGE_TAGS).await {
+        return None;
+    }
+
+    whatlang::detect(content)
+        .filter(|info| info.is_reliable())
+        .map(|info| info.lang())
+}
+
+pub fn describe(language: Lang) -> String {
+    format!("{} (`{}`)", language.eng_name(), language.code())
+}
+
+pub enum LanguageRoute {
+    Default,
+    Ignored,
+    Channel(ChannelId),
+}
+
+/// Where logs about content in `language` go, per the guild's `/language` rules.
+pub async fn route(settings: &Settings, guild_id: GuildId, language: Lang) -> LanguageRoute {
+    let code = language.code();
+
+    if settings
+        .get_raw(guild_id, &ignored_key(code))
+        .await
+        .is_some_and(|ignored| ignored == "true")
+    {
+        return LanguageRoute::Ignored;
+    }
+
+    match settings
+        .get_raw(guild_id, &channel_key(code))
+        .await
+        .and_then(|id| id.parse::<u64>().ok())
+    {
+        Some(id) => LanguageRoute::Channel(ChannelId::new(id)),
+        None => LanguageRoute::Default,
+    }
+}
@@ -0,0 +1,169 @@
+use std::str::FromStr;
+
+use serenity::all::{ChannelId, GuildId, MessageId, UserId};
+use sqlx::{Pool, Sqlite};
+
+use super::now;
+
+pub struct LogMessage {
+    pub channel_id: ChannelId,
+    pub message_id: MessageId,
+    pub kind: String,
+    pub subject: Option<UserId>,
+    pub created_at: i64,
+    pub status: Option<String>,
+}
+
+/// What's kept about a posted log, besides where it was posted.
+pub struct RecordedLog<'a> {
+    pub guild_id: GuildId,
+    pub kind: &'a str,
+    pub subject: Option<UserId>,
+    /// The message the log is about, e.g. the one that was deleted.
+    pub about: Option<MessageId>,
+    pub incident_id: Option<i64>,
+}
+
+pub async fn record(
+    pool: &Pool<Sqlite>,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    log: &RecordedLog<'_>,
+) -> Result<(), sqlx::Error> {
+    let guild_id = log.guild_id.to_string();
+    let channel_id = channel_id.to_string();
+    let message_id = message_id.to_string();
+    let subject = log.subject.map(|id| id.to_string());
+    let about = log.about.map(|id| id.to_string());
+    let now = now() as i64;
+
+    sqlx::query!(
+        "INSERT INTO log_messages (message_id, channel_id, guild_id, kind, subject_id, about_message_id, incident_id, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        message_id,
+        channel_id,
+        guild_id,
+        log.kind,
+        subject,
+        about,
+        log.incident_id,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Sets the triage status of a log message. Returns `false` if `message_id` isn't a log message.
+pub async fn set_status(
+    pool: &Pool<Sqlite>,
+    message_id: MessageId,
+    status: Option<&str>,
+    triaged_by: UserId,
+) -> Result<bool, sqlx::Error> {
+    let message_id = message_id.to_string();
+    let triaged_by = triaged_by.to_string();
+    let now = now() as i64;
+
+    let result = sqlx::query!(
+        "UPDATE log_messages SET status = ?, triaged_by = ?, triaged_at = ? WHERE message_id = ?",
+        status,
+        triaged_by,
+        now,
+        message_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn status(
+    pool: &Pool<Sqlite>,
+    message_id: MessageId,
+) -> Result<Option<String>, sqlx::Error> {
+    let message_id = message_id.to_string();
+
+    Ok(sqlx::query!(
+        "SELECT status FROM log_messages WHERE message_id = ?",
+        message_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .and_then(|row| row.status))
+}
+
+/// The most recent log messages in a guild matching the given filters.
+pub async fn search(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    status: Option<&str>,
+    kind: Option<&str>,
+    subject: Option<UserId>,
+    limit: i64,
+) -> Result<Vec<LogMessage>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let subject = subject.map(|id| id.to_string());
+
+    Ok(sqlx::query!(
+        "SELECT channel_id, message_id, kind, subject_id, created_at, status FROM log_messages
+        WHERE guild_id = ? AND (? IS NULL OR status = ?) AND (? IS NULL OR kind = ?) AND (? IS NULL OR subject_id = ?)
+        ORDER BY created_at DESC LIMIT ?",
+        guild_id,
+        status,
+        status,
+        kind,
+        kind,
+        subject,
+        subject,
+        limit
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter_map(|row| {
+        Some(LogMessage {
+            channel_id: ChannelId::from_str(&row.channel_id).ok()?,
+            message_id: MessageId::from_str(&row.message_id).ok()?,
+            kind: row.kind,
+            subject: row
+                .subject_id
+                .and_then(|id| UserId::from_str(&id).ok()),
+            created_at: row.created_at,
+            status: row.status,
+        })
+    })
+    .collect())
+}
+
+/// Every log about `about` (e.g. its deletion and edits), oldest first.
+pub async fn about_message(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    about: MessageId,
+) -> Result<Vec<LogMessage>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let about = about.to_string();
+
+    Ok(sqlx::query!(
+        "SELECT channel_id, message_id, kind, subject_id, created_at, status FROM log_messages
+        WHERE guild_id = ? AND about_message_id = ? ORDER BY created_at",
+        guild_id,
+        about
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter_map(|row| {
+        Some(LogMessage {
+            channel_id: ChannelId::from_str(&row.channel_id).ok()?,
+            message_id: MessageId::from_str(&row.message_id).ok()?,
+            kind: row.kind,
+            subject: row.subject_id.and_then(|id| UserId::from_str(&id).ok()),
+            created_at: row.created_at,
+            status: row.status,
+        })
+    })
+    .collect())
+}
@@ -0,0 +1,268 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+use serenity::{
+    all::{ChannelId, GuildId, Http, MessageId, UserId},
+    builder::{CreateEmbed, CreateMessage},
+};
+use sqlx::{Pool, Sqlite};
+
+use super::{
+    log_messages::{self, RecordedLog},
+    now, theme,
+};
+use crate::{commands::LogType, features::GLOBAL_SCOPE, settings::Settings};
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+pub struct Maintenance {
+    pub started_by: UserId,
+    pub started_at: i64,
+}
+
+/// Whether log deliveries to `guild_id` are paused, either for the guild itself or for the whole instance.
+pub async fn is_active(pool: &Pool<Sqlite>, guild_id: GuildId) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    let row = sqlx::query!(
+        "SELECT scope FROM maintenance WHERE scope IN (?, ?) LIMIT 1",
+        guild_id,
+        GLOBAL_SCOPE
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.is_some())
+}
+
+pub async fn get(pool: &Pool<Sqlite>, scope: &str) -> Result<Option<Maintenance>, sqlx::Error> {
+    let row = sqlx::query!(
+        "SELECT started_by, started_at FROM maintenance WHERE scope = ?",
+        scope
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.and_then(|row| {
+        Some(Maintenance {
+            started_by: UserId::from_str(&row.started_by).ok()?,
+            started_at: row.started_at,
+        })
+    }))
+}
+
+/// Pauses deliveries for `scope`. Returns `false` if it was already paused.
+pub async fn start(
+    pool: &Pool<Sqlite>,
+    scope: &str,
+    started_by: UserId,
+) -> Result<bool, sqlx::Error> {
+    let started_by = started_by.to_string();
+    let now = now() as i64;
+
+    let result = sqlx::query!(
+        "INSERT INTO maintenance (scope, started_by, started_at) VALUES (?, ?, ?) ON CONFLICT (scope) DO NOTHING",
+        scope,
+        started_by,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Resumes deliveries for `scope`; the backlog is posted by [`flush`]. Returns `false` if it wasn't paused.
+pub async fn stop(pool: &Pool<Sqlite>, scope: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!("DELETE FROM maintenance WHERE scope = ?", scope)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// How many logs are waiting to be posted, for `guild_id` or across all guilds.
+pub async fn backlog(pool: &Pool<Sqlite>, guild_id: Option<GuildId>) -> Result<i64, sqlx::Error> {
+    let guild_id = guild_id.map(|id| id.to_string());
+
+    let row = sqlx::query!(
+        r#"SELECT COUNT(*) AS "count!: i64" FROM maintenance_queue WHERE ?1 IS NULL OR guild_id = ?1"#,
+        guild_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.count)
+}
+
+/// Queues a log that would have been posted to `channel_id`, along with its followups.
+/// Logs with a `log` to record are tracked in [`log_messages`] once they're posted.
+pub async fn queue(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    log: Option<&RecordedLog<'_>>,
+    message: &CreateMessage,
+    followups: &[CreateMessage],
+) -> Result<(), crate::client::Error> {
+    let guild_id = guild_id.to_string();
+    let channel_id = channel_id.to_string();
+    let kind = log.map(|log| log.kind);
+    let subject = log.and_then(|log| log.subject).map(|id| id.to_string());
+    let about = log.and_then(|log| log.about).map(|id| id.to_string());
+    let incident_id = log.and_then(|log| log.incident_id);
+    let messages = serde_json::to_string(
+        &std::iter::once(message)
+            .chain(followups)
+            .collect::<Vec<_>>(),
+    )?;
+    let now = now() as i64;
+
+    sqlx::query!(
+        "INSERT INTO maintenance_queue (guild_id, channel_id, kind, subject_id, about_message_id, incident_id, messages, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        guild_id,
+        channel_id,
+        kind,
+        subject,
+        about,
+        incident_id,
+        messages,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Posts a log from one of the background tasks with the guild's footer for `log_type`,
+/// or queues it if its guild is in maintenance. Returns the posted message, or `None` if it was queued.
+pub async fn send(
+    http: &Http,
+    pool: &Pool<Sqlite>,
+    settings: &Settings,
+    guild_id: GuildId,
+    log_type: LogType,
+    channel_id: ChannelId,
+    mut embed: CreateEmbed,
+) -> Result<Option<MessageId>, crate::client::Error> {
+    if let Some(footer) = theme::footer(settings, guild_id, log_type, None).await {
+        embed = embed.footer(footer);
+    }
+
+    let message = CreateMessage::new().embed(embed);
+
+    if is_active(pool, guild_id).await? {
+        queue(pool, guild_id, channel_id, None, &message, &[]).await?;
+        return Ok(None);
+    }
+
+    Ok(Some(channel_id.send_message(http, message).await?.id))
+}
+
+/// Posts queued logs, oldest first, once their guild is out of maintenance.
+pub async fn flush(http: Arc<Http>, pool: Pool<Sqlite>) {
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let queued = match sqlx::query!(
+            "SELECT id, guild_id, channel_id, kind, subject_id, about_message_id, incident_id, messages
+            FROM maintenance_queue ORDER BY id"
+        )
+        .fetch_all(&pool)
+        .await
+        {
+            Ok(queued) => queued,
+            Err(error) => {
+                println!("Failed to fetch the maintenance queue: {error}");
+                continue;
+            }
+        };
+
+        let mut paused = HashMap::new();
+
+        for row in queued {
+            let (Ok(guild_id), Ok(channel_id)) = (
+                GuildId::from_str(&row.guild_id),
+                ChannelId::from_str(&row.channel_id),
+            ) else {
+                continue;
+            };
+
+            if let Entry::Vacant(entry) = paused.entry(guild_id) {
+                entry.insert(is_active(&pool, guild_id).await.unwrap_or(true));
+            }
+
+            if paused[&guild_id] {
+                continue;
+            }
+
+            // taken off the queue first, so a log that can't be posted doesn't hold up everything after it.
+            if let Err(error) = sqlx::query!("DELETE FROM maintenance_queue WHERE id = ?", row.id)
+                .execute(&pool)
+                .await
+            {
+                println!("Failed to take a queued log: {error}");
+                continue;
+            }
+
+            let messages = serde_json::from_str::<Vec<Value>>(&row.messages).unwrap_or_default();
+            let mut messages = messages.into_iter();
+
+            let Some(first) = messages.next() else {
+                continue;
+            };
+
+            let message = match http.send_message(channel_id, Vec::new(), &first).await {
+                Ok(message) => message,
+                Err(error) => {
+                    println!("Failed to post a queued log: {error}");
+                    continue;
+                }
+            };
+
+            let Some(kind) = &row.kind else {
+                continue;
+            };
+
+            if let Err(error) = log_messages::record(
+                &pool,
+                channel_id,
+                message.id,
+                &RecordedLog {
+                    guild_id,
+                    kind,
+                    subject: row
+                        .subject_id
+                        .as_deref()
+                        .and_then(|id| UserId::from_str(id).ok()),
+                    about: row
+                        .about_message_id
+                        .as_deref()
+                        .and_then(|id| MessageId::from_str(id).ok()),
+                    incident_id: row.incident_id,
+                },
+            )
+            .await
+            {
+                println!("Failed to record a queued log: {error}");
+            }
+
+            for mut followup in messages {
+                followup["message_reference"] = json!({ "message_id": message.id });
+
+                if let Err(error) = http.send_message(channel_id, Vec::new(), &followup).await {
+                    println!("Failed to post a queued followup: {error}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use serde_json::{json, Value};
+use serenity::{
+    all::{
+        audit_log::Action, AuditLogs, ChannelId, GuildChannel, GuildId, Http, Member, Message,
+        MessageId, Role, RoleId, UserId,
+    },
+    async_trait,
+    builder::{CreateAttachment, CreateMessage},
+};
+use sqlx::sqlite::SqlitePoolOptions;
+
+use super::{
+    context::{Delivery, EventContext},
+    now,
+};
+use crate::client::{Data, Error};
+
+/// Reads a gateway payload (the `d` of a dispatch) from `tests/fixtures`.
+pub fn fixture(name: &str) -> Value {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    let payload = fs::read_to_string(&path)
+        .unwrap_or_else(|error| panic!("Failed to read fixture {}: {error}", path.display()));
+
+    serde_json::from_str(&payload)
+        .unwrap_or_else(|error| panic!("Fixture {name} isn't valid JSON: {error}"))
+}
+
+/// Dates a raw audit log entry to now, since audit lookups only consider recent entries.
+pub fn recent(mut entry: Value) -> Value {
+    const DISCORD_EPOCH_MS: u64 = 1_420_070_400_000;
+
+    entry["id"] = json!(((now() * 1000 - DISCORD_EPOCH_MS) << 22).to_string());
+    entry
+}
+
+/// Bot state backed by a fresh in-memory database with every migration applied.
+pub async fn data() -> Data {
+    // every connection to an in-memory database gets its own, so the pool has to stick to one.
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!().run(&pool).await.unwrap();
+
+    Data::new(pool)
+}
+
+/// A log message the pipeline sent.
+#[derive(Debug)]
+pub struct Sent {
+    pub channel_id: ChannelId,
+    /// The message as it would have been posted to Discord's API.
+    pub payload: Value,
+}
+
+impl Sent {
+    /// The value of the first embed's field called `name`.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.payload["embeds"][0]["fields"]
+            .as_array()?
+            .iter()
+            .find(|field| field["name"] == name)?["value"]
+            .as_str()
+    }
+}
+
+/// Stands in for Discord in tests: serves a cache and audit log set up by the test, and records what's sent
+/// instead of posting it.
+pub struct MockDiscord {
+    pub messages: Vec<Message>,
+    pub members: HashMap<GuildId, Vec<Member>>,
+    /// Raw audit log entries, as in the `audit_log_entries` of Discord's response.
+    pub audit_log: Vec<Value>,
+    /// How many audit log lookups come back empty before `audit_log` shows up, like Discord writing entries late.
+    pub audit_log_lag: usize,
+    pub audit_log_lookups: AtomicUsize,
+    pub sent: Mutex<Vec<Sent>>,
+    pub pinned: Mutex<Vec<(ChannelId, MessageId)>>,
+    pub direct_messages: Mutex<Vec<(UserId, Value)>>,
+    next_id: AtomicU64,
+    // never authenticated, so anything that does slip through to it fails rather than touching a real server.
+    http: Http,
+}
+
+impl MockDiscord {
+    pub fn new() -> Self {
+        Self {
+            messages: Vec::new(),
+            members: HashMap::new(),
+            audit_log: Vec::new(),
+            audit_log_lag: 0,
+            audit_log_lookups: AtomicUsize::new(0),
+            sent: Mutex::new(Vec::new()),
+            pinned: Mutex::new(Vec::new()),
+            direct_messages: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+            http: Http::new(""),
+        }
+    }
+
+    pub fn sent(&self) -> std::sync::MutexGuard<'_, Vec<Sent>> {
+        self.sent.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl EventContext for MockDiscord {
+    fn cached_message(&self, channel_id: ChannelId, message_id: MessageId) -> Option<Message> {
+        self.messages
+            .iter()
+            .find(|message| message.channel_id == channel_id && message.id == message_id)
+            .cloned()
+    }
+
+    fn cached_messages(&self, channel_id: ChannelId) -> Vec<Message> {
+        let mut messages = self
+            .messages
+            .iter()
+            .filter(|message| message.channel_id == channel_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        messages.sort_by_key(|message| message.id);
+        messages
+    }
+
+    fn cached_members(&self, guild_id: GuildId) -> Vec<Member> {
+        self.members.get(&guild_id).cloned().unwrap_or_default()
+    }
+
+    fn cached_member(&self, guild_id: GuildId, user_id: UserId) -> Option<Member> {
+        self.cached_members(guild_id)
+            .into_iter()
+            .find(|member| member.user.id == user_id)
+    }
+
+    fn guild_owner(&self, _guild_id: GuildId) -> Option<UserId> {
+        None
+    }
+
+    fn guild_name(&self, _guild_id: GuildId) -> Option<String> {
+        None
+    }
+
+    fn channel_guild(&self, channel_id: ChannelId) -> Option<GuildId> {
+        self.messages
+            .iter()
+            .find(|message| message.channel_id == channel_id)
+            .and_then(|message| message.guild_id)
+    }
+
+    fn cached_role(&self, _guild_id: GuildId, _role_id: RoleId) -> Option<Role> {
+        None
+    }
+
+    fn cached_channel(&self, _guild_id: GuildId, _channel_id: ChannelId) -> Option<GuildChannel> {
+        None
+    }
+
+    async fn message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Result<Message, Error> {
+        self.cached_message(channel_id, message_id)
+            .ok_or_else(|| format!("Unknown message {message_id}").into())
+    }
+
+    async fn audit_logs(
+        &self,
+        _guild_id: GuildId,
+        action: Option<Action>,
+        user_id: Option<UserId>,
+        limit: Option<u8>,
+    ) -> Result<AuditLogs, Error> {
+        let lagging = self.audit_log_lookups.fetch_add(1, Ordering::Relaxed) < self.audit_log_lag;
+
+        let entries = self
+            .audit_log
+            .iter()
+            .filter(|_| !lagging)
+            .filter(|entry| {
+                action.is_none_or(|action| entry["action_type"] == action.num())
+                    && user_id.is_none_or(|user_id| entry["user_id"] == user_id.to_string())
+            })
+            .take(limit.unwrap_or(50) as usize)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        Ok(serde_json::from_value(json!({
+            "audit_log_entries": entries,
+            "auto_moderation_rules": [],
+            "application_commands": [],
+            "guild_scheduled_events": [],
+            "integrations": [],
+            "threads": [],
+            "users": [],
+            "webhooks": [],
+        }))?)
+    }
+
+    async fn raw_audit_logs(
+        &self,
+        _guild_id: GuildId,
+        action: Action,
+        limit: Option<u8>,
+    ) -> Result<Vec<Value>, Error> {
+        Ok(self
+            .audit_log
+            .iter()
+            .filter(|entry| entry["action_type"] == action.num())
+            .take(limit.unwrap_or(50) as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn pins(&self, channel_id: ChannelId) -> Result<Vec<Message>, Error> {
+        Ok(self
+            .messages
+            .iter()
+            .filter(|message| message.channel_id == channel_id && message.pinned)
+            .rev()
+            .cloned()
+            .collect())
+    }
+
+    async fn download_attachment(&self, url: &str) -> Result<CreateAttachment, Error> {
+        Err(format!("Attachments aren't downloaded in tests: {url}").into())
+    }
+
+    async fn direct_message(&self, user_id: UserId, message: CreateMessage) -> Result<(), Error> {
+        self.direct_messages
+            .lock()
+            .unwrap()
+            .push((user_id, serde_json::to_value(message)?));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Delivery for MockDiscord {
+    fn http(&self) -> &Http {
+        &self.http
+    }
+
+    async fn send_message(
+        &self,
+        channel_id: ChannelId,
+        message: CreateMessage,
+    ) -> Result<MessageId, Error> {
+        let message_id = MessageId::new(self.next_id.fetch_add(1, Ordering::Relaxed));
+
+        self.sent.lock().unwrap().push(Sent {
+            channel_id,
+            payload: serde_json::to_value(message)?,
+        });
+
+        Ok(message_id)
+    }
+
+    async fn pin_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<(), Error> {
+        self.pinned.lock().unwrap().push((channel_id, message_id));
+        Ok(())
+    }
+}
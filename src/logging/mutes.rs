@@ -0,0 +1,203 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use serenity::{
+    all::{GuildId, Http, UserId},
+    builder::CreateEmbed,
+};
+use sqlx::{Pool, Sqlite};
+
+use super::{maintenance, now, theme};
+use crate::{commands::LogType, settings::Settings};
+
+const RESUME_INTERVAL: Duration = Duration::from_secs(60);
+
+pub struct RouteMute {
+    pub log_type: LogType,
+    pub muted_by: UserId,
+    pub reason: Option<String>,
+    pub muted_at: i64,
+    pub until: i64,
+    pub lifted_by: Option<UserId>,
+}
+
+pub async fn is_muted(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    log_type: LogType,
+) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let route = log_type.as_column_name();
+    let now = now() as i64;
+
+    let row = sqlx::query!(
+        "SELECT id FROM route_mutes WHERE guild_id = ? AND route = ? AND until > ? LIMIT 1",
+        guild_id,
+        route,
+        now
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.is_some())
+}
+
+/// Mutes `log_type` until `until`, replacing any mute already active on it.
+pub async fn mute(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    log_type: LogType,
+    muted_by: UserId,
+    reason: Option<String>,
+    until: i64,
+) -> Result<(), sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let route = log_type.as_column_name();
+    let muted_by = muted_by.to_string();
+    let now = now() as i64;
+
+    // the replaced mute just ends here; nobody lifted it, and the route isn't resuming.
+    sqlx::query!(
+        "UPDATE route_mutes SET until = ?, resumed = TRUE WHERE guild_id = ? AND route = ? AND until > ?",
+        now,
+        guild_id,
+        route,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query!(
+        "INSERT INTO route_mutes (guild_id, route, muted_by, reason, muted_at, until) VALUES (?, ?, ?, ?, ?, ?)",
+        guild_id,
+        route,
+        muted_by,
+        reason,
+        now,
+        until
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Ends any active mute on `log_type` early. Returns whether there was one.
+pub async fn lift(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    log_type: LogType,
+    lifted_by: UserId,
+) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let route = log_type.as_column_name();
+    let lifted_by = lifted_by.to_string();
+    let now = now() as i64;
+
+    // lifted mutes are resumed by whoever lifted them, so the background task doesn't announce them again.
+    let result = sqlx::query!(
+        "UPDATE route_mutes SET until = ?, lifted_by = ?, resumed = TRUE WHERE guild_id = ? AND route = ? AND until > ?",
+        now,
+        lifted_by,
+        guild_id,
+        route,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// The most recent mutes in a guild, active ones included.
+pub async fn recent(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    limit: i64,
+) -> Result<Vec<RouteMute>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    let rows = sqlx::query!(
+        "SELECT route, muted_by, reason, muted_at, until, lifted_by FROM route_mutes WHERE guild_id = ? ORDER BY id DESC LIMIT ?",
+        guild_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(RouteMute {
+                log_type: LogType::from_column_name(&row.route)?,
+                muted_by: UserId::from_str(&row.muted_by).ok()?,
+                reason: row.reason,
+                muted_at: row.muted_at,
+                until: row.until,
+                lifted_by: row.lifted_by.and_then(|id| UserId::from_str(&id).ok()),
+            })
+        })
+        .collect())
+}
+
+/// Periodically lets log channels know their route is live again once a mute runs out.
+pub async fn announce_resumed(http: Arc<Http>, pool: Pool<Sqlite>, settings: Settings) {
+    let mut interval = tokio::time::interval(RESUME_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let now = now() as i64;
+        let expired = match sqlx::query!(
+            "SELECT id, guild_id, route, muted_by FROM route_mutes WHERE until <= ? AND NOT resumed",
+            now
+        )
+        .fetch_all(&pool)
+        .await
+        {
+            Ok(expired) => expired,
+            Err(error) => {
+                println!("Failed to fetch expired route mutes: {error}");
+                continue;
+            }
+        };
+
+        for row in expired {
+            if let Err(error) =
+                sqlx::query!("UPDATE route_mutes SET resumed = TRUE WHERE id = ?", row.id)
+                    .execute(&pool)
+                    .await
+            {
+                println!("Failed to mark route mute as resumed: {error}");
+                continue;
+            }
+
+            let (Ok(guild_id), Some(log_type)) = (
+                GuildId::from_str(&row.guild_id),
+                LogType::from_column_name(&row.route),
+            ) else {
+                continue;
+            };
+
+            let Some(channel) = log_type.fetch_channel(&pool, guild_id).await else {
+                continue;
+            };
+
+            let style = theme::style(&settings, guild_id, "changed").await;
+
+            let embed = CreateEmbed::new()
+                .title(format!("{} Logging resumed", style.emoji))
+                .colour(style.colour)
+                .description(format!(
+                    "The mute <@{}> placed on {} has run out. Logs will be posted here again.",
+                    row.muted_by,
+                    log_type.to_string()
+                ));
+
+            if let Err(error) =
+                maintenance::send(&http, &pool, &settings, guild_id, log_type, channel, embed).await
+            {
+                println!("Failed to announce resumed route: {error}");
+            }
+        }
+    }
+}
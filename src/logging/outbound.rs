@@ -0,0 +1,121 @@
+use std::{
+    fmt::Display,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use reqwest::{redirect, Client, Url};
+
+use crate::settings::Settings;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Why a URL set by a guild can't be sent requests.
+#[derive(Debug)]
+pub enum UnsafeUrl {
+    Invalid,
+    NotHttps,
+    Unresolvable,
+    NotPublic(IpAddr),
+}
+
+impl Display for UnsafeUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid => write!(f, "it isn't a valid URL"),
+            Self::NotHttps => write!(f, "it doesn't use https"),
+            Self::Unresolvable => write!(f, "its host couldn't be resolved"),
+            Self::NotPublic(address) => write!(
+                f,
+                "its host resolves to {address}, which isn't a public address"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UnsafeUrl {}
+
+fn is_public_v4(address: Ipv4Addr) -> bool {
+    let [first, second, ..] = address.octets();
+
+    !(address.is_unspecified()
+        || address.is_loopback()
+        || address.is_private()
+        || address.is_link_local()
+        || address.is_broadcast()
+        || address.is_documentation()
+        || first == 0
+        // carrier-grade NAT, 100.64.0.0/10
+        || (first == 100 && (64..128).contains(&second)))
+}
+
+fn is_public_v6(address: Ipv6Addr) -> bool {
+    if let Some(mapped) = address.to_ipv4_mapped() {
+        return is_public_v4(mapped);
+    }
+
+    let first = address.segments()[0];
+
+    !(address.is_unspecified()
+        || address.is_loopback()
+        // unique local, fc00::/7
+        || (first & 0xfe00) == 0xfc00
+        // link-local, fe80::/10
+        || (first & 0xffc0) == 0xfe80)
+}
+
+fn is_public(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => is_public_v4(address),
+        IpAddr::V6(address) => is_public_v6(address),
+    }
+}
+
+/// Builds a client that can only reach `url` at the public addresses its host resolves to right now.
+///
+/// Guild admins can point webhooks anywhere, so without this a shared instance could be made to send
+/// requests into its operator's own network. Connections are pinned to the checked addresses and redirects
+/// aren't followed, so neither a DNS change nor a redirect can get around the check afterwards.
+pub async fn client_for(url: &str) -> Result<Client, UnsafeUrl> {
+    let parsed = Url::parse(url).map_err(|_| UnsafeUrl::Invalid)?;
+
+    if parsed.scheme() != "https" {
+        return Err(UnsafeUrl::NotHttps);
+    }
+
+    let host = parsed.host_str().ok_or(UnsafeUrl::Invalid)?;
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let addresses = tokio::net::lookup_host((host.trim_matches(['[', ']']), port))
+        .await
+        .map_err(|_| UnsafeUrl::Unresolvable)?
+        .collect::<Vec<SocketAddr>>();
+
+    if addresses.is_empty() {
+        return Err(UnsafeUrl::Unresolvable);
+    }
+
+    if let Some(address) = addresses.iter().find(|address| !is_public(address.ip())) {
+        return Err(UnsafeUrl::NotPublic(address.ip()));
+    }
+
+    Client::builder()
+        .timeout(TIMEOUT)
+        .redirect(redirect::Policy::none())
+        .resolve_to_addrs(host, &addresses)
+        .build()
+        .map_err(|_| UnsafeUrl::Invalid)
+}
+
+/// Like [`client_for`], except that `url` is trusted as is when it's the operator's own default for the
+/// setting `key`. Operators may well want alerts to go to a service on their own network.
+pub async fn client_for_setting(key: &str, url: &str) -> Result<Client, UnsafeUrl> {
+    if Settings::operator_default(key).as_deref() == Some(url) {
+        return Client::builder()
+            .timeout(TIMEOUT)
+            .build()
+            .map_err(|_| UnsafeUrl::Invalid);
+    }
+
+    client_for(url).await
+}
@@ -0,0 +1,22 @@
+use serenity::all::{ChannelId, GuildId};
+
+use super::now;
+use crate::settings::{keys, Settings};
+
+pub struct PanicMode {
+    /// Where logs are escalated to while panic mode is on. Logs keep their usual routes if unset.
+    pub staff_channel: Option<ChannelId>,
+}
+
+/// Panic mode logs everything: filters, damping and route mutes are all bypassed until it runs out.
+pub async fn active(settings: &Settings, guild_id: GuildId) -> Option<PanicMode> {
+    let until = settings.get(guild_id, &keys::PANIC_UNTIL).await;
+
+    if until <= now() as i64 {
+        return None;
+    }
+
+    Some(PanicMode {
+        staff_channel: settings.get(guild_id, &keys::STAFF_CHANNEL).await,
+    })
+}
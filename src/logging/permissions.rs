@@ -0,0 +1,131 @@
+use serenity::all::{PermissionOverwrite, PermissionOverwriteType, Permissions, Role};
+
+use super::formatter::Severity;
+
+/// Permissions that let a member act on other members, channels or the guild itself.
+const MODERATION_PERMISSIONS: Permissions = Permissions::ADMINISTRATOR
+    .union(Permissions::BAN_MEMBERS)
+    .union(Permissions::KICK_MEMBERS)
+    .union(Permissions::MODERATE_MEMBERS)
+    .union(Permissions::MANAGE_CHANNELS)
+    .union(Permissions::MANAGE_GUILD)
+    .union(Permissions::MANAGE_ROLES)
+    .union(Permissions::MANAGE_MESSAGES)
+    .union(Permissions::MANAGE_WEBHOOKS);
+
+/// The subset that effectively hands over control of the guild.
+const DANGEROUS_PERMISSIONS: Permissions = Permissions::ADMINISTRATOR
+    .union(Permissions::BAN_MEMBERS)
+    .union(Permissions::MANAGE_GUILD)
+    .union(Permissions::MANAGE_ROLES);
+
+pub struct GrantContext {
+    pub permissions: Permissions,
+    /// Position of the highest granted role, so reviewers can see where it sits in the hierarchy.
+    pub highest_position: u16,
+    pub severity: Severity,
+}
+
+impl GrantContext {
+    pub fn describe(&self) -> String {
+        format!(
+            "Grants {} (highest role position {})",
+            self.permissions.get_permission_names().join(", "),
+            self.highest_position
+        )
+    }
+}
+
+/// Works out which moderation-relevant permissions `roles` grant, and how severe granting them is.
+/// Returns `None` if they grant nothing of interest.
+pub fn grant_context(roles: &[Role]) -> Option<GrantContext> {
+    let permissions = roles
+        .iter()
+        .fold(Permissions::empty(), |acc, role| acc | role.permissions)
+        & MODERATION_PERMISSIONS;
+
+    if permissions.is_empty() {
+        return None;
+    }
+
+    let severity = if permissions.intersects(DANGEROUS_PERMISSIONS) {
+        Severity::Critical
+    } else {
+        Severity::Warning
+    };
+
+    let highest_position = roles
+        .iter()
+        .filter(|role| role.permissions.intersects(MODERATION_PERMISSIONS))
+        .map(|role| role.position)
+        .max()
+        .unwrap_or_default();
+
+    Some(GrantContext {
+        permissions,
+        highest_position,
+        severity,
+    })
+}
+
+fn permission_lines(old: Permissions, new: Permissions, prefix: &str) -> Vec<String> {
+    let added = (new - old)
+        .get_permission_names()
+        .into_iter()
+        .map(|name| format!("+ {prefix}{name}"));
+    let removed = (old - new)
+        .get_permission_names()
+        .into_iter()
+        .map(|name| format!("- {prefix}{name}"));
+
+    added.chain(removed).collect()
+}
+
+fn diff_block(lines: Vec<String>) -> Option<String> {
+    (!lines.is_empty()).then(|| format!("```diff\n{}\n```", lines.join("\n")))
+}
+
+/// Renders a permission bitset change (e.g. a role update) as a `diff` code block, or `None` if nothing changed.
+pub fn render_permission_diff(old: Permissions, new: Permissions) -> Option<String> {
+    diff_block(permission_lines(old, new, ""))
+}
+
+/// Renders channel permission overwrite changes, one `(target mention, diff block)` pair per changed overwrite.
+pub fn render_overwrite_diff(
+    old: &[PermissionOverwrite],
+    new: &[PermissionOverwrite],
+) -> Vec<(String, String)> {
+    let mut targets = old.iter().map(|o| o.kind).collect::<Vec<_>>();
+    for overwrite in new {
+        if !targets.contains(&overwrite.kind) {
+            targets.push(overwrite.kind);
+        }
+    }
+
+    let find = |overwrites: &[PermissionOverwrite], kind| {
+        overwrites
+            .iter()
+            .find(|o| o.kind == kind)
+            .map(|o| (o.allow, o.deny))
+            .unwrap_or((Permissions::empty(), Permissions::empty()))
+    };
+
+    targets
+        .into_iter()
+        .filter_map(|kind| {
+            let (old_allow, old_deny) = find(old, kind);
+            let (new_allow, new_deny) = find(new, kind);
+
+            let mut lines = permission_lines(old_allow, new_allow, "allow ");
+            lines.extend(permission_lines(old_deny, new_deny, "deny "));
+
+            let target = match kind {
+                PermissionOverwriteType::Member(id) => format!("<@{id}>"),
+                PermissionOverwriteType::Role(id) => format!("<@&{id}>"),
+                _ => "Unknown".into(),
+            };
+
+            Some((target, diff_block(lines)?))
+        })
+        .collect()
+}
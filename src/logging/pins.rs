@@ -0,0 +1,41 @@
+use serenity::all::{ChannelId, GuildId, MessageId};
+use sqlx::{Pool, Sqlite};
+
+use super::now;
+use crate::client::Error;
+
+/// Stores `pins` as `channel_id`'s pinned messages, returning the previous set if it was known.
+pub async fn replace(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    pins: &[MessageId],
+) -> Result<Option<Vec<MessageId>>, Error> {
+    let channel_id = channel_id.to_string();
+    let guild_id = guild_id.to_string();
+    let message_ids = serde_json::to_string(pins)?;
+    let now = now() as i64;
+
+    let previous = sqlx::query!(
+        "SELECT message_ids FROM pin_snapshots WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    sqlx::query!(
+        "INSERT INTO pin_snapshots (channel_id, guild_id, message_ids, taken_at) VALUES (?, ?, ?, ?)
+        ON CONFLICT (channel_id) DO UPDATE SET message_ids = excluded.message_ids, taken_at = excluded.taken_at",
+        channel_id,
+        guild_id,
+        message_ids,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(match previous {
+        Some(row) => Some(serde_json::from_str(&row.message_ids)?),
+        None => None,
+    })
+}
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use serde_json::json;
+use serenity::all::{ChannelId, GuildId, MessageId};
+
+use super::outbound;
+use crate::settings::{keys, Settings};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const PUSHOVER_API: &str = "https://api.pushover.net/1/messages.json";
+
+/// Pushes a critical alert to the guild's ntfy topic and/or Pushover account, so it reaches operators away from Discord.
+/// Both fall back to operator-wide defaults (`LOGSALOT_DEFAULT_NTFY_URL`, ...). Runs in the background.
+pub async fn notify(
+    settings: &Settings,
+    guild_id: GuildId,
+    title: &str,
+    body: String,
+    alert: Option<(ChannelId, MessageId)>,
+) {
+    let ntfy_url = settings.get(guild_id, &keys::NTFY_URL).await;
+    let pushover_token = settings.get(guild_id, &keys::PUSHOVER_TOKEN).await;
+    let pushover_user = settings.get(guild_id, &keys::PUSHOVER_USER).await;
+
+    if ntfy_url.is_empty() && (pushover_token.is_empty() || pushover_user.is_empty()) {
+        return;
+    }
+
+    let title = title.to_string();
+    let link = alert.map(|(channel_id, message_id)| message_id.link(channel_id, Some(guild_id)));
+
+    tokio::spawn(async move {
+        if !ntfy_url.is_empty() {
+            match outbound::client_for_setting(keys::NTFY_URL.name, &ntfy_url).await {
+                Ok(ntfy) => push_ntfy(&ntfy, &ntfy_url, &title, &body, link.as_deref()).await,
+                Err(error) => println!("Refusing to push alert to ntfy at {ntfy_url}: {error}"),
+            }
+        }
+
+        if !pushover_token.is_empty() && !pushover_user.is_empty() {
+            let result = reqwest::Client::new()
+                .post(PUSHOVER_API)
+                .timeout(TIMEOUT)
+                .json(&json!({
+                    "token": pushover_token,
+                    "user": pushover_user,
+                    "title": title,
+                    "message": body,
+                    "url": link,
+                    "priority": 1,
+                }))
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+
+            if let Err(error) = result {
+                println!("Failed to push alert to Pushover: {error}");
+            }
+        }
+    });
+}
+
+async fn push_ntfy(
+    client: &reqwest::Client,
+    url: &str,
+    title: &str,
+    body: &str,
+    link: Option<&str>,
+) {
+    let mut request = client
+        .post(url)
+        .header("Title", title)
+        .header("Priority", "urgent")
+        .header("Tags", "rotating_light")
+        .body(body.to_string());
+
+    if let Some(link) = link {
+        request = request.header("Click", link);
+    }
+
+    if let Err(error) = request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+    {
+        println!("Failed to push alert to ntfy: {error}");
+    }
+}
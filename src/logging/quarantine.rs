@@ -0,0 +1,62 @@
+use serde_json::json;
+use serenity::all::{ChannelId, GuildId, MessageId, UserId};
+
+use super::{formatter::Severity, now, outbound};
+use crate::settings::{keys, Settings};
+
+/// What gets handed to external anti-nuke tooling when a detection fires.
+pub struct Detection {
+    pub guild_id: GuildId,
+    /// The formatter kind, e.g. `mass_deletion`.
+    pub kind: &'static str,
+    pub severity: Severity,
+    pub executor: Option<UserId>,
+    pub incident_id: Option<i64>,
+    pub message: Option<(ChannelId, MessageId)>,
+}
+
+/// POSTs `detection` to the guild's quarantine webhook, if one is configured.
+/// Runs in the background; logsalot never acts on the response.
+pub async fn notify(settings: &Settings, detection: Detection) {
+    let url = settings
+        .get(detection.guild_id, &keys::QUARANTINE_WEBHOOK_URL)
+        .await;
+
+    if url.is_empty() {
+        return;
+    }
+
+    let payload = json!({
+        "guild_id": detection.guild_id.to_string(),
+        "kind": detection.kind,
+        "severity": format!("{:?}", detection.severity).to_lowercase(),
+        "executor_id": detection.executor.map(|id| id.to_string()),
+        "incident_id": detection.incident_id,
+        "message_url": detection
+            .message
+            .map(|(channel_id, message_id)| message_id.link(channel_id, Some(detection.guild_id))),
+        "detected_at": now(),
+    });
+
+    tokio::spawn(async move {
+        let client =
+            match outbound::client_for_setting(keys::QUARANTINE_WEBHOOK_URL.name, &url).await {
+                Ok(client) => client,
+                Err(error) => {
+                    println!("Refusing to notify quarantine webhook {url}: {error}");
+                    return;
+                }
+            };
+
+        let result = client
+            .post(&url)
+            .json(&payload)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        if let Err(error) = result {
+            println!("Failed to notify quarantine webhook: {error}");
+        }
+    });
+}
@@ -0,0 +1,246 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use serenity::{
+    all::{ChannelId, GuildId, Http},
+    builder::CreateEmbed,
+};
+use sqlx::{Pool, Sqlite};
+
+use super::{maintenance, now, theme};
+use crate::{
+    commands::LogType,
+    settings::{keys, Settings},
+};
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+/// Digests list at most this many logs; the rest are only counted.
+const DIGEST_LINES: usize = 25;
+
+fn window_key(log_type: LogType) -> String {
+    format!("quiet_hours.{}", log_type.as_column_name())
+}
+
+/// Parses a time of day like `22:00` or `7` into minutes since midnight.
+pub fn parse_time(input: &str) -> Option<i64> {
+    let (hours, minutes) = input.trim().split_once(':').unwrap_or((input.trim(), "0"));
+    let (hours, minutes) = (hours.parse::<i64>().ok()?, minutes.parse::<i64>().ok()?);
+
+    ((0..24).contains(&hours) && (0..60).contains(&minutes)).then_some(hours * 60 + minutes)
+}
+
+pub fn format_time(minutes: i64) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Parses a UTC offset like `+02:00`, `-5` or `+5:30` into minutes.
+pub fn parse_offset(input: &str) -> Option<i64> {
+    let input = input.trim();
+    let (sign, rest) = match input.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, input.strip_prefix('+').unwrap_or(input)),
+    };
+
+    let minutes = parse_time(rest).filter(|minutes| *minutes <= 14 * 60)?;
+
+    Some(sign * minutes)
+}
+
+pub fn format_offset(offset: i64) -> String {
+    format!(
+        "UTC{}{}",
+        if offset < 0 { '-' } else { '+' },
+        format_time(offset.abs())
+    )
+}
+
+/// The quiet hours configured for `log_type`, as `(start, end)` in minutes since local midnight.
+pub async fn window(
+    settings: &Settings,
+    guild_id: GuildId,
+    log_type: LogType,
+) -> Option<(i64, i64)> {
+    let raw = settings.get_raw(guild_id, &window_key(log_type)).await?;
+    let (start, end) = raw.split_once('-')?;
+
+    Some((start.parse().ok()?, end.parse().ok()?))
+}
+
+pub async fn set_window(
+    settings: &Settings,
+    guild_id: GuildId,
+    log_type: LogType,
+    window: Option<(i64, i64)>,
+) -> Result<(), crate::client::Error> {
+    match window {
+        Some((start, end)) => {
+            settings
+                .set_raw(guild_id, &window_key(log_type), format!("{start}-{end}"))
+                .await?
+        }
+        None => settings.unset(guild_id, &window_key(log_type)).await?,
+    }
+
+    Ok(())
+}
+
+/// Whether `log_type` is currently in its quiet hours, in the guild's local time.
+pub async fn is_quiet(settings: &Settings, guild_id: GuildId, log_type: LogType) -> bool {
+    let Some((start, end)) = window(settings, guild_id, log_type).await else {
+        return false;
+    };
+
+    let offset = settings.get(guild_id, &keys::TIMEZONE_OFFSET).await;
+    let minute = (now() as i64 / 60 + offset).rem_euclid(24 * 60);
+
+    if start <= end {
+        (start..end).contains(&minute)
+    } else {
+        // windows like 22:00-07:00 wrap around midnight.
+        minute >= start || minute < end
+    }
+}
+
+/// Holds a log back for the route's next digest instead of posting it.
+pub async fn hold(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    log_type: LogType,
+    channel_id: ChannelId,
+    kind: &str,
+    embed: &CreateEmbed,
+) -> Result<(), crate::client::Error> {
+    let guild_id = guild_id.to_string();
+    let route = log_type.as_column_name();
+    let channel_id = channel_id.to_string();
+    let embed = serde_json::to_string(embed)?;
+    let now = now() as i64;
+
+    sqlx::query!(
+        "INSERT INTO digest_entries (guild_id, route, channel_id, kind, embed, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        guild_id,
+        route,
+        channel_id,
+        kind,
+        embed,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// One line per held log: when it happened, its title and the start of its description.
+fn digest_line(embed: &str, created_at: i64) -> String {
+    let embed = serde_json::from_str::<serde_json::Value>(embed).unwrap_or_default();
+    let title = embed["title"].as_str().unwrap_or("Log");
+    let description = embed["description"]
+        .as_str()
+        .unwrap_or_default()
+        .lines()
+        .next()
+        .unwrap_or_default();
+
+    let description = match description.char_indices().nth(120) {
+        Some((index, _)) => format!("{}…", &description[..index]),
+        None => description.to_string(),
+    };
+
+    format!("<t:{created_at}:t> **{title}** {description}")
+}
+
+/// Periodically posts the logs held back during quiet hours, once each route's quiet hours are over.
+pub async fn flush(http: Arc<Http>, pool: Pool<Sqlite>, settings: Settings) {
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let routes =
+            match sqlx::query!("SELECT DISTINCT guild_id, route, channel_id FROM digest_entries")
+                .fetch_all(&pool)
+                .await
+            {
+                Ok(routes) => routes,
+                Err(error) => {
+                    println!("Failed to fetch held digest entries: {error}");
+                    continue;
+                }
+            };
+
+        for row in routes {
+            let (Ok(guild_id), Some(log_type), Ok(channel_id)) = (
+                GuildId::from_str(&row.guild_id),
+                LogType::from_column_name(&row.route),
+                ChannelId::from_str(&row.channel_id),
+            ) else {
+                continue;
+            };
+
+            if is_quiet(&settings, guild_id, log_type).await {
+                continue;
+            }
+
+            let entries = match sqlx::query!(
+                "DELETE FROM digest_entries WHERE guild_id = ? AND route = ? AND channel_id = ?
+                RETURNING embed, created_at",
+                row.guild_id,
+                row.route,
+                row.channel_id
+            )
+            .fetch_all(&pool)
+            .await
+            {
+                Ok(entries) => entries,
+                Err(error) => {
+                    println!("Failed to take held digest entries: {error}");
+                    continue;
+                }
+            };
+
+            if entries.is_empty() {
+                continue;
+            }
+
+            let mut entries = entries
+                .into_iter()
+                .map(|entry| (entry.created_at, entry.embed))
+                .collect::<Vec<_>>();
+            entries.sort_by_key(|(created_at, _)| *created_at);
+
+            let mut lines = entries
+                .iter()
+                .take(DIGEST_LINES)
+                .map(|(created_at, embed)| digest_line(embed, *created_at))
+                .collect::<Vec<_>>();
+
+            if entries.len() > DIGEST_LINES {
+                lines.push(format!("…and {} more.", entries.len() - DIGEST_LINES));
+            }
+
+            let style = theme::style(&settings, guild_id, "changed").await;
+
+            let embed = CreateEmbed::new()
+                .title(format!("{} Quiet hours digest", style.emoji))
+                .colour(style.colour)
+                .description(format!(
+                    "{} {} held back during quiet hours:\n\n{}",
+                    entries.len(),
+                    if entries.len() == 1 {
+                        "log was"
+                    } else {
+                        "logs were"
+                    },
+                    lines.join("\n")
+                ));
+
+            if let Err(error) = maintenance::send(
+                &http, &pool, &settings, guild_id, log_type, channel_id, embed,
+            )
+            .await
+            {
+                println!("Failed to send quiet hours digest: {error}");
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::{collections::BTreeMap, fmt};
+
+use serenity::all::{ChannelId, GuildId};
+use whatlang::Lang;
+
+use super::{
+    language::{self, LanguageRoute},
+    mutes,
+};
+use crate::{client::Data, commands::LogType, settings::keys};
+
+/// Where a kind of log ends up.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Destination {
+    Nowhere,
+    Ignored,
+    Muted(ChannelId),
+    Channel(ChannelId),
+}
+
+impl fmt::Display for Destination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Nowhere => write!(f, "nowhere"),
+            Self::Ignored => write!(f, "ignored"),
+            Self::Muted(channel) => write!(f, "<#{channel}> (muted)"),
+            Self::Channel(channel) => write!(f, "<#{channel}>"),
+        }
+    }
+}
+
+/// Where every kind of log is delivered in a guild, keyed by kind.
+/// With a `language`, logs about content in that language are followed through its `/language` rules.
+pub struct Routing {
+    pub language: Option<Lang>,
+    destinations: BTreeMap<&'static str, Destination>,
+}
+
+impl Routing {
+    pub async fn snapshot(
+        data: &Data,
+        guild_id: GuildId,
+        language: Option<Lang>,
+    ) -> Result<Self, sqlx::Error> {
+        // languages aren't detected at all with tags off, so their rules don't apply either.
+        let language_route = match language {
+            Some(language) if data.settings.get(guild_id, &keys::LANGUAGE_TAGS).await => {
+                language::route(&data.settings, guild_id, language).await
+            }
+            _ => LanguageRoute::Default,
+        };
+
+        let mut destinations = BTreeMap::new();
+
+        for log_type in LogType::ALL {
+            let channel = log_type.fetch_channel(&data.pool, guild_id).await;
+            let muted = mutes::is_muted(&data.pool, guild_id, log_type).await?;
+
+            for formatter in data.formatters.all() {
+                if formatter.default_route().as_column_name() != log_type.as_column_name() {
+                    continue;
+                }
+
+                let channel = match (&language_route, formatter.detects_language()) {
+                    (LanguageRoute::Ignored, true) => {
+                        destinations.insert(formatter.kind(), Destination::Ignored);
+                        continue;
+                    }
+                    (LanguageRoute::Channel(channel), true) => Some(*channel),
+                    _ => channel,
+                };
+
+                let destination = match channel {
+                    None => Destination::Nowhere,
+                    Some(channel) if muted => Destination::Muted(channel),
+                    Some(channel) => Destination::Channel(channel),
+                };
+
+                destinations.insert(formatter.kind(), destination);
+            }
+        }
+
+        Ok(Self {
+            language,
+            destinations,
+        })
+    }
+
+    /// Describes which kinds moved between `self` and `after`, one line per move. Empty if nothing moved.
+    pub fn changes(&self, after: &Self) -> Vec<String> {
+        let mut moves: BTreeMap<(Destination, Destination), Vec<&str>> = BTreeMap::new();
+
+        for (kind, before) in &self.destinations {
+            let after = after
+                .destinations
+                .get(kind)
+                .copied()
+                .unwrap_or(Destination::Nowhere);
+
+            if *before != after {
+                moves.entry((*before, after)).or_default().push(kind);
+            }
+        }
+
+        moves
+            .into_iter()
+            .map(|((before, after), kinds)| {
+                format!(
+                    "{before} → **{after}**: {}",
+                    kinds
+                        .iter()
+                        .map(|kind| format!("`{kind}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+            .collect()
+    }
+}
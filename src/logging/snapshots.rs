@@ -0,0 +1,51 @@
+use std::{collections::HashMap, hash::Hash, sync::Mutex};
+
+use serenity::all::GuildId;
+
+/// The last set of emojis, stickers or soundboard sounds seen for each guild. The cache is already updated by the time
+/// `GuildEmojisUpdate` and `GuildStickersUpdate` are dispatched, and doesn't hold sounds at all,
+/// so the previous set has to be kept here to diff against.
+pub struct Snapshots<K, V> {
+    guilds: Mutex<HashMap<GuildId, HashMap<K, V>>>,
+}
+
+impl<K, V> Default for Snapshots<K, V> {
+    fn default() -> Self {
+        Self {
+            guilds: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Snapshots<K, V> {
+    /// Stores `current` as the guild's set, returning the previous one if it was known.
+    pub fn replace(&self, guild_id: GuildId, current: &HashMap<K, V>) -> Option<HashMap<K, V>> {
+        self.guilds
+            .lock()
+            .unwrap()
+            .insert(guild_id, current.clone())
+    }
+
+    /// Stores a single item, returning the previous version if it was known.
+    pub fn insert(&self, guild_id: GuildId, key: K, value: V) -> Option<V> {
+        self.guilds
+            .lock()
+            .unwrap()
+            .entry(guild_id)
+            .or_default()
+            .insert(key, value)
+    }
+
+    pub fn get(&self, guild_id: GuildId, key: &K) -> Option<V> {
+        self.guilds
+            .lock()
+            .unwrap()
+            .get(&guild_id)?
+            .get(key)
+            .cloned()
+    }
+
+    pub fn remove(&self, guild_id: GuildId, key: &K) -> Option<V> {
+        self.guilds.lock().unwrap().get_mut(&guild_id)?.remove(key)
+    }
+}
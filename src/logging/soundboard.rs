@@ -0,0 +1,157 @@
+use serde_json::Value;
+use serenity::{
+    all::{Context, Event, GuildId, RawEventHandler, UserId},
+    async_trait,
+};
+
+use super::{
+    anomalies,
+    context::Delivery,
+    deliver,
+    formatters::{SoundCreate, SoundDelete, SoundUpdate},
+    EventFormatter, LogEntry,
+};
+use crate::client::{Data, Error};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoundboardSound {
+    pub id: u64,
+    pub name: String,
+    /// From 0 to 1.
+    pub volume: f64,
+    pub emoji: Option<String>,
+    /// Who uploaded the sound. Only known for sounds created while the bot was watching.
+    pub user: Option<UserId>,
+}
+
+fn snowflake(value: &Value) -> Option<u64> {
+    value.as_str()?.parse().ok()
+}
+
+impl SoundboardSound {
+    fn from_payload(payload: &Value) -> Option<Self> {
+        let emoji = match (
+            payload["emoji_name"].as_str(),
+            snowflake(&payload["emoji_id"]),
+        ) {
+            (_, Some(emoji_id)) => Some(format!("<:emoji:{emoji_id}>")),
+            (Some(emoji), None) => Some(emoji.to_string()),
+            (None, None) => None,
+        };
+
+        Some(Self {
+            id: snowflake(&payload["sound_id"])?,
+            name: payload["name"].as_str()?.to_string(),
+            volume: payload["volume"].as_f64().unwrap_or(1.0),
+            emoji,
+            user: snowflake(&payload["user"]["id"]).map(UserId::new),
+        })
+    }
+
+    pub fn url(&self) -> String {
+        format!("https://cdn.discordapp.com/soundboard-sounds/{}", self.id)
+    }
+}
+
+async fn log(
+    ctx: &dyn Delivery,
+    data: &Data,
+    formatter: &dyn EventFormatter,
+    entry: LogEntry,
+) -> Result<(), Error> {
+    anomalies::record(&data.pool, entry.guild_id, formatter.kind()).await?;
+    deliver(ctx, data, formatter, entry).await?;
+    Ok(())
+}
+
+/// Logs a soundboard gateway event, given its name and payload.
+pub async fn handle(
+    ctx: &dyn Delivery,
+    data: &Data,
+    kind: &str,
+    payload: &Value,
+) -> Result<(), Error> {
+    let Some(guild_id) = snowflake(&payload["guild_id"]).map(GuildId::new) else {
+        return Ok(());
+    };
+
+    match kind {
+        "GUILD_SOUNDBOARD_SOUND_CREATE" => {
+            let Some(sound) = SoundboardSound::from_payload(payload) else {
+                return Ok(());
+            };
+
+            data.sounds.insert(guild_id, sound.id, sound.clone());
+
+            let entry = SoundCreate::entry(ctx, data, guild_id, &sound).await;
+            log(ctx, data, &SoundCreate, entry).await
+        }
+        "GUILD_SOUNDBOARD_SOUND_UPDATE" => {
+            let Some(mut sound) = SoundboardSound::from_payload(payload) else {
+                return Ok(());
+            };
+
+            let previous = data.sounds.get(guild_id, &sound.id);
+            sound.user = sound
+                .user
+                .or(previous.as_ref().and_then(|previous| previous.user));
+            data.sounds.insert(guild_id, sound.id, sound.clone());
+
+            if previous.as_ref() == Some(&sound) {
+                return Ok(());
+            }
+
+            let entry = SoundUpdate::entry(ctx, data, guild_id, previous.as_ref(), &sound).await;
+            log(ctx, data, &SoundUpdate, entry).await
+        }
+        "GUILD_SOUNDBOARD_SOUND_DELETE" => {
+            let Some(sound_id) = snowflake(&payload["sound_id"]) else {
+                return Ok(());
+            };
+
+            let previous = data.sounds.remove(guild_id, &sound_id);
+
+            let entry = SoundDelete::entry(ctx, data, guild_id, sound_id, previous.as_ref()).await;
+            log(ctx, data, &SoundDelete, entry).await
+        }
+        // bulk updates only refresh what's known, e.g. after sounds became available again.
+        "GUILD_SOUNDBOARD_SOUNDS_UPDATE" => {
+            for sound in payload["soundboard_sounds"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(SoundboardSound::from_payload)
+            {
+                data.sounds.insert(guild_id, sound.id, sound);
+            }
+
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// serenity doesn't model soundboard events yet, so they only reach raw event handlers, as unknown events.
+pub struct SoundboardEvents;
+
+#[async_trait]
+impl RawEventHandler for SoundboardEvents {
+    async fn raw_event(&self, ctx: Context, event: Event) {
+        let Event::Unknown(event) = event else {
+            return;
+        };
+
+        if !event.kind.starts_with("GUILD_SOUNDBOARD_") {
+            return;
+        }
+
+        // raw handlers don't get the framework's data, so setup shares it through the context.
+        let Some(data) = ctx.data.read().await.get::<Data>().cloned() else {
+            return;
+        };
+
+        if let Err(error) = handle(&ctx, &data, &event.kind, &event.value).await {
+            println!("Failed to log soundboard event {}: {error}", event.kind);
+        }
+    }
+}
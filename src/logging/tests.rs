@@ -0,0 +1,798 @@
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+use serenity::all::{
+    AuditLogEntry, ChannelId, FullEvent, GuildId, Message, MessageDeleteEvent, MessageId,
+    MessageUpdateEvent, RoleId, UserId, VoiceChannelStatusUpdateEvent, VoiceState,
+};
+
+use super::{
+    audit_poll,
+    damping::{Damper, COOLDOWN, THRESHOLD, WINDOW},
+    formatters::MemberReport,
+    mock::{data, fixture, recent, MockDiscord},
+    mutes,
+    outbound::{self, UnsafeUrl},
+    process, soundboard,
+};
+use crate::{cases, commands::LogType, mod_stats, settings::keys};
+
+const GUILD: GuildId = GuildId::new(1100000000000000001);
+const CHAT_LOGS: ChannelId = ChannelId::new(1100000000000000098);
+const VOICE_LOGS: ChannelId = ChannelId::new(1100000000000000099);
+const SERVER_LOGS: ChannelId = ChannelId::new(1100000000000000097);
+const MODERATION_LOGS: ChannelId = ChannelId::new(1100000000000000096);
+const MEMBER_LOGS: ChannelId = ChannelId::new(1100000000000000095);
+
+fn cached_message() -> Message {
+    serde_json::from_value(fixture("message_create.json")).unwrap()
+}
+
+fn message_delete() -> FullEvent {
+    let event: MessageDeleteEvent = serde_json::from_value(fixture("message_delete.json")).unwrap();
+
+    FullEvent::MessageDelete {
+        channel_id: event.channel_id,
+        deleted_message_id: event.message_id,
+        guild_id: event.guild_id,
+    }
+}
+
+#[tokio::test]
+async fn deleted_messages_are_logged_with_their_content() {
+    let data = data().await;
+    LogType::Chat
+        .store_channel(&data.pool, GUILD, Some(CHAT_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord.messages.push(cached_message());
+
+    process(&discord, &message_delete(), &data).await.unwrap();
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].channel_id, CHAT_LOGS);
+    assert_eq!(sent[0].field("Content"), Some("Has anyone seen my crab?"));
+    assert_eq!(sent[0].field("Deleted by"), None);
+}
+
+#[tokio::test]
+async fn message_delete_formatter_builds_its_entry_from_the_cache() {
+    let data = data().await;
+    let formatter = data.formatters.by_kind("message_delete").unwrap();
+
+    let discord = MockDiscord::new();
+    assert!(formatter
+        .format(&discord, &message_delete(), &data)
+        .await
+        .is_none());
+
+    let mut discord = MockDiscord::new();
+    discord.messages.push(cached_message());
+
+    let message = cached_message();
+    let entry = formatter
+        .format(&discord, &message_delete(), &data)
+        .await
+        .unwrap();
+
+    assert_eq!(entry.guild_id, GUILD);
+    assert_eq!(entry.subject, Some(message.author.id));
+    assert_eq!(entry.message, Some(message.id));
+    assert_eq!(
+        entry.content,
+        vec![("Content", "Has anyone seen my crab?".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn deleted_forwards_show_the_forwarded_content() {
+    let data = data().await;
+    LogType::Chat
+        .store_channel(&data.pool, GUILD, Some(CHAT_LOGS))
+        .await
+        .unwrap();
+
+    // the original is in another server, so the snapshot is the only way to see what was forwarded.
+    let mut discord = MockDiscord::new();
+    discord
+        .messages
+        .push(serde_json::from_value(fixture("message_create_forward.json")).unwrap());
+
+    process(&discord, &message_delete(), &data).await.unwrap();
+
+    let sent = discord.sent();
+    assert!(sent[0]
+        .field("Forwarded from")
+        .unwrap()
+        .starts_with("[A message in another server]"));
+    assert_eq!(
+        sent[0].field("Forwarded content"),
+        Some("Crab sighted in the harbour!")
+    );
+}
+
+#[tokio::test]
+async fn deletions_by_moderators_name_them() {
+    let data = data().await;
+    LogType::Chat
+        .store_channel(&data.pool, GUILD, Some(CHAT_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord.messages.push(cached_message());
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_message_delete.json")));
+
+    process(&discord, &message_delete(), &data).await.unwrap();
+
+    let sent = discord.sent();
+    assert_eq!(sent[0].field("Deleted by"), Some("<@1100000000000000200>"));
+}
+
+#[tokio::test]
+async fn purges_name_the_moderator_and_their_reason() {
+    let data = data().await;
+    LogType::Chat
+        .store_channel(&data.pool, GUILD, Some(CHAT_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord.messages.push(cached_message());
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_message_bulk_delete.json")));
+
+    let message = cached_message();
+    process(
+        &discord,
+        &FullEvent::MessageDeleteBulk {
+            channel_id: message.channel_id,
+            multiple_deleted_messages_ids: vec![message.id, MessageId::new(1100000000000000050)],
+            guild_id: Some(GUILD),
+        },
+        &data,
+    )
+    .await
+    .unwrap();
+
+    let sent = discord.sent();
+    assert_eq!(sent[0].channel_id, CHAT_LOGS);
+    assert_eq!(sent[0].field("Purged By"), Some("<@1100000000000000200>"));
+    assert_eq!(sent[0].field("Reason"), Some("Raid cleanup"));
+}
+
+#[tokio::test]
+async fn metadata_only_guilds_get_no_message_content() {
+    let data = data().await;
+    LogType::Chat
+        .store_channel(&data.pool, GUILD, Some(CHAT_LOGS))
+        .await
+        .unwrap();
+    data.settings
+        .set(GUILD, &keys::METADATA_ONLY, &true)
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord.messages.push(cached_message());
+
+    process(&discord, &message_delete(), &data).await.unwrap();
+
+    let sent = discord.sent();
+    let content = sent[0].field("Content").unwrap();
+    assert!(content.starts_with("*Hidden*"));
+    assert!(!content.contains("crab"));
+}
+
+#[tokio::test]
+async fn uncached_deletions_are_skipped() {
+    let data = data().await;
+    LogType::Chat
+        .store_channel(&data.pool, GUILD, Some(CHAT_LOGS))
+        .await
+        .unwrap();
+
+    let discord = MockDiscord::new();
+
+    process(&discord, &message_delete(), &data).await.unwrap();
+
+    assert!(discord.sent().is_empty());
+}
+
+#[tokio::test]
+async fn long_edits_are_compacted_behind_buttons() {
+    let data = data().await;
+    LogType::Chat
+        .store_channel(&data.pool, GUILD, Some(CHAT_LOGS))
+        .await
+        .unwrap();
+
+    let mut old = cached_message();
+    old.content = "crab ".repeat(300);
+
+    let mut new = old.clone();
+    new.content = "Never mind, found it.".to_string();
+
+    let event: MessageUpdateEvent = serde_json::from_value(fixture("message_update.json")).unwrap();
+
+    let discord = MockDiscord::new();
+
+    process(
+        &discord,
+        &FullEvent::MessageUpdate {
+            old_if_available: Some(old),
+            new: Some(new),
+            event,
+        },
+        &data,
+    )
+    .await
+    .unwrap();
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].field("Previous").unwrap().ends_with('…'));
+    assert_eq!(sent[0].field("New"), Some("Never mind, found it."));
+
+    let buttons = &sent[0].payload["components"][0]["components"];
+    assert!(buttons[0]["custom_id"]
+        .as_str()
+        .unwrap()
+        .starts_with("edit:diff:"));
+    assert!(buttons[1]["custom_id"]
+        .as_str()
+        .unwrap()
+        .starts_with("edit:previous:"));
+}
+
+#[tokio::test]
+async fn voice_channel_statuses_name_who_set_them() {
+    let data = data().await;
+    LogType::Voice
+        .store_channel(&data.pool, GUILD, Some(VOICE_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_voice_channel_status.json")));
+
+    let event: VoiceChannelStatusUpdateEvent =
+        serde_json::from_value(fixture("voice_channel_status_update.json")).unwrap();
+
+    process(
+        &discord,
+        &FullEvent::VoiceChannelStatusUpdate {
+            old: None,
+            status: event.status,
+            id: event.id,
+            guild_id: event.guild_id,
+        },
+        &data,
+    )
+    .await
+    .unwrap();
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].channel_id, VOICE_LOGS);
+    assert_eq!(sent[0].field("Set by"), Some("<@1100000000000000200>"));
+    assert_eq!(sent[0].field("New"), Some("Movie night 🍿"));
+}
+
+#[tokio::test]
+async fn hops_after_the_hopping_alert_are_not_logged() {
+    let data = data().await;
+    LogType::Voice
+        .store_channel(&data.pool, GUILD, Some(VOICE_LOGS))
+        .await
+        .unwrap();
+    LogType::Member
+        .store_channel(&data.pool, GUILD, Some(MEMBER_LOGS))
+        .await
+        .unwrap();
+    data.settings
+        .set(GUILD, &keys::VOICE_HOP_THRESHOLD, &3)
+        .await
+        .unwrap();
+
+    let discord = MockDiscord::new();
+
+    let joined: VoiceState = serde_json::from_value(fixture("voice_state_update.json")).unwrap();
+    let mut left = joined.clone();
+    left.channel_id = None;
+
+    // join, leave, join (flagged), leave, join
+    for hop in 0..5 {
+        let (old, new) = match hop % 2 {
+            0 => (left.clone(), joined.clone()),
+            _ => (joined.clone(), left.clone()),
+        };
+
+        process(
+            &discord,
+            &FullEvent::VoiceStateUpdate {
+                old: Some(old),
+                new,
+            },
+            &data,
+        )
+        .await
+        .unwrap();
+    }
+
+    let sent = discord.sent();
+    let in_channel = |channel_id| {
+        sent.iter()
+            .filter(|sent| sent.channel_id == channel_id)
+            .count()
+    };
+
+    assert_eq!(in_channel(VOICE_LOGS), 3);
+    assert_eq!(in_channel(MEMBER_LOGS), 1);
+}
+
+#[tokio::test]
+async fn hopping_alerts_are_posted_without_a_voice_log_channel() {
+    let data = data().await;
+    LogType::Member
+        .store_channel(&data.pool, GUILD, Some(MEMBER_LOGS))
+        .await
+        .unwrap();
+    data.settings
+        .set(GUILD, &keys::VOICE_HOP_THRESHOLD, &3)
+        .await
+        .unwrap();
+
+    let discord = MockDiscord::new();
+
+    let joined: VoiceState = serde_json::from_value(fixture("voice_state_update.json")).unwrap();
+    let mut left = joined.clone();
+    left.channel_id = None;
+
+    for hop in 0..3 {
+        let (old, new) = match hop % 2 {
+            0 => (left.clone(), joined.clone()),
+            _ => (joined.clone(), left.clone()),
+        };
+
+        process(
+            &discord,
+            &FullEvent::VoiceStateUpdate {
+                old: Some(old),
+                new,
+            },
+            &data,
+        )
+        .await
+        .unwrap();
+    }
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].channel_id, MEMBER_LOGS);
+}
+
+#[tokio::test]
+async fn removed_soundboard_sounds_are_described_from_their_snapshot() {
+    let data = data().await;
+    LogType::Server
+        .store_channel(&data.pool, GUILD, Some(SERVER_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_soundboard_sound_delete.json")));
+
+    soundboard::handle(
+        &discord,
+        &data,
+        "GUILD_SOUNDBOARD_SOUND_CREATE",
+        &fixture("guild_soundboard_sound_create.json"),
+    )
+    .await
+    .unwrap();
+    soundboard::handle(
+        &discord,
+        &data,
+        "GUILD_SOUNDBOARD_SOUND_DELETE",
+        &fixture("guild_soundboard_sound_delete.json"),
+    )
+    .await
+    .unwrap();
+
+    let sent = discord.sent();
+    let removed = sent
+        .iter()
+        .find(|sent| sent.field("Removed by").is_some())
+        .unwrap();
+    assert_eq!(removed.channel_id, SERVER_LOGS);
+    assert_eq!(removed.field("Removed by"), Some("<@1100000000000000200>"));
+    assert!(removed.payload["embeds"][0]["description"]
+        .as_str()
+        .unwrap()
+        .contains("**crab rave** 🦀 at 50% volume"));
+}
+
+#[tokio::test]
+async fn onboarding_prompt_edits_list_changed_options() {
+    let data = data().await;
+    LogType::Server
+        .store_channel(&data.pool, GUILD, Some(SERVER_LOGS))
+        .await
+        .unwrap();
+
+    let raw = fixture("audit_log_onboarding_prompt_update.json");
+    let entry: AuditLogEntry = serde_json::from_value(raw.clone()).unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord.audit_log.push(raw);
+
+    process(
+        &discord,
+        &FullEvent::GuildAuditLogEntryCreate {
+            entry,
+            guild_id: GUILD,
+        },
+        &data,
+    )
+    .await
+    .unwrap();
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(
+        sent[0].field("Title"),
+        Some("What brings you here? → What are you into?")
+    );
+    assert_eq!(sent[0].field("Options added"), Some("- Shrimp"));
+    assert_eq!(sent[0].field("Options removed"), Some("- Lobsters"));
+    assert_eq!(sent[0].field("Options edited"), None);
+    assert_eq!(sent[0].field("Edited by"), Some("<@1100000000000000200>"));
+}
+
+#[tokio::test]
+async fn kicks_are_logged_as_moderation_instead_of_leaves() {
+    let data = data().await;
+    LogType::Moderation
+        .store_channel(&data.pool, GUILD, Some(MODERATION_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_member_kick.json")));
+
+    process(
+        &discord,
+        &FullEvent::GuildMemberRemoval {
+            guild_id: GUILD,
+            user: cached_message().author,
+            member_data_if_available: None,
+        },
+        &data,
+    )
+    .await
+    .unwrap();
+
+    // the case remembers its log, so /case reason can edit it.
+    let case = cases::get(&data.pool, GUILD, 1).await.unwrap().unwrap();
+    assert_eq!(case.log_message, Some((MODERATION_LOGS, MessageId::new(1))));
+
+    let stats = mod_stats::since(&data.pool, GUILD, 0, 10).await.unwrap();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].kicks, 1);
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].channel_id, MODERATION_LOGS);
+    assert_eq!(sent[0].field("Moderator"), Some("<@1100000000000000200>"));
+    assert_eq!(sent[0].field("Reason"), Some("Crab spam"));
+    assert_eq!(sent[0].field("Case"), Some("#1"));
+}
+
+#[tokio::test]
+async fn kicks_on_a_muted_route_are_still_counted() {
+    let data = data().await;
+    LogType::Moderation
+        .store_channel(&data.pool, GUILD, Some(MODERATION_LOGS))
+        .await
+        .unwrap();
+    mutes::mute(
+        &data.pool,
+        GUILD,
+        LogType::Moderation,
+        UserId::new(1100000000000000200),
+        None,
+        i64::MAX,
+    )
+    .await
+    .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_member_kick.json")));
+
+    process(
+        &discord,
+        &FullEvent::GuildMemberRemoval {
+            guild_id: GUILD,
+            user: cached_message().author,
+            member_data_if_available: None,
+        },
+        &data,
+    )
+    .await
+    .unwrap();
+
+    assert!(discord.sent().is_empty());
+
+    let stats = mod_stats::since(&data.pool, GUILD, 0, 10).await.unwrap();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].kicks, 1);
+}
+
+fn ban() -> FullEvent {
+    FullEvent::GuildBanAddition {
+        guild_id: GUILD,
+        banned_user: cached_message().author,
+    }
+}
+
+#[tokio::test]
+async fn bans_pick_up_late_audit_entries() {
+    let data = data().await;
+    LogType::Moderation
+        .store_channel(&data.pool, GUILD, Some(MODERATION_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_member_ban_add.json")));
+    discord.audit_log_lag = 1;
+
+    process(&discord, &ban(), &data).await.unwrap();
+
+    assert_eq!(discord.audit_log_lookups.load(Ordering::Relaxed), 2);
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].field("Moderator"), Some("<@1100000000000000200>"));
+    assert_eq!(sent[0].field("Reason"), Some("Crab spam"));
+}
+
+#[tokio::test]
+async fn bans_stop_waiting_for_audit_entries_eventually() {
+    let data = data().await;
+    LogType::Moderation
+        .store_channel(&data.pool, GUILD, Some(MODERATION_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_member_ban_add.json")));
+    discord.audit_log_lag = usize::MAX;
+
+    process(&discord, &ban(), &data).await.unwrap();
+
+    // the first lookup and one per retry, several seconds apart.
+    assert_eq!(discord.audit_log_lookups.load(Ordering::Relaxed), 3);
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].field("Moderator"), Some("Unknown"));
+}
+
+#[tokio::test]
+async fn deleted_roles_name_who_deleted_them() {
+    let data = data().await;
+    LogType::Server
+        .store_channel(&data.pool, GUILD, Some(SERVER_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_role_delete.json")));
+
+    process(
+        &discord,
+        &FullEvent::GuildRoleDelete {
+            guild_id: GUILD,
+            removed_role_id: RoleId::new(1100000000000000300),
+            removed_role_data_if_available: None,
+        },
+        &data,
+    )
+    .await
+    .unwrap();
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].channel_id, SERVER_LOGS);
+    assert_eq!(sent[0].field("Deleted by"), Some("<@1100000000000000200>"));
+}
+
+#[tokio::test]
+async fn polling_logs_new_audit_entries_once() {
+    let data = data().await;
+    LogType::Moderation
+        .store_channel(&data.pool, GUILD, Some(MODERATION_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_member_kick.json")));
+
+    // the first poll only takes note of what's already there.
+    audit_poll::poll_guild(&discord, &data, GUILD)
+        .await
+        .unwrap();
+    assert!(discord.sent().is_empty());
+
+    let mut prune = recent(fixture("audit_log_member_prune.json"));
+    let id = prune["id"].as_str().unwrap().parse::<u64>().unwrap() + 1;
+    prune["id"] = id.to_string().into();
+    // Discord lists the newest entries first.
+    discord.audit_log.insert(0, prune);
+
+    audit_poll::poll_guild(&discord, &data, GUILD)
+        .await
+        .unwrap();
+    audit_poll::poll_guild(&discord, &data, GUILD)
+        .await
+        .unwrap();
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].channel_id, MODERATION_LOGS);
+    assert_eq!(sent[0].field("Moderator"), Some("<@1100000000000000200>"));
+}
+
+#[tokio::test]
+async fn automod_edits_list_removed_keywords() {
+    let data = data().await;
+    LogType::Server
+        .store_channel(&data.pool, GUILD, Some(SERVER_LOGS))
+        .await
+        .unwrap();
+
+    let mut discord = MockDiscord::new();
+    discord
+        .audit_log
+        .push(recent(fixture("audit_log_automod_rule_update.json")));
+
+    process(
+        &discord,
+        &FullEvent::AutoModRuleUpdate {
+            rule: serde_json::from_value(fixture("auto_moderation_rule_update.json")).unwrap(),
+        },
+        &data,
+    )
+    .await
+    .unwrap();
+
+    let sent = discord.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].channel_id, SERVER_LOGS);
+    assert_eq!(sent[0].field("Updated by"), Some("<@1100000000000000200>"));
+    assert_eq!(sent[0].field("Keywords removed"), Some("`airdrop`"));
+    assert_eq!(sent[0].field("Keywords added"), None);
+}
+
+#[tokio::test]
+async fn webhooks_into_private_networks_are_refused() {
+    for url in [
+        "https://127.0.0.1/hook",
+        "https://10.0.0.5/hook",
+        "https://169.254.169.254/latest/meta-data",
+        "https://[::1]/hook",
+        "https://[::ffff:192.168.1.1]/hook",
+    ] {
+        assert!(
+            matches!(
+                outbound::client_for(url).await,
+                Err(UnsafeUrl::NotPublic(_))
+            ),
+            "{url} was allowed"
+        );
+    }
+
+    assert!(matches!(
+        outbound::client_for("http://1.1.1.1/hook").await,
+        Err(UnsafeUrl::NotHttps)
+    ));
+    assert!(outbound::client_for("https://1.1.1.1/hook").await.is_ok());
+}
+
+const NOISY: UserId = UserId::new(1100000000000000300);
+
+#[test]
+fn damping_collapses_logs_past_the_threshold() {
+    let damper = Damper::default();
+    let start = Instant::now();
+
+    for _ in 0..THRESHOLD {
+        assert!(damper.allow_at(start, GUILD, NOISY, &MemberReport, LogType::Member));
+    }
+
+    assert!(!damper.allow_at(start, GUILD, NOISY, &MemberReport, LogType::Member));
+    // other users aren't affected.
+    assert!(damper.allow_at(
+        start,
+        GUILD,
+        UserId::new(1100000000000000301),
+        &MemberReport,
+        LogType::Member
+    ));
+}
+
+#[test]
+fn damping_windows_start_over() {
+    let damper = Damper::default();
+    let start = Instant::now();
+
+    for _ in 0..THRESHOLD {
+        assert!(damper.allow_at(start, GUILD, NOISY, &MemberReport, LogType::Member));
+    }
+
+    let later = start + WINDOW + Duration::from_secs(1);
+    assert!(damper.allow_at(later, GUILD, NOISY, &MemberReport, LogType::Member));
+}
+
+#[test]
+fn damping_cooldowns_extend_with_each_collapsed_log() {
+    let damper = Damper::default();
+    let start = Instant::now();
+
+    for _ in 0..=THRESHOLD {
+        damper.allow_at(start, GUILD, NOISY, &MemberReport, LogType::Member);
+    }
+
+    let almost = start + COOLDOWN - Duration::from_secs(1);
+    assert!(!damper.allow_at(almost, GUILD, NOISY, &MemberReport, LogType::Member));
+
+    // the collapsed log just now pushed the cooldown back.
+    let extended = start + COOLDOWN + Duration::from_secs(1);
+    assert!(!damper.allow_at(extended, GUILD, NOISY, &MemberReport, LogType::Member));
+
+    let calm = extended + COOLDOWN + Duration::from_secs(1);
+    assert!(damper.allow_at(calm, GUILD, NOISY, &MemberReport, LogType::Member));
+}
+
+#[test]
+fn damping_summaries_count_collapsed_logs_once() {
+    let damper = Damper::default();
+    let start = Instant::now();
+
+    for _ in 0..THRESHOLD + 3 {
+        damper.allow_at(start, GUILD, NOISY, &MemberReport, LogType::Member);
+    }
+
+    let summaries = damper.take_summaries_at(start);
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].user_id, NOISY);
+    assert_eq!(summaries[0].count, 3);
+
+    assert!(damper.take_summaries_at(start).is_empty());
+}
@@ -0,0 +1,120 @@
+use serenity::{all::GuildId, builder::CreateEmbedFooter, model::Colour};
+
+use super::formatter::{Category, Severity};
+use crate::{commands::LogType, settings::Settings};
+
+#[derive(Debug, Clone)]
+pub struct Style {
+    pub colour: Colour,
+    pub emoji: String,
+}
+
+fn default_style(target: &str) -> Style {
+    let (colour, emoji) = match target {
+        "added" => (Colour::DARK_GREEN, "📥"),
+        "changed" => (Colour::FADED_PURPLE, "✏️"),
+        "removed" => (Colour::RED, "🗑️"),
+        "moderation" => (Colour::ORANGE, "🔨"),
+        "warning" => (Colour::GOLD, "⚠️"),
+        "critical" => (Colour::DARK_RED, "🚨"),
+        _ => (Colour::LIGHT_GREY, "📄"),
+    };
+
+    Style {
+        colour,
+        emoji: emoji.into(),
+    }
+}
+
+/// Elevated severities take precedence over the category's style, so alerts stand out regardless of what they're about.
+fn target(category: Category, severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "critical",
+        Severity::Warning => "warning",
+        Severity::Info | Severity::Notice => category.as_key(),
+    }
+}
+
+pub async fn resolve(
+    settings: &Settings,
+    guild_id: GuildId,
+    category: Category,
+    severity: Severity,
+) -> Style {
+    style(settings, guild_id, target(category, severity)).await
+}
+
+pub fn colour_key(target: &str) -> String {
+    format!("theme.{target}.colour")
+}
+
+pub fn emoji_key(target: &str) -> String {
+    format!("theme.{target}.emoji")
+}
+
+pub async fn style(settings: &Settings, guild_id: GuildId, target: &str) -> Style {
+    let mut style = default_style(target);
+
+    if let Some(colour) = settings
+        .get_raw(guild_id, &colour_key(target))
+        .await
+        .and_then(|colour| colour.parse::<u32>().ok())
+    {
+        style.colour = Colour::new(colour);
+    }
+
+    if let Some(emoji) = settings.get_raw(guild_id, &emoji_key(target)).await {
+        style.emoji = emoji;
+    }
+
+    style
+}
+
+/// The footer setting `field` (`text` or `icon`) for `log_type`, or for every log type when `None`.
+pub fn footer_key(log_type: Option<LogType>, field: &str) -> String {
+    match log_type {
+        Some(log_type) => format!("theme.footer.{}.{field}", log_type.as_column_name()),
+        None => format!("theme.footer.{field}"),
+    }
+}
+
+/// The guild's footer for logs routed to `log_type`, with `note` (e.g. an incident number) in front of it.
+/// Each part falls back to the guild-wide footer when the log type has none of its own.
+pub async fn footer(
+    settings: &Settings,
+    guild_id: GuildId,
+    log_type: LogType,
+    note: Option<&str>,
+) -> Option<CreateEmbedFooter> {
+    let mut parts = Vec::new();
+
+    for field in ["text", "icon"] {
+        let value = match settings
+            .get_raw(guild_id, &footer_key(Some(log_type), field))
+            .await
+        {
+            Some(value) => Some(value),
+            None => settings.get_raw(guild_id, &footer_key(None, field)).await,
+        };
+        parts.push(value);
+    }
+
+    let icon = parts.pop().flatten();
+    let text = [note.map(str::to_string), parts.pop().flatten()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" • ");
+
+    // discord drops footers without text, icon or not.
+    if text.is_empty() {
+        return None;
+    }
+
+    let mut footer = CreateEmbedFooter::new(text);
+    if let Some(icon) = icon {
+        footer = footer.icon_url(icon);
+    }
+
+    Some(footer)
+}
@@ -0,0 +1,48 @@
+#[derive(Debug, poise::ChoiceParameter, Clone, Copy, Default)]
+pub enum TimestampStyle {
+    #[name = "Relative"]
+    Relative,
+    #[name = "Date and time"]
+    ShortDateTime,
+    #[default]
+    #[name = "Both"]
+    Both,
+}
+
+impl TimestampStyle {
+    pub fn as_key(&self) -> &'static str {
+        match self {
+            Self::Relative => "relative",
+            Self::ShortDateTime => "short_date_time",
+            Self::Both => "both",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "relative" => Some(Self::Relative),
+            "short_date_time" => Some(Self::ShortDateTime),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+
+    pub fn format(&self, timestamp: i64) -> String {
+        match self {
+            Self::Relative => format!("<t:{timestamp}:R>"),
+            Self::ShortDateTime => format!("<t:{timestamp}:f>"),
+            Self::Both => format!("<t:{timestamp}:f> (<t:{timestamp}:R>)"),
+        }
+    }
+}
+
+/// Renders a duration in seconds as e.g. `3d 4h` or `12m`.
+pub fn describe_duration(secs: i64) -> String {
+    let (days, hours, minutes) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60);
+
+    match (days, hours) {
+        (0, 0) => format!("{}m", minutes.max(1)),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
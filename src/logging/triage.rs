@@ -0,0 +1,194 @@
+use poise::ChoiceParameter;
+use serenity::{
+    all::{
+        ButtonStyle, ComponentInteraction, Context, FullEvent, GuildId, Interaction, Permissions,
+        Reaction,
+    },
+    builder::{
+        CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
+    },
+};
+
+use super::log_messages;
+use crate::{client::Data, settings::Settings};
+
+#[derive(Debug, poise::ChoiceParameter, Clone, Copy, PartialEq, Eq)]
+pub enum TriageStatus {
+    #[name = "handled"]
+    Handled,
+    #[name = "investigating"]
+    Investigating,
+    #[name = "false positive"]
+    FalsePositive,
+}
+
+impl TriageStatus {
+    pub const ALL: [Self; 3] = [Self::Handled, Self::Investigating, Self::FalsePositive];
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_key() == key)
+    }
+
+    pub fn as_key(&self) -> &'static str {
+        match self {
+            Self::Handled => "handled",
+            Self::Investigating => "investigating",
+            Self::FalsePositive => "false_positive",
+        }
+    }
+
+    fn default_emoji(&self) -> &'static str {
+        match self {
+            Self::Handled => "✅",
+            Self::Investigating => "👀",
+            Self::FalsePositive => "🚫",
+        }
+    }
+
+    pub fn emoji_key(&self) -> String {
+        format!("triage.{}.emoji", self.as_key())
+    }
+
+    pub async fn emoji(&self, settings: &Settings, guild_id: GuildId) -> String {
+        settings
+            .get_raw(guild_id, &self.emoji_key())
+            .await
+            .unwrap_or_else(|| self.default_emoji().to_string())
+    }
+
+    async fn from_reaction(
+        settings: &Settings,
+        guild_id: GuildId,
+        reaction: &Reaction,
+    ) -> Option<Self> {
+        let emoji = reaction.emoji.to_string();
+
+        for status in Self::ALL {
+            if status.emoji(settings, guild_id).await == emoji {
+                return Some(status);
+            }
+        }
+
+        None
+    }
+}
+
+/// Triage buttons for logs that need a decision, like member reports.
+pub fn buttons() -> CreateActionRow {
+    CreateActionRow::Buttons(
+        TriageStatus::ALL
+            .into_iter()
+            .map(|status| {
+                CreateButton::new(format!("triage:{}", status.as_key()))
+                    .label(status.name())
+                    .emoji(status.default_emoji().chars().next().unwrap())
+                    .style(match status {
+                        TriageStatus::Handled => ButtonStyle::Success,
+                        TriageStatus::Investigating => ButtonStyle::Primary,
+                        TriageStatus::FalsePositive => ButtonStyle::Secondary,
+                    })
+            })
+            .collect(),
+    )
+}
+
+async fn on_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), crate::client::Error> {
+    let Some(status) = interaction
+        .data
+        .custom_id
+        .strip_prefix("triage:")
+        .and_then(TriageStatus::from_key)
+    else {
+        return Ok(());
+    };
+
+    let allowed = interaction
+        .member
+        .as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.contains(Permissions::MANAGE_MESSAGES));
+
+    let content = if !allowed {
+        "You need the Manage Messages permission to triage logs.".to_string()
+    } else if log_messages::set_status(
+        &data.pool,
+        interaction.message.id,
+        Some(status.as_key()),
+        interaction.user.id,
+    )
+    .await?
+    {
+        format!("Marked this log as {}.", status.name())
+    } else {
+        "This log isn't tracked anymore.".to_string()
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Updates a log message's triage status when a moderator reacts to it with one of the triage emojis,
+/// or presses one of its triage buttons.
+pub async fn on_event(
+    ctx: &Context,
+    event: &FullEvent,
+    data: &Data,
+) -> Result<(), crate::client::Error> {
+    let (reaction, added) = match event {
+        FullEvent::ReactionAdd { add_reaction } => (add_reaction, true),
+        FullEvent::ReactionRemove { removed_reaction } => (removed_reaction, false),
+        FullEvent::InteractionCreate {
+            interaction: Interaction::Component(interaction),
+        } => return on_button(ctx, interaction, data).await,
+        _ => return Ok(()),
+    };
+
+    let (Some(guild_id), Some(user_id)) = (reaction.guild_id, reaction.user_id) else {
+        return Ok(());
+    };
+
+    if reaction
+        .member
+        .as_ref()
+        .is_some_and(|member| member.user.bot)
+    {
+        return Ok(());
+    }
+
+    let Some(status) = TriageStatus::from_reaction(&data.settings, guild_id, reaction).await else {
+        return Ok(());
+    };
+
+    if added {
+        log_messages::set_status(
+            &data.pool,
+            reaction.message_id,
+            Some(status.as_key()),
+            user_id,
+        )
+        .await?;
+    } else if log_messages::status(&data.pool, reaction.message_id)
+        .await?
+        .as_deref()
+        == Some(status.as_key())
+    {
+        // only clear the status if the removed reaction is the one that set it.
+        log_messages::set_status(&data.pool, reaction.message_id, None, user_id).await?;
+    }
+
+    Ok(())
+}
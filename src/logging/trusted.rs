@@ -0,0 +1,119 @@
+use std::str::FromStr;
+
+use serenity::{
+    all::{Embed, GuildId, RoleId, UserId},
+    builder::CreateEmbed,
+};
+use sqlx::{Pool, Sqlite};
+
+use super::now;
+
+pub async fn set_trusted(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    role_id: RoleId,
+    trusted: bool,
+) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let role_id = role_id.to_string();
+
+    let result = if trusted {
+        sqlx::query!(
+            "INSERT INTO trusted_roles (guild_id, role_id) VALUES (?, ?) ON CONFLICT DO NOTHING",
+            guild_id,
+            role_id
+        )
+        .execute(pool)
+        .await?
+    } else {
+        sqlx::query!(
+            "DELETE FROM trusted_roles WHERE guild_id = ? AND role_id = ?",
+            guild_id,
+            role_id
+        )
+        .execute(pool)
+        .await?
+    };
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn trusted_roles(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+) -> Result<Vec<RoleId>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    Ok(sqlx::query!(
+        "SELECT role_id FROM trusted_roles WHERE guild_id = ?",
+        guild_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter_map(|row| RoleId::from_str(&row.role_id).ok())
+    .collect())
+}
+
+pub async fn is_trusted(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    roles: &[RoleId],
+) -> Result<bool, sqlx::Error> {
+    let trusted = trusted_roles(pool, guild_id).await?;
+
+    Ok(roles.iter().any(|role| trusted.contains(role)))
+}
+
+/// Keeps a log that was held back from the log channels, so admins can still review it.
+pub async fn archive(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    kind: &str,
+    user_id: UserId,
+    embed: &CreateEmbed,
+) -> Result<(), crate::client::Error> {
+    let guild_id = guild_id.to_string();
+    let user_id = user_id.to_string();
+    let embed = serde_json::to_string(embed)?;
+    let now = now() as i64;
+
+    sqlx::query!(
+        "INSERT INTO suppressed_logs (guild_id, kind, user_id, embed, created_at) VALUES (?, ?, ?, ?, ?)",
+        guild_id,
+        kind,
+        user_id,
+        embed,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// The most recent suppressed logs in a guild, optionally only those about `user_id`.
+pub async fn review(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: Option<UserId>,
+    limit: i64,
+) -> Result<Vec<CreateEmbed>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let user_id = user_id.map(|id| id.to_string());
+
+    Ok(sqlx::query!(
+        "SELECT embed FROM suppressed_logs WHERE guild_id = ? AND (? IS NULL OR user_id = ?)
+        ORDER BY id DESC LIMIT ?",
+        guild_id,
+        user_id,
+        user_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter_map(|row| serde_json::from_str::<Embed>(&row.embed).ok())
+    .map(CreateEmbed::from)
+    .collect())
+}
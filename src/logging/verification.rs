@@ -0,0 +1,116 @@
+use serenity::all::{GuildId, Member, UserId};
+use sqlx::{Pool, Sqlite};
+
+use super::now;
+
+pub async fn record_join(pool: &Pool<Sqlite>, member: &Member) -> Result<(), sqlx::Error> {
+    let guild_id = member.guild_id.to_string();
+    let user_id = member.user.id.to_string();
+    let joined_at = member
+        .joined_at
+        .map_or(now() as i64, |joined_at| joined_at.unix_timestamp());
+
+    // rejoining restarts the clock.
+    sqlx::query!(
+        "INSERT INTO member_verification (guild_id, user_id, joined_at, screened) VALUES (?, ?, ?, ?)
+        ON CONFLICT (guild_id, user_id) DO UPDATE SET joined_at = excluded.joined_at, screened = excluded.screened, verified_at = NULL, first_message_at = NULL",
+        guild_id,
+        user_id,
+        joined_at,
+        member.pending
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Marks `member` as verified once they've passed screening (or, without screening, got their first role).
+/// Returns how long they took, but only the first time.
+pub async fn record_update(
+    pool: &Pool<Sqlite>,
+    member: &Member,
+) -> Result<Option<i64>, sqlx::Error> {
+    let guild_id = member.guild_id.to_string();
+    let user_id = member.user.id.to_string();
+    let now = now() as i64;
+    let screened_passed = !member.pending;
+    let has_role = !member.roles.is_empty();
+
+    let row = sqlx::query!(
+        "UPDATE member_verification SET verified_at = ?
+        WHERE guild_id = ? AND user_id = ? AND verified_at IS NULL AND (CASE WHEN screened THEN ? ELSE ? END)
+        RETURNING joined_at",
+        now,
+        guild_id,
+        user_id,
+        screened_passed,
+        has_role
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| now - row.joined_at))
+}
+
+pub struct VerificationStats {
+    pub joined: usize,
+    pub verified: usize,
+    pub median_secs: Option<i64>,
+    pub slowest_secs: Option<i64>,
+}
+
+/// Verification stats for members who joined since `since`.
+pub async fn stats(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    since: i64,
+) -> Result<VerificationStats, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    let rows = sqlx::query!(
+        "SELECT joined_at, verified_at FROM member_verification WHERE guild_id = ? AND joined_at >= ?",
+        guild_id,
+        since
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut durations = rows
+        .iter()
+        .filter_map(|row| Some(row.verified_at? - row.joined_at))
+        .collect::<Vec<_>>();
+    durations.sort_unstable();
+
+    Ok(VerificationStats {
+        joined: rows.len(),
+        verified: durations.len(),
+        median_secs: durations.get(durations.len() / 2).copied(),
+        slowest_secs: durations.last().copied(),
+    })
+}
+
+/// Records the first message of a member who joined since tracking started.
+/// Returns how long after joining they sent it, but only the first time.
+pub async fn record_first_message(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<Option<i64>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let user_id = user_id.to_string();
+    let now = now() as i64;
+
+    let row = sqlx::query!(
+        "UPDATE member_verification SET first_message_at = ?
+        WHERE guild_id = ? AND user_id = ? AND first_message_at IS NULL
+        RETURNING joined_at",
+        now,
+        guild_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| now - row.joined_at))
+}
@@ -0,0 +1,76 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serenity::all::{GuildId, UserId};
+
+/// Trackers idle for longer than this are dropped. The configurable window is capped to it.
+pub const MAX_HOP_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+struct Hops {
+    window_start: Instant,
+    window: Duration,
+    last_hop: Instant,
+    count: i64,
+    alerted: bool,
+}
+
+/// Voice channel joins, leaves and moves per member, for flagging members who hop between channels.
+#[derive(Default)]
+pub struct VoiceHops {
+    hops: Mutex<HashMap<(GuildId, UserId), Hops>>,
+}
+
+impl VoiceHops {
+    /// Counts a join, leave or move and returns the number of hops in the current window
+    /// the first time it reaches `threshold`.
+    pub fn record(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        threshold: i64,
+        window: Duration,
+    ) -> Option<i64> {
+        let now = Instant::now();
+        let mut hops = self.hops.lock().unwrap();
+
+        hops.retain(|_, hops| now.duration_since(hops.last_hop) <= MAX_HOP_WINDOW);
+
+        let hops = hops.entry((guild_id, user_id)).or_insert_with(|| Hops {
+            window_start: now,
+            window,
+            last_hop: now,
+            count: 0,
+            alerted: false,
+        });
+
+        if now.duration_since(hops.window_start) > window {
+            hops.window_start = now;
+            hops.count = 0;
+            hops.alerted = false;
+        }
+
+        hops.window = window;
+        hops.last_hop = now;
+        hops.count += 1;
+
+        if hops.count >= threshold && !hops.alerted {
+            hops.alerted = true;
+            Some(hops.count)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `user_id` was flagged for hopping in the current window,
+    /// in which case their individual joins and leaves should be left to the alert.
+    pub fn is_flagged(&self, guild_id: GuildId, user_id: UserId) -> bool {
+        self.hops
+            .lock()
+            .unwrap()
+            .get(&(guild_id, user_id))
+            .is_some_and(|hops| hops.alerted && hops.window_start.elapsed() <= hops.window)
+    }
+}
@@ -2,9 +2,30 @@
 
 use sqlx::sqlite::SqlitePoolOptions;
 
+mod appeals;
+mod cases;
 mod client;
 mod commands;
+mod config_snapshots;
+mod dashboard;
+mod diff;
+mod disclosure;
+mod emoji_stats;
+mod features;
+mod lockdown;
 mod logging;
+mod member_counts;
+mod mod_stats;
+mod onboarding;
+mod oncall;
+mod reports;
+mod retention;
+mod settings;
+mod sparkline;
+mod transfer;
+mod upgrades;
+mod user_reports;
+mod welcomes;
 
 #[tokio::main]
 async fn main() {
@@ -15,6 +36,43 @@ async fn main() {
     let pool = SqlitePoolOptions::new().connect(&db_uri).await.unwrap();
 
     sqlx::migrate!().run(&pool).await.unwrap();
+    upgrades::run(&pool).await.unwrap();
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [] => {}
+        ["export", guild_id, path] => {
+            let guild_id = guild_id
+                .parse::<u64>()
+                .unwrap_or_else(|_| panic!("{guild_id} is not a valid guild ID."));
+
+            let count = transfer::export(&pool, guild_id.into(), path)
+                .await
+                .unwrap();
+
+            println!("Exported {count} rows for guild {guild_id} to {path}.");
+            return;
+        }
+        ["import", path] => {
+            let (guild_id, count) = transfer::import(&pool, path).await.unwrap();
+
+            println!("Imported {count} rows for guild {guild_id} from {path}.");
+            return;
+        }
+        ["migrate-postgres", url] => {
+            transfer::to_postgres(&pool, url).await.unwrap();
+            return;
+        }
+        _ => panic!(
+            "Usage: logsalot [export <guild id> <file> | import <file> | migrate-postgres <url>]"
+        ),
+    }
 
     let mut client = client::get_client(pool).await;
 
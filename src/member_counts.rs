@@ -0,0 +1,200 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use poise::serenity_prelude::*;
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    client::Data,
+    logging::{self, formatters::MemberMilestone, now},
+    settings::{keys, Settings},
+};
+
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn milestone_floor(count: i64, interval: i64) -> i64 {
+    if interval <= 0 {
+        0
+    } else {
+        count / interval * interval
+    }
+}
+
+/// The persisted member count, kept in step with joins and leaves and reconciled daily.
+/// Prefer this over the cache's count, which is only as fresh as the last `GuildCreate`.
+pub async fn get(pool: &Pool<Sqlite>, guild_id: GuildId) -> Result<Option<i64>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    let row = sqlx::query!(
+        "SELECT member_count FROM member_counts WHERE guild_id = ?",
+        guild_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| row.member_count))
+}
+
+/// Overwrites the persisted member count, e.g. from a fresh guild payload.
+/// Milestones already passed when a guild is first seen are never announced.
+pub async fn set(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    count: i64,
+    interval: i64,
+) -> Result<(), sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let floor = milestone_floor(count, interval);
+    let now = now() as i64;
+
+    sqlx::query!(
+        "INSERT INTO member_counts (guild_id, member_count, last_milestone, updated_at) VALUES (?, ?, ?, ?)
+        ON CONFLICT (guild_id) DO UPDATE SET member_count = excluded.member_count, updated_at = excluded.updated_at",
+        guild_id,
+        count,
+        floor,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Adjusts the persisted member count by `delta`, returning the new count if the guild is tracked.
+pub async fn adjust(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    delta: i64,
+) -> Result<Option<i64>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let now = now() as i64;
+
+    let row = sqlx::query!(
+        "UPDATE member_counts SET member_count = MAX(member_count + ?, 0), updated_at = ? WHERE guild_id = ?
+        RETURNING member_count",
+        delta,
+        now,
+        guild_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| row.member_count))
+}
+
+/// Marks the milestone `count` has reached as announced. Returns it if it hadn't been announced yet,
+/// so members leaving and rejoining around a milestone don't announce it twice.
+async fn claim_milestone(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    count: i64,
+    interval: i64,
+) -> Result<Option<i64>, sqlx::Error> {
+    let milestone = milestone_floor(count, interval);
+
+    if milestone == 0 {
+        return Ok(None);
+    }
+
+    let guild_id = guild_id.to_string();
+
+    let result = sqlx::query!(
+        "UPDATE member_counts SET last_milestone = ? WHERE guild_id = ? AND last_milestone < ?",
+        milestone,
+        guild_id,
+        milestone
+    )
+    .execute(pool)
+    .await?;
+
+    Ok((result.rows_affected() > 0).then_some(milestone))
+}
+
+async fn announce_milestone(ctx: &Context, data: &Data, guild_id: GuildId, milestone: i64) {
+    let entry = MemberMilestone::entry(guild_id, milestone);
+
+    if let Err(error) = logging::deliver(ctx, data, &MemberMilestone, entry).await {
+        println!("Failed to log member milestone: {error}");
+    }
+
+    if let Some(channel) = data.settings.get(guild_id, &keys::MILESTONE_CHANNEL).await
+        && let Err(error) = channel
+            .say(ctx, format!("🎉 We just reached {milestone} members!"))
+            .await
+    {
+        println!("Failed to announce member milestone: {error}");
+    }
+}
+
+/// Corrects drift from missed join and leave events once a day, using the API's approximate member count.
+pub async fn reconcile(http: Arc<Http>, pool: Pool<Sqlite>, settings: Settings) {
+    let mut interval = tokio::time::interval(RECONCILE_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let guilds = match sqlx::query!("SELECT guild_id FROM member_counts")
+            .fetch_all(&pool)
+            .await
+        {
+            Ok(guilds) => guilds,
+            Err(error) => {
+                println!("Failed to fetch tracked member counts: {error}");
+                continue;
+            }
+        };
+
+        for row in guilds {
+            let Ok(guild_id) = GuildId::from_str(&row.guild_id) else {
+                continue;
+            };
+
+            let count = match http.get_guild_with_counts(guild_id).await {
+                Ok(guild) => guild.approximate_member_count,
+                Err(error) => {
+                    println!("Failed to fetch member count for guild {guild_id}: {error}");
+                    continue;
+                }
+            };
+
+            let Some(count) = count else {
+                continue;
+            };
+
+            let interval = settings.get(guild_id, &keys::MILESTONE_INTERVAL).await;
+
+            if let Err(error) = set(&pool, guild_id, count as i64, interval).await {
+                println!("Failed to reconcile member count for guild {guild_id}: {error}");
+            }
+        }
+    }
+}
+
+/// Keeps the persisted member count in step with gateway events, announcing milestones as they're reached.
+pub async fn on_event(ctx: &Context, event: &FullEvent, data: &Data) -> Result<(), sqlx::Error> {
+    match event {
+        FullEvent::GuildCreate { guild, .. } => {
+            let interval = data.settings.get(guild.id, &keys::MILESTONE_INTERVAL).await;
+            set(&data.pool, guild.id, guild.member_count as i64, interval).await?;
+        }
+        FullEvent::GuildMemberAddition { new_member } => {
+            let guild_id = new_member.guild_id;
+
+            let Some(count) = adjust(&data.pool, guild_id, 1).await? else {
+                return Ok(());
+            };
+
+            let interval = data.settings.get(guild_id, &keys::MILESTONE_INTERVAL).await;
+
+            if let Some(milestone) = claim_milestone(&data.pool, guild_id, count, interval).await? {
+                announce_milestone(ctx, data, guild_id, milestone).await;
+            }
+        }
+        FullEvent::GuildMemberRemoval { guild_id, .. } => {
+            adjust(&data.pool, *guild_id, -1).await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
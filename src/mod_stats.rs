@@ -0,0 +1,91 @@
+use std::str::FromStr;
+
+use serenity::all::{GuildId, UserId};
+use sqlx::{Pool, Sqlite};
+
+use crate::logging::now;
+
+/// A moderator action attributed through the audit log, counted whatever happens to its log.
+pub struct ModAction {
+    pub moderator: UserId,
+    /// `ban`, `kick`, `timeout` or `delete`.
+    pub action: &'static str,
+    pub count: i64,
+}
+
+/// Counts `count` of `action` (`ban`, `kick`, `timeout` or `delete`) towards `moderator`.
+pub async fn record(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    moderator: UserId,
+    action: &str,
+    count: i64,
+) -> Result<(), sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let moderator = moderator.to_string();
+    let now = now() as i64;
+
+    sqlx::query!(
+        "INSERT INTO moderator_actions (guild_id, moderator_id, action, count, created_at) VALUES (?, ?, ?, ?, ?)",
+        guild_id,
+        moderator,
+        action,
+        count,
+        now
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub struct ModeratorStats {
+    pub moderator: UserId,
+    pub bans: i64,
+    pub kicks: i64,
+    pub timeouts: i64,
+    /// Messages deleted, including each message of a purge.
+    pub deletes: i64,
+}
+
+impl ModeratorStats {
+    pub fn total(&self) -> i64 {
+        self.bans + self.kicks + self.timeouts + self.deletes
+    }
+}
+
+/// What each moderator did since `since`, most active first.
+pub async fn since(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    since: i64,
+    limit: i64,
+) -> Result<Vec<ModeratorStats>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    Ok(sqlx::query!(
+        r#"SELECT moderator_id,
+            SUM(CASE WHEN action = 'ban' THEN count ELSE 0 END) AS "bans!: i64",
+            SUM(CASE WHEN action = 'kick' THEN count ELSE 0 END) AS "kicks!: i64",
+            SUM(CASE WHEN action = 'timeout' THEN count ELSE 0 END) AS "timeouts!: i64",
+            SUM(CASE WHEN action = 'delete' THEN count ELSE 0 END) AS "deletes!: i64"
+        FROM moderator_actions WHERE guild_id = ? AND created_at >= ?
+        GROUP BY moderator_id ORDER BY SUM(count) DESC LIMIT ?"#,
+        guild_id,
+        since,
+        limit
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter_map(|row| {
+        Some(ModeratorStats {
+            moderator: UserId::from_str(&row.moderator_id).ok()?,
+            bans: row.bans,
+            kicks: row.kicks,
+            timeouts: row.timeouts,
+            deletes: row.deletes,
+        })
+    })
+    .collect())
+}
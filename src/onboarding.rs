@@ -0,0 +1,189 @@
+use std::time::Duration;
+
+use poise::serenity_prelude::*;
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    commands::LogType,
+    features::{self, Feature},
+};
+
+const SETUP_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Finds whoever added the bot, falling back to the guild owner if the audit log isn't readable
+/// or the guild turned audit log correlation off.
+async fn inviter(ctx: &Context, pool: &Pool<Sqlite>, guild: &Guild) -> UserId {
+    let bot_id = ctx.cache.current_user().id;
+
+    if !features::is_enabled(pool, guild.id, Feature::AuditCorrelation).await {
+        return guild.owner_id;
+    }
+
+    guild
+        .id
+        .audit_logs(
+            ctx,
+            Some(audit_log::Action::Member(audit_log::MemberAction::BotAdd)),
+            None,
+            None,
+            Some(10),
+        )
+        .await
+        .ok()
+        .and_then(|logs| {
+            logs.entries
+                .into_iter()
+                .find(|entry| entry.target_id.map(|id| id.get()) == Some(bot_id.get()))
+        })
+        .map_or(guild.owner_id, |entry| entry.user_id)
+}
+
+fn text_channels(guild: &Guild) -> Vec<&GuildChannel> {
+    let mut channels = guild
+        .channels
+        .values()
+        .filter(|channel| channel.kind == ChannelType::Text)
+        .collect::<Vec<_>>();
+    channels.sort_by_key(|channel| channel.position);
+    channels
+}
+
+fn channel_select(log_type: LogType, guild: &Guild) -> CreateActionRow {
+    let channels = text_channels(guild);
+
+    // select menus are capped at 25 options; bigger guilds can use /channels set for the rest.
+    let options = channels
+        .into_iter()
+        .take(25)
+        .map(|channel| {
+            CreateSelectMenuOption::new(format!("#{}", channel.name), channel.id.to_string())
+        })
+        .collect();
+
+    CreateActionRow::SelectMenu(
+        CreateSelectMenu::new(
+            format!("onboarding:{}", log_type.as_column_name()),
+            CreateSelectMenuKind::String { options },
+        )
+        .placeholder(log_type.to_string()),
+    )
+}
+
+/// Posts a setup hint in the guild's system channel for when setup can't happen over DM.
+async fn fallback_notice(ctx: &Context, guild: &Guild, hint: &str) {
+    let Some(channel) = guild.system_channel_id else {
+        return;
+    };
+
+    let _ = channel
+        .say(ctx, format!("Thanks for adding logsalot! {hint}"))
+        .await;
+}
+
+pub async fn start(ctx: Context, pool: Pool<Sqlite>, guild: Guild) {
+    // Discord rejects select menus without options, so there'd be nothing to pick in the DM.
+    if text_channels(&guild).is_empty() {
+        fallback_notice(
+            &ctx,
+            &guild,
+            "There are no text channels to send logs to yet - create one, then use `/channels set` to choose where logs should go.",
+        )
+        .await;
+        return;
+    }
+
+    let inviter = inviter(&ctx, &pool, &guild).await;
+
+    // Discord allows five rows of components, and the last one is needed for the Done button.
+    let log_types = [
+        LogType::Member,
+        LogType::Chat,
+        LogType::Server,
+        LogType::Moderation,
+    ];
+
+    let mut components = log_types
+        .iter()
+        .map(|log_type| channel_select(*log_type, &guild))
+        .collect::<Vec<_>>();
+    components.push(CreateActionRow::Buttons(vec![CreateButton::new(
+        "onboarding:done",
+    )
+    .label("Done")
+    .style(ButtonStyle::Success)]));
+
+    let message = inviter
+        .direct_message(
+            &ctx,
+            CreateMessage::new()
+                .content(format!(
+                    "Thanks for adding logsalot to **{}**! Pick a channel for each kind of log below, or skip this and use `/channels set` later.",
+                    guild.name
+                ))
+                .components(components),
+        )
+        .await;
+
+    let message = match message {
+        Ok(message) => message,
+        Err(_) => {
+            fallback_notice(
+                &ctx,
+                &guild,
+                "Use `/channels set` to choose where logs should go.",
+            )
+            .await;
+            return;
+        }
+    };
+
+    while let Some(interaction) = message
+        .await_component_interaction(&ctx.shard)
+        .timeout(SETUP_TIMEOUT)
+        .await
+    {
+        if interaction.data.custom_id == "onboarding:done" {
+            let _ = interaction
+                .create_response(
+                    &ctx,
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new()
+                            .content(format!(
+                                "All set! Log channels for **{}** can be changed any time with `/channels set`.",
+                                guild.name
+                            ))
+                            .components(Vec::new()),
+                    ),
+                )
+                .await;
+            return;
+        }
+
+        let log_type = log_types.into_iter().find(|log_type| {
+            interaction.data.custom_id == format!("onboarding:{}", log_type.as_column_name())
+        });
+
+        if let (Some(log_type), ComponentInteractionDataKind::StringSelect { values }) =
+            (log_type, &interaction.data.kind)
+        {
+            let channel = values.first().and_then(|id| id.parse::<ChannelId>().ok());
+
+            if let Err(error) = log_type.store_channel(&pool, guild.id, channel).await {
+                println!(
+                    "Failed to store onboarding channel for guild {}: {error}",
+                    guild.id
+                );
+            }
+        }
+
+        let _ = interaction
+            .create_response(&ctx, CreateInteractionResponse::Acknowledge)
+            .await;
+    }
+
+    // timed out - strip the components so the stale menus can't be used anymore.
+    let _ = message
+        .channel_id
+        .edit_message(&ctx, message.id, EditMessage::new().components(Vec::new()))
+        .await;
+}
@@ -0,0 +1,358 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use poise::serenity_prelude::*;
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    client::Data,
+    logging::now,
+    settings::{keys, Settings},
+};
+
+const ESCALATION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The guild's on-call rotation, in order.
+pub async fn rotation(pool: &Pool<Sqlite>, guild_id: GuildId) -> Result<Vec<UserId>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    Ok(sqlx::query!(
+        "SELECT user_id FROM oncall_rotation WHERE guild_id = ? ORDER BY position",
+        guild_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter_map(|row| UserId::from_str(&row.user_id).ok())
+    .collect())
+}
+
+/// Adds `user_id` to the end of the rotation. Returns `false` if they're already in it.
+pub async fn add(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let user_id = user_id.to_string();
+
+    let result = sqlx::query!(
+        "INSERT OR IGNORE INTO oncall_rotation (guild_id, user_id, position)
+        VALUES (?1, ?2, (SELECT COALESCE(MAX(position), -1) + 1 FROM oncall_rotation WHERE guild_id = ?1))",
+        guild_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn remove(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<bool, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let user_id = user_id.to_string();
+
+    let result = sqlx::query!(
+        "DELETE FROM oncall_rotation WHERE guild_id = ? AND user_id = ?",
+        guild_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Index of the currently on-call moderator in `rotation`.
+pub async fn pointer(settings: &Settings, guild_id: GuildId, rotation: &[UserId]) -> usize {
+    if rotation.is_empty() {
+        return 0;
+    }
+
+    settings.get(guild_id, &keys::ONCALL_POINTER).await.max(0) as usize % rotation.len()
+}
+
+/// Hands on-call over to the next moderator in the rotation, returning them.
+pub async fn advance(
+    pool: &Pool<Sqlite>,
+    settings: &Settings,
+    guild_id: GuildId,
+) -> Result<Option<UserId>, crate::client::Error> {
+    let rotation = rotation(pool, guild_id).await?;
+
+    if rotation.is_empty() {
+        return Ok(None);
+    }
+
+    let next = (pointer(settings, guild_id, &rotation).await + 1) % rotation.len();
+    settings
+        .set(guild_id, &keys::ONCALL_POINTER, &(next as i64))
+        .await?;
+
+    Ok(Some(rotation[next]))
+}
+
+async fn send_page(
+    http: &Http,
+    user_id: UserId,
+    page_id: i64,
+    guild_id: GuildId,
+    title: &str,
+    alert: Option<(ChannelId, MessageId)>,
+) -> bool {
+    let server = guild_id
+        .to_partial_guild(http)
+        .await
+        .map_or("a server".to_string(), |guild| {
+            format!("**{}**", guild.name)
+        });
+    let link = alert
+        .map(|(channel_id, message_id)| {
+            format!("\n{}", message_id.link(channel_id, Some(guild_id)))
+        })
+        .unwrap_or_default();
+
+    user_id
+        .direct_message(
+            http,
+            CreateMessage::new()
+                .content(format!(
+                    "🚨 **{title}** in {server} - you're on call. Acknowledge to stop this alert from escalating.{link}"
+                ))
+                .components(vec![CreateActionRow::Buttons(vec![CreateButton::new(
+                    format!("oncall:ack:{page_id}"),
+                )
+                .label("Acknowledge")
+                .style(ButtonStyle::Danger)])]),
+        )
+        .await
+        .is_ok()
+}
+
+/// DMs the on-call moderator about a maximum-severity alert. If nobody acknowledges it in time,
+/// [`escalate`] pages the next moderators in the rotation.
+pub async fn page(
+    http: &Http,
+    pool: &Pool<Sqlite>,
+    settings: &Settings,
+    guild_id: GuildId,
+    kind: &str,
+    title: &str,
+    alert: Option<(ChannelId, MessageId)>,
+) -> Result<(), crate::client::Error> {
+    let rotation = rotation(pool, guild_id).await?;
+
+    if rotation.is_empty() {
+        return Ok(());
+    }
+
+    let on_call = rotation[pointer(settings, guild_id, &rotation).await];
+
+    let guild_id_string = guild_id.to_string();
+    let channel_id = alert.map(|(channel_id, _)| channel_id.to_string());
+    let message_id = alert.map(|(_, message_id)| message_id.to_string());
+    let now = now() as i64;
+
+    let page_id = sqlx::query!(
+        "INSERT INTO oncall_pages (guild_id, kind, channel_id, message_id, created_at, attempts, last_paged_at)
+        VALUES (?, ?, ?, ?, ?, 1, ?)",
+        guild_id_string,
+        kind,
+        channel_id,
+        message_id,
+        now,
+        now
+    )
+    .execute(pool)
+    .await?
+    .last_insert_rowid();
+
+    if !send_page(http, on_call, page_id, guild_id, title, alert).await {
+        println!("Failed to page on-call moderator {on_call} in guild {guild_id}");
+    }
+
+    Ok(())
+}
+
+/// Marks a page as acknowledged. Returns whoever acknowledged it first.
+async fn acknowledge(
+    pool: &Pool<Sqlite>,
+    page_id: i64,
+    user_id: UserId,
+) -> Result<Option<(UserId, Option<(ChannelId, MessageId)>)>, sqlx::Error> {
+    let user_id_string = user_id.to_string();
+    let now = now() as i64;
+
+    sqlx::query!(
+        "UPDATE oncall_pages SET acknowledged_by = ?, acknowledged_at = ? WHERE id = ? AND acknowledged_at IS NULL",
+        user_id_string,
+        now,
+        page_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(sqlx::query!(
+        "SELECT channel_id, message_id, acknowledged_by FROM oncall_pages WHERE id = ?",
+        page_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .and_then(|row| {
+        let alert = row
+            .channel_id
+            .zip(row.message_id)
+            .and_then(|(channel_id, message_id)| {
+                Some((
+                    ChannelId::from_str(&channel_id).ok()?,
+                    MessageId::from_str(&message_id).ok()?,
+                ))
+            });
+
+        Some((UserId::from_str(&row.acknowledged_by?).ok()?, alert))
+    }))
+}
+
+/// Handles the acknowledgement buttons on page DMs.
+pub async fn on_event(
+    ctx: &Context,
+    event: &FullEvent,
+    data: &Data,
+) -> Result<(), crate::client::Error> {
+    let FullEvent::InteractionCreate {
+        interaction: Interaction::Component(interaction),
+    } = event
+    else {
+        return Ok(());
+    };
+
+    let Some(page_id) = interaction
+        .data
+        .custom_id
+        .strip_prefix("oncall:ack:")
+        .and_then(|id| id.parse::<i64>().ok())
+    else {
+        return Ok(());
+    };
+
+    let Some((acknowledged_by, alert)) =
+        acknowledge(&data.pool, page_id, interaction.user.id).await?
+    else {
+        return Ok(());
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(format!(
+                        "{}\n\nAcknowledged by <@{acknowledged_by}>.",
+                        interaction.message.content
+                    ))
+                    .components(Vec::new()),
+            ),
+        )
+        .await?;
+
+    // later clicks (e.g. by escalated moderators) only update their own DM.
+    if acknowledged_by == interaction.user.id
+        && let Some((channel_id, message_id)) = alert
+    {
+        channel_id
+            .send_message(
+                ctx,
+                CreateMessage::new()
+                    .content(format!(
+                        "Acknowledged by on-call moderator <@{acknowledged_by}>."
+                    ))
+                    .reference_message((channel_id, message_id))
+                    .allowed_mentions(CreateAllowedMentions::new().empty_users()),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Periodically pages the next moderator in the rotation for alerts nobody acknowledged in time,
+/// until everyone in the rotation has been paged once.
+pub async fn escalate(http: Arc<Http>, pool: Pool<Sqlite>, settings: Settings) {
+    let mut interval = tokio::time::interval(ESCALATION_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let pending = match sqlx::query!(
+            "SELECT id, guild_id, kind, channel_id, message_id, attempts, last_paged_at FROM oncall_pages
+            WHERE acknowledged_at IS NULL"
+        )
+        .fetch_all(&pool)
+        .await
+        {
+            Ok(pending) => pending,
+            Err(error) => {
+                println!("Failed to fetch unacknowledged pages: {error}");
+                continue;
+            }
+        };
+
+        let now = now() as i64;
+
+        for page in pending {
+            let Ok(guild_id) = GuildId::from_str(&page.guild_id) else {
+                continue;
+            };
+
+            let delay = settings
+                .get(guild_id, &keys::ONCALL_ESCALATION_MINUTES)
+                .await
+                * 60;
+            if page.last_paged_at + delay > now {
+                continue;
+            }
+
+            let Ok(rotation) = rotation(&pool, guild_id).await else {
+                continue;
+            };
+
+            if page.attempts as usize >= rotation.len() {
+                continue;
+            }
+
+            let next = rotation[(pointer(&settings, guild_id, &rotation).await
+                + page.attempts as usize)
+                % rotation.len()];
+
+            if let Err(error) = sqlx::query!(
+                "UPDATE oncall_pages SET attempts = attempts + 1, last_paged_at = ? WHERE id = ?",
+                now,
+                page.id
+            )
+            .execute(&pool)
+            .await
+            {
+                println!("Failed to escalate page {}: {error}", page.id);
+                continue;
+            }
+
+            let alert =
+                page.channel_id
+                    .zip(page.message_id)
+                    .and_then(|(channel_id, message_id)| {
+                        Some((
+                            ChannelId::from_str(&channel_id).ok()?,
+                            MessageId::from_str(&message_id).ok()?,
+                        ))
+                    });
+
+            let title = format!("Unacknowledged {} alert", page.kind);
+            if !send_page(&http, next, page.id, guild_id, &title, alert).await {
+                println!("Failed to page on-call moderator {next} in guild {guild_id}");
+            }
+        }
+    }
+}
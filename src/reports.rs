@@ -0,0 +1,110 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use poise::serenity_prelude::*;
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    commands::LogType,
+    logging::{maintenance, now, theme, timestamps, verification},
+    settings::{keys, Settings},
+};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const REPORT_PERIOD_SECS: i64 = 7 * 24 * 60 * 60;
+
+async fn weekly_report(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    since: i64,
+) -> Result<Vec<(String, String)>, sqlx::Error> {
+    let stats = verification::stats(pool, guild_id, since).await?;
+
+    let mut verification = format!("{} joined, {} verified", stats.joined, stats.verified);
+
+    if let (Some(median), Some(slowest)) = (stats.median_secs, stats.slowest_secs) {
+        verification += &format!(
+            "\nMedian time to verify: {}\nSlowest: {}",
+            timestamps::describe_duration(median),
+            timestamps::describe_duration(slowest)
+        );
+    }
+
+    Ok(vec![("Verification".to_string(), verification)])
+}
+
+/// Posts a weekly summary to each guild's server logs.
+pub async fn post_weekly(http: Arc<Http>, pool: Pool<Sqlite>, settings: Settings) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let guilds =
+            match sqlx::query!("SELECT guild_id FROM log_channels WHERE server_logs IS NOT NULL")
+                .fetch_all(&pool)
+                .await
+            {
+                Ok(guilds) => guilds,
+                Err(error) => {
+                    println!("Failed to fetch guilds for weekly reports: {error}");
+                    continue;
+                }
+            };
+
+        let now = now() as i64;
+
+        for row in guilds {
+            let Ok(guild_id) = GuildId::from_str(&row.guild_id) else {
+                continue;
+            };
+
+            let last_report = settings.get(guild_id, &keys::LAST_WEEKLY_REPORT).await;
+
+            // the first week starts counting when reports are first seen, rather than reporting on nothing.
+            if last_report != 0 && now - last_report < REPORT_PERIOD_SECS {
+                continue;
+            }
+
+            if let Err(error) = settings
+                .set(guild_id, &keys::LAST_WEEKLY_REPORT, &now)
+                .await
+            {
+                println!("Failed to store weekly report time: {error}");
+                continue;
+            }
+
+            if last_report == 0 {
+                continue;
+            }
+
+            let (Some(channel), Ok(fields)) = (
+                LogType::Server.fetch_channel(&pool, guild_id).await,
+                weekly_report(&pool, guild_id, last_report).await,
+            ) else {
+                continue;
+            };
+
+            let style = theme::style(&settings, guild_id, "changed").await;
+
+            let embed = CreateEmbed::new()
+                .title(format!("{} Weekly report", style.emoji))
+                .colour(style.colour)
+                .description(format!("Activity since <t:{last_report}:f>."))
+                .fields(fields.into_iter().map(|(name, value)| (name, value, false)));
+
+            if let Err(error) = maintenance::send(
+                &http,
+                &pool,
+                &settings,
+                guild_id,
+                LogType::Server,
+                channel,
+                embed,
+            )
+            .await
+            {
+                println!("Failed to send weekly report: {error}");
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use poise::serenity_prelude::*;
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    commands::LogType,
+    logging::{housekeeping::Housekeeping, maintenance, now, theme},
+    settings::Settings,
+};
+
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Caps how much a single sweep deletes per channel, so a newly configured busy channel is worked through gradually.
+const MAX_DELETIONS_PER_SWEEP: usize = 500;
+/// Discord refuses to bulk delete messages older than two weeks.
+const BULK_DELETE_MAX_AGE_SECS: i64 = 14 * 24 * 60 * 60;
+const DISCORD_EPOCH_MS: u64 = 1_420_070_400_000;
+
+/// The smallest message ID that could have been sent at `timestamp`.
+fn snowflake_at(timestamp: i64) -> MessageId {
+    MessageId::new(((timestamp as u64 * 1000).saturating_sub(DISCORD_EPOCH_MS) << 22).max(1))
+}
+
+pub async fn set(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    days: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let channel_id = channel_id.to_string();
+
+    match days {
+        Some(days) => {
+            sqlx::query!(
+                "INSERT INTO channel_retention (channel_id, guild_id, days) VALUES (?, ?, ?)
+                ON CONFLICT (channel_id) DO UPDATE SET days = excluded.days",
+                channel_id,
+                guild_id,
+                days
+            )
+            .execute(pool)
+            .await?;
+        }
+        None => {
+            sqlx::query!(
+                "DELETE FROM channel_retention WHERE channel_id = ? AND guild_id = ?",
+                channel_id,
+                guild_id
+            )
+            .execute(pool)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn list(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+) -> Result<Vec<(ChannelId, i64)>, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+
+    Ok(sqlx::query!(
+        "SELECT channel_id, days FROM channel_retention WHERE guild_id = ?",
+        guild_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter_map(|row| Some((ChannelId::from_str(&row.channel_id).ok()?, row.days)))
+    .collect())
+}
+
+/// Deletes messages older than `days` in `channel_id`, returning how many were removed.
+async fn sweep_channel(
+    http: &Http,
+    housekeeping: &Housekeeping,
+    channel_id: ChannelId,
+    days: i64,
+) -> Result<usize, serenity::Error> {
+    let now = now() as i64;
+    let mut before = snowflake_at(now - days * 24 * 60 * 60);
+    let mut deleted = 0;
+
+    while deleted < MAX_DELETIONS_PER_SWEEP {
+        let messages = channel_id
+            .messages(http, GetMessages::new().before(before).limit(100))
+            .await?;
+
+        let Some(oldest) = messages.last() else {
+            break;
+        };
+        before = oldest.id;
+
+        let (bulk, single): (Vec<_>, Vec<_>) = messages
+            .iter()
+            .filter(|message| !message.pinned)
+            .map(|message| message.id)
+            .partition(|id| now - id.created_at().unix_timestamp() < BULK_DELETE_MAX_AGE_SECS);
+
+        housekeeping.mark(bulk.iter().chain(single.iter()).copied());
+
+        if !bulk.is_empty() {
+            channel_id.delete_messages(http, &bulk).await?;
+        }
+
+        for id in single.iter() {
+            channel_id.delete_message(http, *id).await?;
+        }
+
+        deleted += bulk.len() + single.len();
+    }
+
+    Ok(deleted)
+}
+
+/// Periodically enforces per-channel retention, posting a housekeeping log for each channel it cleaned up.
+pub async fn enforce(
+    http: Arc<Http>,
+    pool: Pool<Sqlite>,
+    settings: Settings,
+    housekeeping: Arc<Housekeeping>,
+) {
+    let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let channels =
+            match sqlx::query!("SELECT channel_id, guild_id, days FROM channel_retention")
+                .fetch_all(&pool)
+                .await
+            {
+                Ok(channels) => channels,
+                Err(error) => {
+                    println!("Failed to fetch channel retention: {error}");
+                    continue;
+                }
+            };
+
+        for row in channels {
+            let (Ok(channel_id), Ok(guild_id)) = (
+                ChannelId::from_str(&row.channel_id),
+                GuildId::from_str(&row.guild_id),
+            ) else {
+                continue;
+            };
+
+            let deleted = match sweep_channel(&http, &housekeeping, channel_id, row.days).await {
+                Ok(0) => continue,
+                Ok(deleted) => deleted,
+                Err(error) => {
+                    println!("Failed to enforce retention in channel {channel_id}: {error}");
+                    continue;
+                }
+            };
+
+            let Some(log_channel) = LogType::Chat.fetch_channel(&pool, guild_id).await else {
+                continue;
+            };
+
+            let style = theme::style(&settings, guild_id, "removed").await;
+
+            let embed = CreateEmbed::new()
+                .title(format!("{} Retention housekeeping", style.emoji))
+                .colour(style.colour)
+                .description(format!(
+                    "Deleted {deleted} messages older than {} days in <#{channel_id}>. These deletions aren't logged individually.",
+                    row.days
+                ));
+
+            if let Err(error) = maintenance::send(
+                &http,
+                &pool,
+                &settings,
+                guild_id,
+                LogType::Chat,
+                log_channel,
+                embed,
+            )
+            .await
+            {
+                println!("Failed to send retention log: {error}");
+            }
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use serenity::all::{ChannelId, GuildId, RoleId};
+use sqlx::{Pool, Sqlite};
+
+use crate::logging::timestamps::TimestampStyle;
+
+pub trait SettingValue: Sized {
+    fn parse(raw: &str) -> Option<Self>;
+    fn serialize(&self) -> String;
+}
+
+impl SettingValue for bool {
+    fn parse(raw: &str) -> Option<Self> {
+        raw.parse().ok()
+    }
+
+    fn serialize(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl SettingValue for i64 {
+    fn parse(raw: &str) -> Option<Self> {
+        raw.parse().ok()
+    }
+
+    fn serialize(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl SettingValue for String {
+    fn parse(raw: &str) -> Option<Self> {
+        Some(raw.to_string())
+    }
+
+    fn serialize(&self) -> String {
+        self.clone()
+    }
+}
+
+impl SettingValue for Option<ChannelId> {
+    fn parse(raw: &str) -> Option<Self> {
+        Some(ChannelId::from_str(raw).ok())
+    }
+
+    fn serialize(&self) -> String {
+        self.map(|id| id.to_string()).unwrap_or_default()
+    }
+}
+
+impl SettingValue for Option<RoleId> {
+    fn parse(raw: &str) -> Option<Self> {
+        Some(RoleId::from_str(raw).ok())
+    }
+
+    fn serialize(&self) -> String {
+        self.map(|id| id.to_string()).unwrap_or_default()
+    }
+}
+
+impl SettingValue for TimestampStyle {
+    fn parse(raw: &str) -> Option<Self> {
+        Self::from_key(raw)
+    }
+
+    fn serialize(&self) -> String {
+        self.as_key().to_string()
+    }
+}
+
+pub struct Key<T> {
+    pub name: &'static str,
+    default: fn() -> T,
+    _value: PhantomData<T>,
+}
+
+impl<T> Key<T> {
+    pub const fn new(name: &'static str, default: fn() -> T) -> Self {
+        Self {
+            name,
+            default,
+            _value: PhantomData,
+        }
+    }
+}
+
+pub mod keys {
+    use serenity::all::{ChannelId, RoleId};
+
+    use super::Key;
+    use crate::logging::timestamps::TimestampStyle;
+
+    pub const TIMESTAMP_STYLE: Key<TimestampStyle> =
+        Key::new("timestamp_style", TimestampStyle::default);
+    pub const SUPPRESS_QUICK_SELF_DELETES: Key<bool> =
+        Key::new("suppress_quick_self_deletes", || false);
+    pub const MIN_DELETED_MESSAGE_AGE: Key<i64> = Key::new("min_deleted_message_age", || 0);
+    /// Log message metadata only: content is replaced by its length and a hash, and attachments aren't re-uploaded.
+    pub const METADATA_ONLY: Key<bool> = Key::new("metadata_only", || false);
+    pub const LOG_SYSTEM_MESSAGES: Key<bool> = Key::new("log_system_messages", || false);
+    pub const ANNOUNCEMENTS: Key<bool> = Key::new("announcements", || true);
+    pub const PIN_CRITICAL_ALERTS: Key<bool> = Key::new("pin_critical_alerts", || false);
+    pub const DETECT_WEBHOOK_SPOOFS: Key<bool> = Key::new("detect_webhook_spoofs", || true);
+    /// How many voice joins/leaves within `VOICE_HOP_WINDOW` seconds count as hopping. 0 disables the alert.
+    pub const VOICE_HOP_THRESHOLD: Key<i64> = Key::new("voice_hop_threshold", || 6);
+    pub const VOICE_HOP_WINDOW: Key<i64> = Key::new("voice_hop_window", || 60);
+    pub const NUKE_PING_OWNER: Key<bool> = Key::new("nuke_ping_owner", || false);
+    /// Role pinged when the vanity URL changes, if any.
+    pub const VANITY_PING_ROLE: Key<Option<RoleId>> = Key::new("vanity_ping_role", || None);
+    /// Raid and nuke detections are POSTed here as JSON, for external anti-nuke tooling. Empty disables it.
+    pub const QUARANTINE_WEBHOOK_URL: Key<String> = Key::new("quarantine_webhook_url", String::new);
+    pub const LANGUAGE_TAGS: Key<bool> = Key::new("language_tags", || false);
+    /// Members verifying this many hours or more after joining are flagged. 0 disables the alert.
+    pub const VERIFICATION_LURK_HOURS: Key<i64> = Key::new("verification_lurk_hours", || 72);
+    pub const LOG_FIRST_MESSAGES: Key<bool> = Key::new("log_first_messages", || false);
+    /// Individual reactions being added and removed. Off by default, since busy servers see a lot of them.
+    pub const LOG_REACTIONS: Key<bool> = Key::new("log_reactions", || false);
+    pub const LAST_WEEKLY_REPORT: Key<i64> = Key::new("last_weekly_report", || 0);
+    /// The last hour (since the unix epoch) checked for unusual log volume.
+    pub const LAST_ANOMALY_CHECK: Key<i64> = Key::new("last_anomaly_check", || 0);
+    pub const WELCOMES: Key<bool> = Key::new("welcomes", || false);
+    /// Where public welcomes go. Falls back to the guild's system channel.
+    pub const WELCOME_CHANNEL: Key<Option<ChannelId>> = Key::new("welcome_channel", || None);
+    pub const WELCOME_TEMPLATE: Key<String> = Key::new("welcome_template", || {
+        crate::welcomes::DEFAULT_TEMPLATE.to_string()
+    });
+    /// Member counts that are a multiple of this are logged as milestones. 0 disables them.
+    pub const MILESTONE_INTERVAL: Key<i64> = Key::new("milestone_interval", || 100);
+    /// Public channel milestones are also announced in, if any.
+    pub const MILESTONE_CHANNEL: Key<Option<ChannelId>> = Key::new("milestone_channel", || None);
+    /// Appeal link included in ban logs, with `{user_id}`, `{user}` and `{server_id}` filled in. Empty disables it.
+    pub const APPEAL_URL: Key<String> = Key::new("appeal_url", String::new);
+    pub const APPEAL_DM: Key<bool> = Key::new("appeal_dm", || false);
+    /// ntfy topic URL critical alerts are pushed to, e.g. `https://ntfy.sh/my-topic`. Empty disables it.
+    pub const NTFY_URL: Key<String> = Key::new("ntfy_url", String::new);
+    /// Pushover application token and user key critical alerts are pushed to. Both are required.
+    pub const PUSHOVER_TOKEN: Key<String> = Key::new("pushover_token", String::new);
+    pub const PUSHOVER_USER: Key<String> = Key::new("pushover_user", String::new);
+    /// The guild's UTC offset in minutes, for quiet hours.
+    pub const TIMEZONE_OFFSET: Key<i64> = Key::new("timezone_offset", || 0);
+    /// How many reports a member may file per hour. 0 removes the limit.
+    pub const REPORT_LIMIT_PER_HOUR: Key<i64> = Key::new("report_limit_per_hour", || 5);
+    /// Whether members can export the records kept about them with `/mydata`.
+    pub const DATA_EXPORTS: Key<bool> = Key::new("data_exports", || false);
+    /// Index of the on-call moderator in the rotation.
+    pub const ONCALL_POINTER: Key<i64> = Key::new("oncall_pointer", || 0);
+    pub const ONCALL_ESCALATION_MINUTES: Key<i64> = Key::new("oncall_escalation_minutes", || 10);
+    /// Unix timestamp panic mode is active until. 0 when it's off.
+    pub const PANIC_UNTIL: Key<i64> = Key::new("panic_until", || 0);
+    pub const STAFF_CHANNEL: Key<Option<ChannelId>> = Key::new("staff_channel", || None);
+}
+
+/// Per-guild settings stored in `guild_settings`, cached in memory per guild.
+///
+/// Lookups fall back to an operator-wide default from the `LOGSALOT_DEFAULT_<KEY>` environment variable,
+/// then to the key's built-in default.
+#[derive(Clone)]
+pub struct Settings {
+    pool: Pool<Sqlite>,
+    cache: Arc<RwLock<HashMap<GuildId, HashMap<String, String>>>>,
+}
+
+impl Settings {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self {
+            pool,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    async fn load(&self, guild_id: GuildId) -> Result<(), sqlx::Error> {
+        if self.cache.read().unwrap().contains_key(&guild_id) {
+            return Ok(());
+        }
+
+        let guild_id_string = guild_id.to_string();
+        let values = sqlx::query!(
+            "SELECT key, value FROM guild_settings WHERE guild_id = ?",
+            guild_id_string
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| (row.key, row.value))
+        .collect();
+
+        self.cache.write().unwrap().insert(guild_id, values);
+
+        Ok(())
+    }
+
+    pub async fn get_raw(&self, guild_id: GuildId, key: &str) -> Option<String> {
+        if let Err(error) = self.load(guild_id).await {
+            println!("Failed to load settings for guild {guild_id}: {error}");
+        }
+
+        let cached = self
+            .cache
+            .read()
+            .unwrap()
+            .get(&guild_id)
+            .and_then(|values| values.get(key).cloned());
+
+        cached.or_else(|| Self::operator_default(key))
+    }
+
+    /// The operator-wide default for `key`, if the `LOGSALOT_DEFAULT_<KEY>` environment variable is set.
+    pub fn operator_default(key: &str) -> Option<String> {
+        std::env::var(format!(
+            "LOGSALOT_DEFAULT_{}",
+            key.to_uppercase().replace('.', "_")
+        ))
+        .ok()
+    }
+
+    pub async fn get<T: SettingValue>(&self, guild_id: GuildId, key: &Key<T>) -> T {
+        self.get_raw(guild_id, key.name)
+            .await
+            .and_then(|raw| T::parse(&raw))
+            .unwrap_or_else(key.default)
+    }
+
+    pub async fn set_raw(
+        &self,
+        guild_id: GuildId,
+        key: &str,
+        value: String,
+    ) -> Result<(), sqlx::Error> {
+        let guild_id_string = guild_id.to_string();
+
+        sqlx::query!(
+            "INSERT INTO guild_settings (guild_id, key, value) VALUES (?, ?, ?)
+            ON CONFLICT (guild_id, key) DO UPDATE SET value = excluded.value",
+            guild_id_string,
+            key,
+            value
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if let Some(values) = self.cache.write().unwrap().get_mut(&guild_id) {
+            values.insert(key.to_string(), value);
+        }
+
+        Ok(())
+    }
+
+    pub async fn set<T: SettingValue>(
+        &self,
+        guild_id: GuildId,
+        key: &Key<T>,
+        value: &T,
+    ) -> Result<(), sqlx::Error> {
+        self.set_raw(guild_id, key.name, value.serialize()).await
+    }
+
+    /// Drops the cached settings for `guild_id`, for when its rows were changed directly, e.g. by a rollback.
+    pub fn invalidate(&self, guild_id: GuildId) {
+        self.cache.write().unwrap().remove(&guild_id);
+    }
+
+    pub async fn unset(&self, guild_id: GuildId, key: &str) -> Result<(), sqlx::Error> {
+        let guild_id_string = guild_id.to_string();
+
+        sqlx::query!(
+            "DELETE FROM guild_settings WHERE guild_id = ? AND key = ?",
+            guild_id_string,
+            key
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if let Some(values) = self.cache.write().unwrap().get_mut(&guild_id) {
+            values.remove(key);
+        }
+
+        Ok(())
+    }
+}
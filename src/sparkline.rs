@@ -0,0 +1,106 @@
+//! Tiny area charts, encoded as PNGs by hand since they're too simple to pull in an image library for.
+
+/// Width of a single value's column, in pixels.
+const COLUMN_WIDTH: usize = 5;
+const HEIGHT: usize = 32;
+const BACKGROUND: [u8; 3] = [0x2b, 0x2d, 0x31];
+
+/// Renders `values` left to right as a filled area chart in `color`, scaled so the largest value fills the height.
+pub fn render(values: &[i64], color: [u8; 3]) -> Vec<u8> {
+    let width = values.len().max(1) * COLUMN_WIDTH;
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    // the area under the line is drawn at half strength, so the line itself stands out.
+    let fill: [u8; 3] =
+        std::array::from_fn(|i| ((color[i] as u16 + BACKGROUND[i] as u16) / 2) as u8);
+
+    let mut pixels = Vec::with_capacity(HEIGHT * (width * 3 + 1));
+
+    for row in 0..HEIGHT {
+        // every row starts with its filter type, and these are never filtered.
+        pixels.push(0);
+
+        for x in 0..width {
+            let value = values.get(x / COLUMN_WIDTH).copied().unwrap_or(0).max(0);
+            let filled = ((value * HEIGHT as i64) as f64 / max as f64).ceil() as usize;
+            let from_bottom = HEIGHT - row;
+
+            let pixel = if from_bottom > filled {
+                BACKGROUND
+            } else if from_bottom == filled {
+                color
+            } else {
+                fill
+            };
+
+            pixels.extend_from_slice(&pixel);
+        }
+    }
+
+    encode(width as u32, HEIGHT as u32, &pixels)
+}
+
+/// Encodes filtered 8-bit RGB scanlines as a PNG, storing them uncompressed.
+fn encode(width: u32, height: u32, scanlines: &[u8]) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per channel, RGB, and the defaults for compression, filtering and interlacing.
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    chunk(&mut png, b"IHDR", &header);
+    chunk(&mut png, b"IDAT", &zlib_stored(scanlines));
+    chunk(&mut png, b"IEND", &[]);
+
+    png
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+
+    let mut crc = !0u32;
+    for &byte in kind.iter().chain(data) {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    png.extend_from_slice(&(!crc).to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let blocks = data.chunks(u16::MAX as usize).collect::<Vec<_>>();
+
+    if blocks.is_empty() {
+        stream.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+
+    for (index, block) in blocks.iter().enumerate() {
+        let last = index == blocks.len() - 1;
+        let len = block.len() as u16;
+
+        stream.push(last as u8);
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    stream.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    stream
+}
@@ -0,0 +1,309 @@
+use serde_json::{json, Map, Value};
+use serenity::all::GuildId;
+use sqlx::{postgres::PgPoolOptions, Pool, Row, Sqlite};
+
+use crate::{client::Error, logging::now};
+
+const ARCHIVE_VERSION: u64 = 1;
+
+/// The condition that selects a guild's rows from `table`, with the guild ID bound as `?1`.
+/// `None` for tables that don't hold guild data.
+fn guild_condition(table: &str, columns: &[String]) -> Option<String> {
+    match table {
+        "feature_flags" | "ignores" => Some("scope = ?1".to_string()),
+        "incident_messages" => {
+            Some("incident_id IN (SELECT id FROM incidents WHERE guild_id = ?1)".to_string())
+        }
+        _ if columns.iter().any(|column| column == "guild_id") => Some("guild_id = ?1".to_string()),
+        _ => None,
+    }
+}
+
+pub(crate) async fn tables(pool: &Pool<Sqlite>) -> Result<Vec<(String, Vec<String>)>, sqlx::Error> {
+    let names = sqlx::query(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| row.get::<String, _>("name"))
+    .collect::<Vec<_>>();
+
+    let mut tables = Vec::new();
+
+    for name in names {
+        let columns = sqlx::query("SELECT name FROM pragma_table_info(?)")
+            .bind(&name)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|row| row.get::<String, _>("name"))
+            .collect();
+
+        tables.push((name, columns));
+    }
+
+    Ok(tables)
+}
+
+/// Writes every row belonging to `guild_id` to a JSON archive at `path`, table by table,
+/// so the guild can be moved to another instance (e.g. when self-hosting). Returns how many rows were exported.
+pub async fn export(pool: &Pool<Sqlite>, guild_id: GuildId, path: &str) -> Result<usize, Error> {
+    let mut exported = Map::new();
+    let mut count = 0;
+
+    for (table, columns) in tables(pool).await? {
+        let Some(condition) = guild_condition(&table, &columns) else {
+            continue;
+        };
+
+        let fields = columns
+            .iter()
+            .map(|column| format!("'{column}', \"{column}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // sqlite builds the JSON itself, so this works for any table without knowing its column types.
+        let rows: String = sqlx::query(&format!(
+            "SELECT json_group_array(json_object({fields})) AS rows FROM \"{table}\" WHERE {condition}"
+        ))
+        .bind(guild_id.to_string())
+        .fetch_one(pool)
+        .await?
+        .get("rows");
+
+        let rows: Value = serde_json::from_str(&rows)?;
+        count += rows.as_array().map_or(0, Vec::len);
+        exported.insert(table, rows);
+    }
+
+    let archive = json!({
+        "version": ARCHIVE_VERSION,
+        "guild_id": guild_id.to_string(),
+        "exported_at": now(),
+        "tables": exported,
+    });
+
+    std::fs::write(path, serde_json::to_vec_pretty(&archive)?)?;
+
+    Ok(count)
+}
+
+/// Loads an archive written by [`export`]. Refuses to import a guild that already has data here,
+/// so an import can't be merged into (or duplicate) an existing setup. Returns the guild and how many rows were imported.
+pub async fn import(pool: &Pool<Sqlite>, path: &str) -> Result<(GuildId, usize), Error> {
+    let archive: Value = serde_json::from_slice(&std::fs::read(path)?)?;
+
+    let version = archive["version"].as_u64().unwrap_or_default();
+    if version != ARCHIVE_VERSION {
+        return Err(format!("Unsupported archive version {version}.").into());
+    }
+
+    let guild_id = archive["guild_id"]
+        .as_str()
+        .and_then(|id| id.parse::<u64>().ok())
+        .map(GuildId::new)
+        .ok_or("Archive doesn't name a valid guild.")?;
+
+    let Some(exported) = archive["tables"].as_object() else {
+        return Err("Archive has no tables.".into());
+    };
+
+    let tables = tables(pool).await?;
+
+    for (table, columns) in &tables {
+        let Some(condition) = guild_condition(table, columns) else {
+            continue;
+        };
+
+        let existing: i64 = sqlx::query(&format!(
+            "SELECT COUNT(*) AS count FROM \"{table}\" WHERE {condition}"
+        ))
+        .bind(guild_id.to_string())
+        .fetch_one(pool)
+        .await?
+        .get("count");
+
+        if existing > 0 {
+            return Err(format!("Guild {guild_id} already has data in {table}.").into());
+        }
+    }
+
+    let mut transaction = pool.begin().await?;
+    let mut count = 0;
+
+    for (table, columns) in &tables {
+        let Some(rows) = exported.get(table).and_then(Value::as_array) else {
+            continue;
+        };
+
+        let Some(first) = rows.first().and_then(Value::as_object) else {
+            continue;
+        };
+
+        // archives from older versions may lack newer columns, which are left at their defaults.
+        let columns = columns
+            .iter()
+            .filter(|column| first.contains_key(*column))
+            .collect::<Vec<_>>();
+
+        let names = columns
+            .iter()
+            .map(|column| format!("\"{column}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let values = columns
+            .iter()
+            .map(|column| format!("json_extract(value, '$.\"{column}\"')"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let result = sqlx::query(&format!(
+            "INSERT INTO \"{table}\" ({names}) SELECT {values} FROM json_each(?)"
+        ))
+        .bind(Value::Array(rows.clone()).to_string())
+        .execute(&mut *transaction)
+        .await?;
+
+        count += result.rows_affected() as usize;
+    }
+
+    transaction.commit().await?;
+
+    Ok((guild_id, count))
+}
+
+/// Rows copied per round trip when migrating to Postgres.
+const BATCH_SIZE: i64 = 1000;
+
+fn postgres_type(sqlite_type: &str) -> &'static str {
+    match sqlite_type.to_uppercase().as_str() {
+        "INTEGER" => "BIGINT",
+        "BOOLEAN" => "BOOLEAN",
+        _ => "TEXT",
+    }
+}
+
+/// Copies every table, including instance bookkeeping, into an empty Postgres database at `url`,
+/// printing progress as it goes. Once done, the row counts on both sides are compared.
+pub async fn to_postgres(pool: &Pool<Sqlite>, url: &str) -> Result<(), Error> {
+    let target = PgPoolOptions::new().connect(url).await?;
+
+    // sqlx's migration bookkeeping only describes the sqlite migrations, so it isn't copied.
+    let tables = tables(pool)
+        .await?
+        .into_iter()
+        .filter(|(table, _)| table != "_sqlx_migrations")
+        .collect::<Vec<_>>();
+
+    for (table, columns) in &tables {
+        let info = sqlx::query("SELECT name, type, pk FROM pragma_table_info(?) ORDER BY cid")
+            .bind(table)
+            .fetch_all(pool)
+            .await?;
+
+        let mut definitions = info
+            .iter()
+            .map(|row| {
+                format!(
+                    "\"{}\" {}",
+                    row.get::<String, _>("name"),
+                    postgres_type(&row.get::<String, _>("type"))
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut keys = info
+            .iter()
+            .filter(|row| row.get::<i64, _>("pk") > 0)
+            .collect::<Vec<_>>();
+        keys.sort_by_key(|row| row.get::<i64, _>("pk"));
+
+        if !keys.is_empty() {
+            definitions.push(format!(
+                "PRIMARY KEY ({})",
+                keys.iter()
+                    .map(|row| format!("\"{}\"", row.get::<String, _>("name")))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS \"{table}\" ({})",
+            definitions.join(", ")
+        ))
+        .execute(&target)
+        .await?;
+
+        let existing: i64 = sqlx::query(&format!("SELECT COUNT(*) AS count FROM \"{table}\""))
+            .fetch_one(&target)
+            .await?
+            .get("count");
+
+        if existing > 0 {
+            return Err(format!("{table} already has rows in the target database.").into());
+        }
+
+        let total: i64 = sqlx::query(&format!("SELECT COUNT(*) AS count FROM \"{table}\""))
+            .fetch_one(pool)
+            .await?
+            .get("count");
+
+        let fields = columns
+            .iter()
+            .map(|column| format!("'{column}', \"{column}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut copied = 0;
+
+        while copied < total {
+            let rows: String = sqlx::query(&format!(
+                "SELECT json_group_array(json_object({fields})) AS rows FROM (SELECT * FROM \"{table}\" ORDER BY rowid LIMIT ? OFFSET ?)"
+            ))
+            .bind(BATCH_SIZE)
+            .bind(copied)
+            .fetch_one(pool)
+            .await?
+            .get("rows");
+
+            // Postgres converts the JSON values to the column types itself, including sqlite's 0/1 booleans.
+            sqlx::query(&format!(
+                "INSERT INTO \"{table}\" SELECT * FROM json_populate_recordset(NULL::\"{table}\", $1::json)"
+            ))
+            .bind(rows)
+            .execute(&target)
+            .await?;
+
+            copied = (copied + BATCH_SIZE).min(total);
+            println!("{table}: {copied}/{total}");
+        }
+    }
+
+    let mut mismatches = Vec::new();
+
+    for (table, _) in &tables {
+        let expected: i64 = sqlx::query(&format!("SELECT COUNT(*) AS count FROM \"{table}\""))
+            .fetch_one(pool)
+            .await?
+            .get("count");
+        let actual: i64 = sqlx::query(&format!("SELECT COUNT(*) AS count FROM \"{table}\""))
+            .fetch_one(&target)
+            .await?
+            .get("count");
+
+        if expected != actual {
+            mismatches.push(format!("{table} ({actual}/{expected} rows)"));
+        }
+    }
+
+    if !mismatches.is_empty() {
+        return Err(format!("Verification failed for {}.", mismatches.join(", ")).into());
+    }
+
+    println!("Copied and verified {} tables.", tables.len());
+
+    Ok(())
+}
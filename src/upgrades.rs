@@ -0,0 +1,175 @@
+use serenity::async_trait;
+use sqlx::{Pool, Sqlite};
+
+use crate::{client::Error, logging::now};
+
+/// Data upgrades that can't be expressed as a plain SQL migration, e.g. cleanups over large tables.
+/// They run after migrations in batches, persisting a cursor after each one so an interrupted upgrade resumes
+/// where it left off instead of starting over.
+#[async_trait]
+trait Upgrade: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Processes one batch after `cursor` and returns the new cursor and how many rows were handled,
+    /// or `None` once there's nothing left.
+    async fn step(&self, pool: &Pool<Sqlite>, cursor: i64) -> Result<Option<(i64, i64)>, Error>;
+}
+
+fn upgrades() -> Vec<Box<dyn Upgrade>> {
+    // run in order, and a failed upgrade stops the ones after it.
+    vec![Box::new(PruneLogChannels), Box::new(UniqueLogChannels)]
+}
+
+pub async fn run(pool: &Pool<Sqlite>) -> Result<(), Error> {
+    for upgrade in upgrades() {
+        let name = upgrade.name();
+
+        sqlx::query!(
+            "INSERT INTO upgrades (name) VALUES (?) ON CONFLICT DO NOTHING",
+            name
+        )
+        .execute(pool)
+        .await?;
+
+        let state = sqlx::query!(
+            "SELECT cursor, processed, completed_at FROM upgrades WHERE name = ?",
+            name
+        )
+        .fetch_one(pool)
+        .await?;
+
+        if state.completed_at.is_some() {
+            continue;
+        }
+
+        let mut cursor = state.cursor;
+        let mut processed = state.processed;
+
+        if processed > 0 {
+            println!("Resuming upgrade {name} after {processed} rows");
+        } else {
+            println!("Running upgrade {name}");
+        }
+
+        while let Some((next_cursor, handled)) = upgrade.step(pool, cursor).await? {
+            cursor = next_cursor;
+            processed += handled;
+
+            sqlx::query!(
+                "UPDATE upgrades SET cursor = ?, processed = ? WHERE name = ?",
+                cursor,
+                processed,
+                name
+            )
+            .execute(pool)
+            .await?;
+
+            println!("Upgrade {name}: {processed} rows processed");
+        }
+
+        let completed_at = now() as i64;
+        sqlx::query!(
+            "UPDATE upgrades SET completed_at = ? WHERE name = ?",
+            completed_at,
+            name
+        )
+        .execute(pool)
+        .await?;
+
+        println!("Finished upgrade {name} ({processed} rows)");
+    }
+
+    Ok(())
+}
+
+/// The initial migration misspelled `PRIMARY KEY`, so `log_channels` could collect several rows per guild.
+/// Keeps the newest row per guild and clears channel columns that don't hold a valid ID.
+struct PruneLogChannels;
+
+const PRUNE_BATCH_SIZE: i64 = 500;
+
+fn is_valid_id(id: &str) -> bool {
+    id.parse::<u64>().is_ok_and(|id| id != 0)
+}
+
+#[async_trait]
+impl Upgrade for PruneLogChannels {
+    fn name(&self) -> &'static str {
+        "prune_log_channels"
+    }
+
+    async fn step(&self, pool: &Pool<Sqlite>, cursor: i64) -> Result<Option<(i64, i64)>, Error> {
+        let rows = sqlx::query!(
+            r#"SELECT rowid AS "rowid!: i64", guild_id, member_logs, chat_logs, server_logs FROM log_channels
+            WHERE rowid > ? ORDER BY rowid LIMIT ?"#,
+            cursor,
+            PRUNE_BATCH_SIZE
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let Some(last) = rows.last() else {
+            return Ok(None);
+        };
+        let next_cursor = last.rowid;
+        let handled = rows.len() as i64;
+
+        for row in rows {
+            let newer = sqlx::query!(
+                "SELECT COUNT(*) AS count FROM log_channels WHERE guild_id = ? AND rowid > ?",
+                row.guild_id,
+                row.rowid
+            )
+            .fetch_one(pool)
+            .await?;
+
+            if newer.count > 0 {
+                sqlx::query!("DELETE FROM log_channels WHERE rowid = ?", row.rowid)
+                    .execute(pool)
+                    .await?;
+                continue;
+            }
+
+            let member_logs = row.member_logs.filter(|id| is_valid_id(id));
+            let chat_logs = row.chat_logs.filter(|id| is_valid_id(id));
+            let server_logs = row.server_logs.filter(|id| is_valid_id(id));
+
+            sqlx::query!(
+                "UPDATE log_channels SET member_logs = ?, chat_logs = ?, server_logs = ? WHERE rowid = ?",
+                member_logs,
+                chat_logs,
+                server_logs,
+                row.rowid
+            )
+            .execute(pool)
+            .await?;
+        }
+
+        Ok(Some((next_cursor, handled)))
+    }
+}
+
+/// Adds the uniqueness the initial migration meant to give `guild_id`. Migrations run before upgrades, so this
+/// can't be one: the index would fail on the duplicates [`PruneLogChannels`] removes.
+struct UniqueLogChannels;
+
+#[async_trait]
+impl Upgrade for UniqueLogChannels {
+    fn name(&self) -> &'static str {
+        "unique_log_channels"
+    }
+
+    async fn step(&self, pool: &Pool<Sqlite>, cursor: i64) -> Result<Option<(i64, i64)>, Error> {
+        if cursor > 0 {
+            return Ok(None);
+        }
+
+        sqlx::query!(
+            "CREATE UNIQUE INDEX IF NOT EXISTS log_channels_guild_id ON log_channels (guild_id)"
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(Some((1, 0)))
+    }
+}
@@ -0,0 +1,122 @@
+use serenity::all::{ChannelId, GuildId, Message, MessageId, UserId};
+use sqlx::{Pool, Sqlite};
+
+use crate::logging::now;
+
+/// Records a member's report of `message`, returning the report's ID.
+pub async fn file(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    reporter: UserId,
+    message: &Message,
+) -> Result<i64, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let reporter = reporter.to_string();
+    let reported_user = message.author.id.to_string();
+    let channel_id = message.channel_id.to_string();
+    let message_id = message.id.to_string();
+    let now = now() as i64;
+
+    Ok(sqlx::query!(
+        "INSERT INTO user_reports (guild_id, reporter_id, reported_user_id, channel_id, message_id, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)",
+        guild_id,
+        reporter,
+        reported_user,
+        channel_id,
+        message_id,
+        now
+    )
+    .execute(pool)
+    .await?
+    .last_insert_rowid())
+}
+
+/// Links a report to the log it was filed as, so the log's triage status can be traced back to the reporter.
+pub async fn attach_log(
+    pool: &Pool<Sqlite>,
+    report_id: i64,
+    log: (ChannelId, MessageId),
+) -> Result<(), sqlx::Error> {
+    let log_message_id = log.1.to_string();
+
+    sqlx::query!(
+        "UPDATE user_reports SET log_message_id = ? WHERE id = ?",
+        log_message_id,
+        report_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// How many reports `reporter` filed since `since`, for rate limiting.
+pub async fn filed_since(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    reporter: UserId,
+    since: i64,
+) -> Result<i64, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let reporter = reporter.to_string();
+
+    Ok(sqlx::query!(
+        r#"SELECT COUNT(*) AS "count!: i64" FROM user_reports WHERE guild_id = ? AND reporter_id = ? AND created_at >= ?"#,
+        guild_id,
+        reporter,
+        since
+    )
+    .fetch_one(pool)
+    .await?
+    .count)
+}
+
+#[derive(Default)]
+pub struct ReporterStats {
+    pub filed: i64,
+    /// Reports whose log moderators triaged as a false positive.
+    pub false_positives: i64,
+    pub handled: i64,
+    /// Reports other members filed about this user.
+    pub received: i64,
+}
+
+pub async fn stats(
+    pool: &Pool<Sqlite>,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<ReporterStats, sqlx::Error> {
+    let guild_id = guild_id.to_string();
+    let user_id = user_id.to_string();
+
+    let filed = sqlx::query!(
+        r#"SELECT
+            COUNT(*) AS "filed!: i64",
+            COALESCE(SUM(log_messages.status = 'false_positive'), 0) AS "false_positives!: i64",
+            COALESCE(SUM(log_messages.status = 'handled'), 0) AS "handled!: i64"
+        FROM user_reports
+        LEFT JOIN log_messages ON log_messages.message_id = user_reports.log_message_id
+        WHERE user_reports.guild_id = ? AND user_reports.reporter_id = ?"#,
+        guild_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let received = sqlx::query!(
+        r#"SELECT COUNT(*) AS "count!: i64" FROM user_reports WHERE guild_id = ? AND reported_user_id = ?"#,
+        guild_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?
+    .count;
+
+    Ok(ReporterStats {
+        filed: filed.filed,
+        false_positives: filed.false_positives,
+        handled: filed.handled,
+        received,
+    })
+}
@@ -0,0 +1,55 @@
+use poise::serenity_prelude::*;
+
+use crate::{client::Data, member_counts, settings::keys};
+
+pub const DEFAULT_TEMPLATE: &str = "Welcome to {server}, {user}!";
+
+/// Fills in `{user}`, `{name}`, `{server}` and `{member_count}` in a welcome template.
+pub fn render(template: &str, user: &User, guild: &Guild, member_count: u64) -> String {
+    template
+        .replace("{user}", &format!("<@{}>", user.id))
+        .replace("{name}", user.global_name.as_deref().unwrap_or(&user.name))
+        .replace("{server}", &guild.name)
+        .replace("{member_count}", &member_count.to_string())
+}
+
+/// Posts the public welcome for `member`, if the guild turned welcomes on.
+/// Goes to the configured welcome channel, or the guild's system channel if none is set.
+pub async fn greet(ctx: &Context, data: &Data, member: &Member) {
+    let guild_id = member.guild_id;
+    let settings = &data.settings;
+
+    if !settings.get(guild_id, &keys::WELCOMES).await {
+        return;
+    }
+
+    let template = settings.get(guild_id, &keys::WELCOME_TEMPLATE).await;
+    let configured_channel = settings.get(guild_id, &keys::WELCOME_CHANNEL).await;
+    let member_count = member_counts::get(&data.pool, guild_id)
+        .await
+        .ok()
+        .flatten();
+
+    let Some((channel, content)) = guild_id.to_guild_cached(&ctx.cache).and_then(|guild| {
+        let channel = configured_channel.or(guild.system_channel_id)?;
+        let member_count = member_count.map_or(guild.member_count, |count| count as u64);
+        Some((
+            channel,
+            render(&template, &member.user, &guild, member_count),
+        ))
+    }) else {
+        return;
+    };
+
+    if let Err(error) = channel
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .content(content)
+                .allowed_mentions(CreateAllowedMentions::new().users([member.user.id])),
+        )
+        .await
+    {
+        println!("Failed to send welcome message in guild {guild_id}: {error}");
+    }
+}